@@ -0,0 +1,19 @@
+//! Benchmarks for the slowest days. Run with `cargo bench -p aoc2021`, adding
+//! `-- NAME` to run only the benchmarks whose names contain NAME.
+
+use aoc2021::{day19, day24};
+use aoc_util::{bench::Bencher, errors::AocResult, io::read_input_file, net::input_path};
+
+fn main() -> AocResult<()> {
+    let bencher = Bencher::from_args();
+
+    let input = read_input_file(&input_path(19))?;
+    bencher.bench("day 19 scanner alignment", || {
+        day19::reconstruct(&input).unwrap()
+    });
+
+    let input = read_input_file(&input_path(24))?;
+    bencher.bench("day 24 solve (largest)", || day24::part1(&input).unwrap());
+    bencher.bench("day 24 solve (smallest)", || day24::part2(&input).unwrap());
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use aoc_util::{
+    errors::{AocResult, LineContext},
+    solver::Solver,
+    window::{count_increases, windows_sum},
+};
+
+pub struct Day01;
+
+impl Solver for Day01 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<usize> {
+    Ok(count_increases(parse_input(input)?))
+}
+
+pub fn part2(input: &str) -> AocResult<usize> {
+    Ok(count_increases(windows_sum(parse_input(input)?, 3)))
+}
+
+fn parse_input(input: &str) -> AocResult<Vec<i64>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| line.trim().parse::<i64>().at_line(i + 1))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file};
+    use std::fs;
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        assert_eq!(part1(&fs::read_to_string(get_test_file(file!())?)?)?, 7);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        assert_eq!(part2(&fs::read_to_string(get_test_file(file!())?)?)?, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        assert_eq!(part1(&fs::read_to_string(get_input_file(file!())?)?)?, 1754);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        assert_eq!(part2(&fs::read_to_string(get_input_file(file!())?)?)?, 1789);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_depth() {
+        let err = part1("199\n2x0\n").unwrap_err();
+        assert!(err.to_string().starts_with("Line 2: "), "{err}");
+    }
+}
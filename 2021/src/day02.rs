@@ -0,0 +1,117 @@
+use aoc_util::{
+    errors::{AocResult, LineContext},
+    failure,
+    solver::Solver,
+};
+
+pub struct Day02;
+
+impl Solver for Day02 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<i64> {
+    let (mut pos, mut depth) = (0, 0);
+    for command in parse_input(input)? {
+        match command {
+            Command::Forward(v) => pos += v,
+            Command::Down(v) => depth += v,
+            Command::Up(v) => depth -= v,
+        }
+    }
+    Ok(depth * pos)
+}
+
+pub fn part2(input: &str) -> AocResult<i64> {
+    let (mut pos, mut depth, mut aim) = (0, 0, 0);
+    for command in parse_input(input)? {
+        match command {
+            Command::Forward(v) => {
+                pos += v;
+                depth += v * aim;
+            }
+            Command::Down(v) => aim += v,
+            Command::Up(v) => aim -= v,
+        }
+    }
+    Ok(depth * pos)
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Command {
+    Forward(i64),
+    Down(i64),
+    Up(i64),
+}
+
+fn parse_input(input: &str) -> AocResult<Vec<Command>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_command(line).at_line(i + 1))
+        .collect()
+}
+
+fn parse_command(line: &str) -> AocResult<Command> {
+    let (direction, value) = line
+        .split_once(' ')
+        .ok_or("Expected a direction and a value")?;
+    let value = value.parse()?;
+    match direction {
+        "forward" => Ok(Command::Forward(value)),
+        "down" => Ok(Command::Down(value)),
+        "up" => Ok(Command::Up(value)),
+        _ => failure!("Unknown direction {direction:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file};
+    use std::fs;
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        assert_eq!(part1(&fs::read_to_string(get_test_file(file!())?)?)?, 150);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        assert_eq!(
+            part1(&fs::read_to_string(get_input_file(file!())?)?)?,
+            2322630
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        assert_eq!(part2(&fs::read_to_string(get_test_file(file!())?)?)?, 900);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        assert_eq!(
+            part2(&fs::read_to_string(get_input_file(file!())?)?)?,
+            2105273490
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bad_commands() {
+        let err = part1("forward 5\nsideways 2").unwrap_err();
+        assert_eq!(err.to_string(), "Line 2: Unknown direction \"sideways\"");
+        assert!(part2("forward").is_err());
+        assert!(part2("up x").is_err());
+    }
+}
@@ -0,0 +1,75 @@
+use aoc_util::{
+    bits::{filter_by_bit_criteria, parse_binary_lines},
+    errors::AocResult,
+    solver::Solver,
+};
+
+pub struct Day03;
+
+impl Solver for Day03 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<u64> {
+    let lines: Vec<&str> = input.lines().collect();
+    let (values, width) = parse_binary_lines(&lines)?;
+    let mut gamma = 0;
+    for bit in 0..width {
+        let ones = values.iter().filter(|&&v| v >> bit & 1 == 1).count();
+        if 2 * ones > values.len() {
+            gamma |= 1 << bit;
+        }
+    }
+    let epsilon = !gamma & (u64::MAX >> (64 - width));
+    Ok(gamma * epsilon)
+}
+
+pub fn part2(input: &str) -> AocResult<u64> {
+    let lines: Vec<&str> = input.lines().collect();
+    let o2 = filter_by_bit_criteria(&lines, true)?;
+    let co2 = filter_by_bit_criteria(&lines, false)?;
+    Ok(o2 * co2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file};
+    use std::fs;
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        assert_eq!(part1(&fs::read_to_string(get_test_file(file!())?)?)?, 198);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        assert_eq!(
+            part1(&fs::read_to_string(get_input_file(file!())?)?)?,
+            2003336
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        assert_eq!(part2(&fs::read_to_string(get_test_file(file!())?)?)?, 230);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        assert_eq!(
+            part2(&fs::read_to_string(get_input_file(file!())?)?)?,
+            1877139
+        );
+        Ok(())
+    }
+}
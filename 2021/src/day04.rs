@@ -0,0 +1,85 @@
+use aoc_util::{
+    bingo::{play, Board},
+    ensure,
+    errors::{malformed, AocResult, LineContext},
+    parse::{numbered_blocks, split_parse},
+    solver::Solver,
+};
+
+pub struct Day04;
+
+impl Solver for Day04 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+fn parse_input(input: &str) -> AocResult<(Vec<i64>, Vec<Board>)> {
+    let mut blocks = numbered_blocks(input.lines()).into_iter();
+    let (lineno, numbers) = blocks.next().ok_or("Can't parse chosen numbers")?;
+    if numbers.len() != 1 {
+        return malformed(lineno + 1, "expected a blank line after the chosen numbers");
+    }
+    let chosen_numbers = split_parse::<i64>(&numbers[0], ",").at_line(lineno)?;
+    let boards = blocks
+        .map(|(lineno, rows)| Board::from_rows(&rows).at_line(lineno))
+        .collect::<AocResult<_>>()?;
+    Ok((chosen_numbers, boards))
+}
+
+pub fn part1(input: &str) -> AocResult<i64> {
+    let (chosen_numbers, boards) = parse_input(input)?;
+    let (_, score) = play(&chosen_numbers, boards).next().ok_or("No wins!")?;
+    Ok(score)
+}
+
+pub fn part2(input: &str) -> AocResult<i64> {
+    let (chosen_numbers, boards) = parse_input(input)?;
+    let n_boards = boards.len();
+    let wins: Vec<_> = play(&chosen_numbers, boards).collect();
+    ensure!(
+        wins.len() == n_boards,
+        "Only {} of {n_boards} boards win",
+        wins.len()
+    );
+    let (_, score) = wins.last().ok_or("No wins!")?;
+    Ok(*score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file};
+    use std::fs;
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        assert_eq!(part1(&fs::read_to_string(get_test_file(file!())?)?)?, 4512);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        assert_eq!(
+            part1(&fs::read_to_string(get_input_file(file!())?)?)?,
+            28082
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        assert_eq!(part2(&fs::read_to_string(get_test_file(file!())?)?)?, 1924);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        assert_eq!(part2(&fs::read_to_string(get_input_file(file!())?)?)?, 8224);
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+use aoc_util::{
+    cli::Args,
+    counter::Counter,
+    errors::{malformed, AocResult, LineContext},
+    io::read_input,
+    parse::capture,
+    segment::Segment,
+    solver::Solver,
+};
+
+pub struct Day05;
+
+impl Solver for Day05 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+/// Which vents `count_overlaps` considers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Angles {
+    /// Only horizontal and vertical vents; the rest are ignored.
+    AxisAligned,
+    /// Horizontal, vertical and 45-degree vents. Any other angle is an error.
+    Diagonal,
+    /// Vents at any angle, covering the integer points they pass through.
+    Any,
+}
+
+pub fn part1(input: &str) -> AocResult<usize> {
+    count_overlaps(&parse_input(input)?, Angles::AxisAligned)
+}
+
+pub fn part2(input: &str) -> AocResult<usize> {
+    count_overlaps(&parse_input(input)?, Angles::Diagonal)
+}
+
+/// Like `solver::run_cli`, but with `--any-angle` to let part 2 count vents at any angle.
+pub fn main() -> AocResult<()> {
+    let args = Args::from_env(&["--any-angle"], &[])?;
+    let segments = parse_input(&read_input(&args.input_path(5)?)?)?;
+    if args.wants_part(1) {
+        println!(
+            "Part 1: {}",
+            count_overlaps(&segments, Angles::AxisAligned)?
+        );
+    }
+    if args.wants_part(2) {
+        let angles = if args.flag("--any-angle") {
+            Angles::Any
+        } else {
+            Angles::Diagonal
+        };
+        println!("Part 2: {}", count_overlaps(&segments, angles)?);
+    }
+    Ok(())
+}
+
+/// One segment per line, like "0,9 -> 5,9".
+pub fn parse_input(input: &str) -> AocResult<Vec<Segment>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let (x1, y1, x2, y2) = capture("{},{} -> {},{}", line).at_line(i + 1)?;
+            Ok(Segment::new((x1, y1), (x2, y2)))
+        })
+        .collect()
+}
+
+/// The number of points covered by at least two of the `segments` allowed by `angles`.
+pub fn count_overlaps(segments: &[Segment], angles: Angles) -> AocResult<usize> {
+    let mut vent_map: Counter<(i64, i64)> = Counter::new();
+    for (i, s) in segments.iter().enumerate() {
+        let allowed = match angles {
+            Angles::AxisAligned => s.is_axis_aligned(),
+            Angles::Diagonal if !s.is_axis_aligned() && !s.is_diagonal() => {
+                return malformed(i + 1, "non-45-degree diagonal");
+            }
+            Angles::Diagonal | Angles::Any => true,
+        };
+        if allowed {
+            vent_map.extend(s.points());
+        }
+    }
+    Ok(vent_map.iter().filter(|&(_, n)| n >= 2).count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file};
+    use std::fs;
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        assert_eq!(part1(&fs::read_to_string(get_test_file(file!())?)?)?, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        assert_eq!(part1(&fs::read_to_string(get_input_file(file!())?)?)?, 4873);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        assert_eq!(part2(&fs::read_to_string(get_test_file(file!())?)?)?, 12);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        assert_eq!(
+            part2(&fs::read_to_string(get_input_file(file!())?)?)?,
+            19472
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn any_angle() -> AocResult<()> {
+        let segments = parse_input("0,0 -> 4,2\n0,1 -> 4,1\n2,0 -> 2,3")?;
+        assert_eq!(count_overlaps(&segments, Angles::AxisAligned)?, 1);
+        // (2, 1) is on all three.
+        assert_eq!(count_overlaps(&segments, Angles::Any)?, 1);
+        let err = count_overlaps(&segments, Angles::Diagonal).unwrap_err();
+        assert_eq!(err.to_string(), "Line 1: non-45-degree diagonal");
+
+        let segments = parse_input("0,0 -> 4,2\n2,1 -> 2,5\n4,2 -> 4,2")?;
+        // The first one crosses the other two at (2, 1) and (4, 2).
+        assert_eq!(count_overlaps(&segments, Angles::AxisAligned)?, 0);
+        assert_eq!(count_overlaps(&segments, Angles::Any)?, 2);
+        assert!(parse_input("0,0 -> 1").is_err());
+        let err = parse_input("0,0 -> 1,1\n0,9 -> 5,x").unwrap_err();
+        assert!(err.to_string().starts_with("Line 2, column 10:"), "{err}");
+        Ok(())
+    }
+}
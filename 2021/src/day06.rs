@@ -0,0 +1,132 @@
+use aoc_util::{
+    cli::Args,
+    ensure,
+    errors::{AocResult, LineContext},
+    io::read_input,
+    math::ModInt,
+    matrix::Scalar,
+    parse::split_parse,
+    recurrence::step_counts,
+    solver::Solver,
+};
+
+pub struct Day06;
+
+impl Solver for Day06 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<u64> {
+    solve(input, 80)
+}
+
+pub fn part2(input: &str) -> AocResult<u64> {
+    solve(input, 256)
+}
+
+/// Each fish's timer counts down to 0, then resets to 6 and spawns a new fish at 8.
+const LANTERNFISH: [&[usize]; 9] = [&[6, 8], &[0], &[1], &[2], &[3], &[4], &[5], &[6], &[7]];
+
+/// Like `solver::run_cli`, but with a `--days N` option to count the fish after any
+/// number of days. Counts are exact while they fit in a u128; `--mod-prime` gives them
+/// modulo 10^9 + 7 instead, for horizons beyond that.
+pub fn main() -> AocResult<()> {
+    let args = Args::from_env(&["--mod-prime"], &["--days"])?;
+    let input = read_input(&args.input_path(6)?)?;
+    let Some(days) = args.option("--days") else {
+        if args.wants_part(1) {
+            println!("Part 1: {}", part1(&input)?);
+        }
+        if args.wants_part(2) {
+            println!("Part 2: {}", part2(&input)?);
+        }
+        return Ok(());
+    };
+    let days: u64 = days.parse()?;
+    if args.flag("--mod-prime") {
+        let count: ModInt<1_000_000_007> = solve(&input, days)?;
+        println!("Fish after {days} days, mod 10^9 + 7: {count}");
+    } else {
+        let count: u128 = solve(&input, days)?;
+        println!("Fish after {days} days: {count}");
+    }
+    Ok(())
+}
+
+fn solve<T: Scalar + From<u64>>(input: &str, n_iters: u64) -> AocResult<T> {
+    let mut buckets = [0u64; 9];
+    let timers: Vec<usize> = split_parse(input.trim(), ",").at_line(1)?;
+    for t in timers {
+        ensure!(t < buckets.len(), "Timer {t} is out of range");
+        buckets[t] += 1;
+    }
+    let buckets: Vec<T> = buckets.into_iter().map(T::from).collect();
+    step_counts(&buckets, &LANTERNFISH, n_iters)?
+        .into_iter()
+        .try_fold(T::ZERO, |acc, x| acc.checked_add(x))
+        .ok_or_else(|| "Too many fish to count".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file};
+    use std::fs;
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        assert_eq!(
+            solve::<u64>(&fs::read_to_string(get_test_file(file!())?)?, 80)?,
+            5934
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        assert_eq!(
+            solve::<u64>(&fs::read_to_string(get_input_file(file!())?)?, 80)?,
+            355386
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        assert_eq!(
+            solve::<u64>(&fs::read_to_string(get_test_file(file!())?)?, 256)?,
+            26984457539
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        assert_eq!(
+            solve::<u64>(&fs::read_to_string(get_input_file(file!())?)?, 256)?,
+            1613415325809
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn huge_horizon() -> AocResult<()> {
+        let input = fs::read_to_string(get_test_file(file!())?)?;
+        // The exact count doesn't fit in a u64, but it can still be had modulo something.
+        assert!(solve::<u64>(&input, 1_000_000_000_000).is_err());
+        assert_eq!(
+            solve::<u128>(&input, 900)?,
+            62497692274899046719590493654936791
+        );
+        assert!(solve::<u128>(&input, 1000).is_err());
+        let count: ModInt<1_000_000_007> = solve(&input, 1_000_000_000_000)?;
+        assert_eq!(count.value(), 995077479);
+        Ok(())
+    }
+}
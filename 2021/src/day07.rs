@@ -0,0 +1,92 @@
+use aoc_util::{
+    errors::{AocResult, LineContext},
+    opt::{median, minimize_near_mean, total_cost},
+    parse::split_parse,
+    solver::Solver,
+};
+
+pub struct Day07;
+
+impl Solver for Day07 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<i64> {
+    solve(input, Cost::Linear)
+}
+
+pub fn part2(input: &str) -> AocResult<i64> {
+    solve(input, Cost::Quadratic)
+}
+
+enum Cost {
+    Linear,
+    Quadratic,
+}
+
+fn solve(input: &str, cost: Cost) -> AocResult<i64> {
+    let input: Vec<i64> = split_parse(input.trim(), ",").at_line(1)?;
+    let min_fuel = match cost {
+        // The median minimizes the total distance.
+        Cost::Linear => median(&input).map(|m| total_cost(&input, m, |d| d)),
+        // Fuel grows like the square of the distance, so the best position is near the
+        // mean.
+        Cost::Quadratic => minimize_near_mean(&input, |d| d * (d + 1) / 2).map(|(_, f)| f),
+    };
+    Ok(min_fuel.ok_or("No crabs?")?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file};
+    use std::fs;
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        assert_eq!(
+            solve(&fs::read_to_string(get_test_file(file!())?)?, Cost::Linear)?,
+            37
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        assert_eq!(
+            solve(&fs::read_to_string(get_input_file(file!())?)?, Cost::Linear)?,
+            364898
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        assert_eq!(
+            solve(
+                &fs::read_to_string(get_test_file(file!())?)?,
+                Cost::Quadratic
+            )?,
+            168
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        assert_eq!(
+            solve(
+                &fs::read_to_string(get_input_file(file!())?)?,
+                Cost::Quadratic
+            )?,
+            104149091
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+use aoc_util::{
+    ensure,
+    errors::{AocResult, LineContext},
+    failure, sevenseg,
+    solver::Solver,
+};
+
+pub struct Day08;
+
+impl Solver for Day08 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<u64> {
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    solve_part1(&lines)
+}
+
+pub fn part2(input: &str) -> AocResult<u64> {
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    solve_part2(&lines)
+}
+
+fn solve_part1(lines: &[String]) -> AocResult<u64> {
+    let segct2digs = [
+        vec![],
+        vec![],
+        vec![1],
+        vec![7],
+        vec![4],
+        vec![2, 3, 5],
+        vec![0, 6, 9],
+        vec![8],
+    ];
+
+    let res = lines
+        .iter()
+        .enumerate()
+        .map(|(i, l)| {
+            let encoded_digits = l
+                .trim()
+                .split('|')
+                .nth(1)
+                .ok_or("No second half?")
+                .at_line(i + 1)?
+                .trim();
+
+            Ok(encoded_digits.split(' ').fold(0, |acc, x| {
+                if segct2digs[x.len()].len() == 1 {
+                    acc + 1
+                } else {
+                    acc
+                }
+            }))
+        })
+        .sum::<AocResult<u64>>()?;
+
+    Ok(res)
+}
+
+fn solve_part2(lines: &[String]) -> AocResult<u64> {
+    let mut sum: u64 = 0;
+    for (i, l) in lines.iter().enumerate() {
+        let (signal_patterns, encoded_digits) = prep_line(l).at_line(i + 1)?;
+        let sigpat2digit = sevenseg::decode(&signal_patterns).at_line(i + 1)?;
+        let mut value = 0;
+        for digit in &encoded_digits {
+            let digit = sigpat2digit
+                .get(&sevenseg::canonicalize(digit))
+                .ok_or(format!("Unknown digit {digit:?}"))
+                .at_line(i + 1)?;
+            value = 10 * value + u64::from(*digit);
+        }
+        sum += value;
+    }
+
+    Ok(sum)
+}
+
+fn prep_line(line: &str) -> AocResult<(Vec<&str>, Vec<&str>)> {
+    let Some((signal_patterns, encoded_digits)) = line.trim().split_once('|') else {
+        failure!("Require exactly two input chunks");
+    };
+    ensure!(
+        !encoded_digits.contains('|'),
+        "Require exactly two input chunks"
+    );
+    Ok((
+        signal_patterns.split_whitespace().collect(),
+        encoded_digits.split_whitespace().collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file, read_lines};
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(solve_part1(&lines)?, 26);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        assert_eq!(solve_part1(&lines)?, 310);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(solve_part2(&lines)?, 61229);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        assert_eq!(solve_part2(&lines)?, 915941);
+        Ok(())
+    }
+}
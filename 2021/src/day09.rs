@@ -0,0 +1,185 @@
+use aoc_util::{
+    cli::Args,
+    ensure,
+    errors::AocResult,
+    grid::{Grid, NeighbourPattern},
+    io::read_input,
+    point::Point,
+    solver::Solver,
+};
+
+pub struct Day09;
+
+impl Solver for Day09 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<u64> {
+    part_1(&Grid::from_digit_matrix(input.lines())?)
+}
+
+pub fn part2(input: &str) -> AocResult<u64> {
+    part_2(&Grid::from_digit_matrix(input.lines())?)
+}
+
+pub fn find_low_points(grid: &Grid) -> AocResult<Vec<(Point, u64)>> {
+    let mut out = Vec::new();
+    for i in 0..grid.num_rows() {
+        for j in 0..grid.num_cols() {
+            let p = Point::new(i, j);
+            let centre = grid.at(p)?;
+            if grid
+                .neighbourhood(Point::new(i, j), NeighbourPattern::Compass4)?
+                .iter()
+                .all(|&x| {
+                    if let Some(neighbour) = x {
+                        centre < neighbour.1
+                    } else {
+                        true
+                    }
+                })
+            {
+                out.push((p, centre as u64));
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Heights of 9 aren't part of any basin.
+const RIDGE: u8 = 9;
+
+/// The points of the basin around each low point, in the order of `find_low_points`.
+fn basins(grid: &Grid) -> AocResult<Vec<Vec<Point>>> {
+    find_low_points(grid)?
+        .into_iter()
+        .map(|(p, _)| grid.flood_fill(p, NeighbourPattern::Compass4, |v| v != RIDGE))
+        .collect()
+}
+
+/// A grid the shape of `grid`, with the cells of the nth basin (in the order of
+/// `find_low_points`) labelled n, counting from 1, and the cells in no basin labelled 0.
+fn label_basins(grid: &Grid) -> AocResult<Grid> {
+    let basins = basins(grid)?;
+    ensure!(
+        basins.len() <= usize::from(u8::MAX),
+        "Too many basins to label: {}",
+        basins.len()
+    );
+    let mut labels = Grid::from_slice(
+        &vec![0; grid.num_rows() * grid.num_cols()],
+        grid.num_rows(),
+        grid.num_cols(),
+    )?;
+    for (label, basin) in (1..).zip(basins) {
+        for p in basin {
+            labels.set(p, label)?;
+        }
+    }
+    Ok(labels)
+}
+
+/// Draws `label_basins`' output with a character per basin, reusing them if there are
+/// more than 62 basins, and '#' between basins.
+fn draw_basins(labels: &Grid) -> AocResult<String> {
+    const SYMBOLS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    let mut out = String::new();
+    for i in 0..labels.num_rows() {
+        for j in 0..labels.num_cols() {
+            out.push(match labels.at(Point::new(i, j))? {
+                0 => '#',
+                label => char::from(SYMBOLS[usize::from(label - 1) % SYMBOLS.len()]),
+            });
+        }
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn part_1(grid: &Grid) -> AocResult<u64> {
+    let mut accum: u64 = 0;
+    for p in find_low_points(grid)? {
+        accum += p.1 + 1
+    }
+    Ok(accum)
+}
+
+fn part_2(grid: &Grid) -> AocResult<u64> {
+    let mut sizes: Vec<u64> = basins(grid)?.iter().map(|b| b.len() as u64).collect();
+    ensure!(sizes.len() >= 3, "Only {} basins", sizes.len());
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(sizes[..3].iter().product())
+}
+
+/// Like `solver::run_cli`, but with a flag to draw the basins.
+pub fn main() -> AocResult<()> {
+    let args = Args::from_env(&["--basins"], &[])?;
+    let input = read_input(&args.input_path(9)?)?;
+    let grid = Grid::from_digit_matrix(input.lines())?;
+    if args.flag("--basins") {
+        print!("{}", draw_basins(&label_basins(&grid)?)?);
+    }
+    if args.wants_part(1) {
+        println!("Part 1: {}", part_1(&grid)?);
+    }
+    if args.wants_part(2) {
+        println!("Part 2: {}", part_2(&grid)?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file};
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        let testfile = get_test_file(file!())?;
+        let grid: Grid = Grid::from_digit_matrix_file(&testfile)?;
+        assert_eq!(part_1(&grid)?, 15);
+        Ok(())
+    }
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        let testfile = get_test_file(file!())?;
+        let grid: Grid = Grid::from_digit_matrix_file(&testfile)?;
+        assert_eq!(part_2(&grid)?, 1134);
+        Ok(())
+    }
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        let testfile = get_input_file(file!())?;
+        let grid: Grid = Grid::from_digit_matrix_file(&testfile)?;
+        assert_eq!(part_1(&grid)?, 436);
+        Ok(())
+    }
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        let testfile = get_input_file(file!())?;
+        let grid: Grid = Grid::from_digit_matrix_file(&testfile)?;
+        assert_eq!(part_2(&grid)?, 1317792);
+        Ok(())
+    }
+    #[test]
+    fn labelled_basins() -> AocResult<()> {
+        let testfile = get_test_file(file!())?;
+        let grid: Grid = Grid::from_digit_matrix_file(&testfile)?;
+        let drawing = draw_basins(&label_basins(&grid)?)?;
+        assert_eq!(
+            drawing,
+            "00###11111\n\
+             0#222#1#11\n\
+             #22222#3#1\n\
+             22222#333#\n\
+             #2###33333\n"
+        );
+        Ok(())
+    }
+}
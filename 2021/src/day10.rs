@@ -0,0 +1,127 @@
+use aoc_util::{
+    brackets::{check, LineStatus},
+    errors::{failure, malformed_at, AocResult},
+    solver::Solver,
+};
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<u64> {
+    let mut out = 0;
+    for status in statuses(input)? {
+        if let LineStatus::Corrupted { found, .. } = status {
+            out += illegal_char_score(found)?;
+        }
+    }
+    Ok(out)
+}
+
+pub fn part2(input: &str) -> AocResult<u64> {
+    let mut scores: Vec<u64> = Vec::new();
+    for status in statuses(input)? {
+        if let LineStatus::Incomplete { missing } = status {
+            scores.push(completion_score(&missing)?);
+        }
+    }
+    if scores.is_empty() {
+        return failure("No incomplete lines");
+    }
+    let mid = scores.len() / 2;
+    Ok(*scores.select_nth_unstable(mid).1)
+}
+
+/// Checks every line, failing on any that has something other than brackets in it.
+fn statuses(input: &str) -> AocResult<Vec<LineStatus>> {
+    let mut out = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let status = check(line);
+        if let LineStatus::Invalid { column, found } = status {
+            return malformed_at(i + 1, column + 1, format!("Unknown character {found:?}"));
+        }
+        out.push(status);
+    }
+    Ok(out)
+}
+
+fn illegal_char_score(c: char) -> AocResult<u64> {
+    match c {
+        ')' => Ok(3),
+        ']' => Ok(57),
+        '}' => Ok(1197),
+        '>' => Ok(25137),
+        _ => failure("Unknown character"),
+    }
+}
+
+fn closing_char_score(c: char) -> AocResult<u64> {
+    match c {
+        ')' => Ok(1),
+        ']' => Ok(2),
+        '}' => Ok(3),
+        '>' => Ok(4),
+        _ => failure("Unknown character"),
+    }
+}
+
+fn completion_score(missing: &str) -> AocResult<u64> {
+    missing
+        .chars()
+        .try_fold(0, |acc, c| Ok(acc * 5 + closing_char_score(c)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file};
+    use std::fs;
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        assert_eq!(part1(&fs::read_to_string(get_test_file(file!())?)?)?, 26397);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        assert_eq!(
+            part1(&fs::read_to_string(get_input_file(file!())?)?)?,
+            345441
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        assert_eq!(
+            part2(&fs::read_to_string(get_test_file(file!())?)?)?,
+            288957
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        assert_eq!(
+            part2(&fs::read_to_string(get_input_file(file!())?)?)?,
+            3235371166
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bad_input() {
+        let err = part1("()\n(x)").unwrap_err().to_string();
+        assert!(err.contains('2') && err.contains('x'), "{err}");
+        assert!(part2("()").is_err());
+    }
+}
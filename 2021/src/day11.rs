@@ -0,0 +1,207 @@
+use aoc_util::{
+    cli::Args,
+    ensure,
+    errors::AocResult,
+    failure,
+    grid::{Grid, NeighbourPattern},
+    io::read_input,
+    point::Point,
+    solver::Solver,
+};
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<usize> {
+    let history = run(&parse_input(input)?, 100, 0)?;
+    Ok(history.flashes.iter().sum())
+}
+
+pub fn part2(input: &str) -> AocResult<usize> {
+    let history = run(&parse_input(input)?, 0, 1)?;
+    Ok(history.syncs[0])
+}
+
+/// Like `solver::run_cli`, but with flags to print the number of flashes in each step,
+/// or the first few steps on which all the octopuses flash, and the period between them.
+pub fn main() -> AocResult<()> {
+    let args = Args::from_env(&["--flashes"], &["--syncs"])?;
+    let grid = parse_input(&read_input(&args.input_path(11)?)?)?;
+    let num_syncs = match args.option("--syncs") {
+        Some(n) => n.parse()?,
+        None => 1,
+    };
+    let history = run(&grid, 100, num_syncs)?;
+    if args.flag("--flashes") {
+        for (step, flashes) in history.flashes.iter().enumerate() {
+            println!("Step {}: {flashes}", step + 1);
+        }
+    }
+    if args.option("--syncs").is_some() {
+        println!("Synchronized on steps: {:?}", history.syncs);
+        if let Some(period) = sync_period(&history)? {
+            println!("Period: {period}");
+        }
+    }
+    if args.wants_part(1) {
+        println!("Part 1: {}", history.flashes[..100].iter().sum::<usize>());
+    }
+    if args.wants_part(2) {
+        let first = history.syncs.first().ok_or("Never synchronized")?;
+        println!("Part 2: {first}");
+    }
+    Ok(())
+}
+
+/// Runs give up after this many steps without reaching what they were asked for.
+const MAX_STEPS: usize = 100_000;
+
+fn parse_input(input: &str) -> AocResult<Grid> {
+    Grid::from_digit_matrix(input.lines())
+}
+
+/// Advances the octopuses by one step, and returns the number that flashed.
+fn step(grid: &mut Grid) -> AocResult<usize> {
+    let mut to_flash: Vec<Point> = Vec::new();
+    for i in 0..grid.num_rows() {
+        for j in 0..grid.num_cols() {
+            let p = Point::new(i, j);
+            let v = grid.at(p)? + 1;
+            grid.set(p, v)?;
+            if v > 9 {
+                to_flash.push(p);
+            }
+        }
+    }
+    // Every energy is at least 1 after the increment above, so 0 marks the octopuses
+    // that have already flashed this step.
+    let mut flashes = 0;
+    while let Some(p) = to_flash.pop() {
+        if grid.at(p)? == 0 {
+            continue;
+        }
+        flashes += 1;
+        grid.set(p, 0)?;
+        for (q, v) in grid
+            .neighbourhood(p, NeighbourPattern::Compass8)?
+            .into_iter()
+            .flatten()
+        {
+            if v != 0 {
+                grid.set(q, v + 1)?;
+                if v + 1 > 9 {
+                    to_flash.push(q);
+                }
+            }
+        }
+    }
+    Ok(flashes)
+}
+
+/// What happened over a run of steps.
+#[derive(Debug)]
+struct History {
+    /// The number of flashes in each step; `flashes[0]` is step 1.
+    flashes: Vec<usize>,
+    /// The steps on which every octopus flashed.
+    syncs: Vec<usize>,
+}
+
+/// Steps a copy of `grid` until it has been through at least `min_steps` steps and
+/// synchronized at least `min_syncs` times.
+fn run(grid: &Grid, min_steps: usize, min_syncs: usize) -> AocResult<History> {
+    let mut grid = grid.clone();
+    let mut history = History {
+        flashes: Vec::new(),
+        syncs: Vec::new(),
+    };
+    while history.flashes.len() < min_steps || history.syncs.len() < min_syncs {
+        ensure!(
+            history.flashes.len() < MAX_STEPS,
+            "Only {} of {min_syncs} synchronizations in {MAX_STEPS} steps",
+            history.syncs.len()
+        );
+        let flashes = step(&mut grid)?;
+        history.flashes.push(flashes);
+        if flashes == grid.vec().len() {
+            history.syncs.push(history.flashes.len());
+        }
+    }
+    Ok(history)
+}
+
+/// The number of steps between synchronizations, or None if there were fewer than two.
+/// Once all the octopuses flash together, they all have the same energy, so from then
+/// on they should flash together every 10 steps; this checks that they do.
+fn sync_period(history: &History) -> AocResult<Option<usize>> {
+    let mut periods = history.syncs.windows(2).map(|w| w[1] - w[0]);
+    let Some(period) = periods.next() else {
+        return Ok(None);
+    };
+    if let Some(other) = periods.find(|&p| p != period) {
+        failure!("Synchronizations are {period} and {other} steps apart");
+    }
+    Ok(Some(period))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file};
+    use std::fs;
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        let testfile = fs::read_to_string(get_test_file(file!())?)?;
+        assert_eq!(part1(&testfile)?, 1656);
+        Ok(())
+    }
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        let testfile = fs::read_to_string(get_test_file(file!())?)?;
+        assert_eq!(part2(&testfile)?, 195);
+        Ok(())
+    }
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        let testfile = fs::read_to_string(get_input_file(file!())?)?;
+        assert_eq!(part1(&testfile)?, 1679);
+        Ok(())
+    }
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        let testfile = fs::read_to_string(get_input_file(file!())?)?;
+        assert_eq!(part2(&testfile)?, 519);
+        Ok(())
+    }
+    #[test]
+    fn flashes_and_syncs() -> AocResult<()> {
+        let testfile = fs::read_to_string(get_test_file(file!())?)?;
+        let history = run(&parse_input(&testfile)?, 10, 3)?;
+        assert_eq!(history.flashes[..10], [0, 35, 45, 16, 8, 1, 7, 24, 39, 29]);
+        assert_eq!(history.syncs, [195, 205, 215]);
+        assert_eq!(sync_period(&history)?, Some(10));
+
+        let once = History {
+            flashes: Vec::new(),
+            syncs: vec![195],
+        };
+        assert_eq!(sync_period(&once)?, None);
+        let uneven = History {
+            flashes: Vec::new(),
+            syncs: vec![1, 11, 12],
+        };
+        assert!(sync_period(&uneven).is_err());
+        assert!(parse_input("19\n9:").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,182 @@
+use aoc_util::{
+    bitset::SmallSet64, ensure, errors::AocResult, graph::UnweightedUndirectedGraph,
+    intern::NodeId, memo::Memo, solver::Solver,
+};
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<u64> {
+    part_1(&UnweightedUndirectedGraph::from_bufreader(
+        input.as_bytes(),
+    )?)
+}
+
+pub fn part2(input: &str) -> AocResult<u64> {
+    part_2(&UnweightedUndirectedGraph::from_bufreader(
+        input.as_bytes(),
+    )?)
+}
+
+/// It appears to be an unstated fact of this problem that large caves
+/// are never directly connected to other large caves, otherwise there would
+/// be an infinite number of paths.
+fn part_1(graph: &UnweightedUndirectedGraph) -> AocResult<u64> {
+    Ok(Caves::new(graph)?.count_paths_to_end(false))
+}
+
+fn part_2(graph: &UnweightedUndirectedGraph) -> AocResult<u64> {
+    Ok(Caves::new(graph)?.count_paths_to_end(true))
+}
+
+/// (Cave, small caves visited so far, whether a second visit is allowed).
+type PathKey = (NodeId, SmallSet64, bool);
+
+/// The cave graph, with everything the path search needs precomputed by node id so that
+/// the search itself never touches cave names.
+struct Caves<'a> {
+    graph: &'a UnweightedUndirectedGraph,
+    start: NodeId,
+    end: NodeId,
+    /// Each small cave's id in the visited set, by node id. None for large caves.
+    small_id: Vec<Option<usize>>,
+}
+
+impl<'a> Caves<'a> {
+    fn new(graph: &'a UnweightedUndirectedGraph) -> AocResult<Self> {
+        let mut n_small = 0;
+        let mut small_id = Vec::new();
+        for id in graph.node_ids() {
+            if graph.name(id).chars().all(char::is_lowercase) {
+                ensure!(
+                    n_small < SmallSet64::CAPACITY,
+                    "More than {} small caves",
+                    SmallSet64::CAPACITY
+                );
+                small_id.push(Some(n_small));
+                n_small += 1;
+            } else {
+                small_id.push(None);
+            }
+        }
+        Ok(Caves {
+            graph,
+            start: graph.id("start")?,
+            end: graph.id("end")?,
+            small_id,
+        })
+    }
+
+    fn count_paths_to_end(&self, allow_twice: bool) -> u64 {
+        self.count_paths_from(self.start, SmallSet64::new(), allow_twice, &mut Memo::new())
+    }
+
+    /// `visited` holds the small caves on the path so far. `allow_twice` is
+    /// true while one small cave may still be visited a second time. The count only
+    /// depends on these and `node`, so it's memoized on them.
+    fn count_paths_from(
+        &self,
+        node: NodeId,
+        mut visited: SmallSet64,
+        allow_twice: bool,
+        memo: &mut Memo<PathKey, u64>,
+    ) -> u64 {
+        if node == self.end {
+            return 1;
+        }
+        if let Some(id) = self.small_id[node.index()] {
+            visited.insert(id);
+        }
+        memo.entry_or_compute((node, visited, allow_twice), |memo| {
+            let mut count = 0;
+            for neighbour in self.graph.neighbours_by_id(node) {
+                if !self.was_visited(neighbour, visited) {
+                    count += self.count_paths_from(neighbour, visited, allow_twice, memo);
+                } else if allow_twice && neighbour != self.start {
+                    count += self.count_paths_from(neighbour, visited, false, memo);
+                }
+            }
+            count
+        })
+    }
+
+    /// Whether `node` is a small cave in `visited`. Large caves never count as visited.
+    fn was_visited(&self, node: NodeId, visited: SmallSet64) -> bool {
+        self.small_id[node.index()].is_some_and(|id| visited.contains(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file, get_test_file_n};
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        let graph = UnweightedUndirectedGraph::from_file(&get_test_file(file!())?)?;
+        assert_eq!(part_1(&graph)?, 226);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_small_test() -> AocResult<()> {
+        let graph = UnweightedUndirectedGraph::from_file(&get_test_file_n(file!(), 2)?)?;
+        assert_eq!(part_1(&graph)?, 10);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        let graph = UnweightedUndirectedGraph::from_file(&get_input_file(file!())?)?;
+        assert_eq!(part_1(&graph)?, 3679);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        let graph = UnweightedUndirectedGraph::from_file(&get_test_file(file!())?)?;
+        assert_eq!(part_2(&graph)?, 3509);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_small_test() -> AocResult<()> {
+        let graph = UnweightedUndirectedGraph::from_file(&get_test_file_n(file!(), 2)?)?;
+        assert_eq!(part_2(&graph)?, 36);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        let graph = UnweightedUndirectedGraph::from_file(&get_input_file(file!())?)?;
+        assert_eq!(part_2(&graph)?, 107395);
+        Ok(())
+    }
+
+    #[test]
+    fn hub_test() -> AocResult<()> {
+        // A large hub cave joined to 16 small ones: every ordering of every subset of the
+        // small caves is a path, far too many to walk one by one.
+        let mut input = String::from("start-HUB\nHUB-end\n");
+        for i in 0..16 {
+            input += &format!("HUB-c{}\n", (b'a' + i) as char);
+        }
+        assert_eq!(part1(&input)?, 56874039553217);
+
+        let letter = |i: u8| (b'a' + i) as char;
+        let too_many: String = (0..65)
+            .map(|i| format!("HUB-x{}{}\n", letter(i / 26), letter(i % 26)))
+            .collect();
+        assert!(part1(&(input + &too_many)).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+use aoc_util::{
+    errors::{AocResult, LineContext},
+    failure,
+    grid::Grid,
+    ocr,
+    parse::{capture, numbered_blocks, parse_pair},
+    point::Point,
+    solver::Solver,
+    transform::{fold_x, fold_y, render_points},
+};
+use std::collections::HashSet;
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<u64> {
+    let (paper, folds) = parse_input(input)?;
+    part_1(&paper, &folds)
+}
+
+pub fn part2(input: &str) -> AocResult<String> {
+    let (paper, folds) = parse_input(input)?;
+    ocr::recognize(&part_2(&paper, &folds)?)
+}
+
+type Paper = HashSet<Point>;
+
+#[derive(Debug)]
+enum Fold {
+    X(usize),
+    Y(usize),
+}
+
+type Folds = Vec<Fold>;
+
+fn parse_input(input: &str) -> AocResult<(Paper, Folds)> {
+    let [(dots_start, dots), (folds_start, folds)] = &numbered_blocks(input.lines())[..]
+    else {
+        failure!("Expected a block of dots and a block of folds");
+    };
+    let paper = dots
+        .iter()
+        .enumerate()
+        .map(|(i, line)| parse_dot(line).at_line(dots_start + i))
+        .collect::<AocResult<_>>()?;
+    let folds = folds
+        .iter()
+        .enumerate()
+        .map(|(i, line)| parse_fold(line).at_line(folds_start + i))
+        .collect::<AocResult<_>>()?;
+    Ok((paper, folds))
+}
+
+fn parse_dot(line: &str) -> AocResult<Point> {
+    let (x, y) = parse_pair(line, ",")?;
+    Ok(Point::new(y, x))
+}
+
+fn parse_fold(line: &str) -> AocResult<Fold> {
+    match capture("fold along {}={}", line)? {
+        ('x', coord) => Ok(Fold::X(coord)),
+        ('y', coord) => Ok(Fold::Y(coord)),
+        (axis, _) => failure!("Bad axis {axis}"),
+    }
+}
+
+fn fold(paper: &Paper, fold: &Fold) -> AocResult<Paper> {
+    match *fold {
+        Fold::X(col) => fold_x(paper, col),
+        Fold::Y(row) => fold_y(paper, row),
+    }
+}
+
+fn part_1(paper: &Paper, folds: &Folds) -> AocResult<u64> {
+    let first = folds.first().ok_or("No folds")?;
+    Ok(<u64>::try_from(fold(paper, first)?.len())?)
+}
+
+fn part_2(paper: &Paper, folds: &Folds) -> AocResult<Grid> {
+    let paper = folds.iter().try_fold(paper.clone(), |p, f| fold(&p, f))?;
+    render_points(&paper)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file};
+    use std::fs;
+
+    fn pixels(s: &str) -> AocResult<Grid> {
+        let lines: Vec<String> = s.lines().map(String::from).collect();
+        Grid::from_symbol_matrix(&lines, |c| match c {
+            '#' => Some(1),
+            '.' => Some(0),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        let (paper, folds) = parse_input(&fs::read_to_string(get_test_file(file!())?)?)?;
+        assert_eq!(part_1(&paper, &folds)?, 17);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        let (paper, folds) = parse_input(&fs::read_to_string(get_input_file(file!())?)?)?;
+        assert_eq!(part_1(&paper, &folds)?, 753);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        let (paper, folds) = parse_input(&fs::read_to_string(get_test_file(file!())?)?)?;
+        assert_eq!(
+            part_2(&paper, &folds)?,
+            pixels(
+                "\
+#####
+#...#
+#...#
+#...#
+#####
+"
+            )?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        let (paper, folds) = parse_input(&fs::read_to_string(get_input_file(file!())?)?)?;
+        assert_eq!(
+            part_2(&paper, &folds)?,
+            pixels(
+                "\
+#..#.####.#....####.#..#...##.###..#..#
+#..#....#.#....#....#..#....#.#..#.#.#.
+####...#..#....###..####....#.#..#.##..
+#..#..#...#....#....#..#....#.###..#.#.
+#..#.#....#....#....#..#.#..#.#.#..#.#.
+#..#.####.####.####.#..#..##..#..#.#..#
+"
+            )?
+        );
+        assert_eq!(
+            part2(&fs::read_to_string(get_input_file(file!())?)?)?,
+            "HZLEHJRK"
+        );
+        Ok(())
+    }
+}
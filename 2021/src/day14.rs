@@ -0,0 +1,142 @@
+use aoc_util::{
+    counter::Counter,
+    errors::{malformed, AocResult, LineContext},
+    failure,
+    solver::Solver,
+};
+use std::collections::HashMap;
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<usize> {
+    let (template, rules) = parse_input(input)?;
+    solve(&template, &rules, 10)
+}
+
+pub fn part2(input: &str) -> AocResult<usize> {
+    let (template, rules) = parse_input(input)?;
+    solve(&template, &rules, 40)
+}
+
+type Pair = [u8; 2];
+
+/// The element inserted between each pair.
+type Rules = HashMap<Pair, u8>;
+
+fn parse_input(input: &str) -> AocResult<(Vec<u8>, Rules)> {
+    let mut lines = input.lines();
+    let mut rules: Rules = Rules::new();
+
+    let template = lines.next().ok_or("No template?")?;
+    if !template.is_ascii() || template.is_empty() {
+        return malformed(1, format!("Template {template:?} isn't nonempty ascii"));
+    }
+
+    if !lines.next().ok_or("Nothing after template?")?.is_empty() {
+        return malformed(2, "No empty line between template and rules?");
+    }
+    for (i, line) in lines.enumerate() {
+        let (pair, insertion) = parse_rule(line).at_line(i + 3)?;
+        rules.insert(pair, insertion);
+    }
+    Ok((template.as_bytes().to_vec(), rules))
+}
+
+fn parse_rule(line: &str) -> AocResult<(Pair, u8)> {
+    let (pair, insertion) = line.split_once("->").ok_or("No \"->\"?")?;
+    let (pair, insertion) = (pair.trim(), insertion.trim());
+    let Ok(pair) = Pair::try_from(pair.as_bytes()) else {
+        failure!("Pair {pair:?} isn't two bytes");
+    };
+    let &[insertion] = insertion.as_bytes() else {
+        failure!("Insertion {insertion:?} isn't one byte");
+    };
+    Ok((pair, insertion))
+}
+
+fn step_pair_counts(pair_counts: &Counter<Pair>, rules: &Rules) -> AocResult<Counter<Pair>> {
+    let mut out = Counter::new();
+    for (p @ &[a, b], n) in pair_counts.iter() {
+        let &e = rules
+            .get(p)
+            .ok_or_else(|| format!("No rule for pair {}{}?", a as char, b as char))?;
+        out.add_n([a, e], n);
+        out.add_n([e, b], n);
+    }
+    Ok(out)
+}
+
+fn solve(template: &[u8], rules: &Rules, n_steps: u32) -> AocResult<usize> {
+    let mut pair_counts: Counter<Pair> = template.windows(2).map(|w| [w[0], w[1]]).collect();
+    for _ in 0..n_steps {
+        pair_counts = step_pair_counts(&pair_counts, rules)?;
+    }
+
+    // Every element but the last starts exactly one pair, and insertions never change
+    // the last element.
+    let mut element_counts: Counter<u8> = Counter::new();
+    for (&[a, _], n) in pair_counts.iter() {
+        element_counts.add_n(a, n);
+    }
+    element_counts.extend(template.last().copied());
+
+    let max_count = element_counts.max_by_count().ok_or("No max?")?.1;
+    let min_count = element_counts.min_by_count().ok_or("No min?")?.1;
+    Ok(max_count - min_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file};
+    use std::fs;
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        assert_eq!(part1(&fs::read_to_string(get_test_file(file!())?)?)?, 1588);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        assert_eq!(part1(&fs::read_to_string(get_input_file(file!())?)?)?, 2027);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        assert_eq!(
+            part2(&fs::read_to_string(get_test_file(file!())?)?)?,
+            2188189693529
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        assert_eq!(
+            part2(&fs::read_to_string(get_input_file(file!())?)?)?,
+            2265039461737
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bad_rules() {
+        assert!(part1("NN\n\nNN -> C").is_err());
+        assert!(part1("NN\n\nNN -> ").is_err());
+        assert!(part1("NN\n\nN -> C").is_err());
+        assert!(part1("NC\n\nNN -> C").is_err());
+        assert!(part1("").is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use aoc_util::{
+    errors::AocResult,
+    grid::{Grid, NeighbourPattern},
+    info,
+    point::Point,
+    search::SearchStats,
+    solver::Solver,
+};
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<u64> {
+    let grid = Grid::from_digit_matrix(input.lines())?;
+    let mut stats = SearchStats::new();
+    let answer = part_1(&grid, &mut stats)?;
+    info!("Part 1 search: {stats}");
+    Ok(answer)
+}
+
+pub fn part2(input: &str) -> AocResult<u64> {
+    let grid = Grid::from_digit_matrix(input.lines())?;
+    let mut stats = SearchStats::new();
+    let answer = part_2(&grid, &mut stats)?;
+    info!("Part 2 search: {stats}");
+    Ok(answer)
+}
+
+fn part_1(grid: &Grid, stats: &mut SearchStats) -> AocResult<u64> {
+    Ok(grid
+        .dial_shortest_path_with_stats(
+            Point::new(0, 0),
+            Point::new(grid.num_rows() - 1, grid.num_cols() - 1),
+            NeighbourPattern::Compass4,
+            stats,
+        )?
+        .1
+        .ok_or("No path")?)
+}
+
+fn part_2(grid: &Grid, stats: &mut SearchStats) -> AocResult<u64> {
+    // Each tile to the right or down adds 1 to the risk, wrapping from 9 back to 1.
+    let grid = grid.tiled(5, 5, |v, ti, tj| {
+        ((usize::from(v) + ti + tj + 8) % 9 + 1) as u8
+    })?;
+    part_1(&grid, stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file};
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        let testfile = get_test_file(file!())?;
+        let grid = Grid::from_digit_matrix_file(&testfile)?;
+        assert_eq!(part_1(&grid, &mut SearchStats::new())?, 40);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        let testfile = get_test_file(file!())?;
+        let grid = Grid::from_digit_matrix_file(&testfile)?;
+        assert_eq!(part_2(&grid, &mut SearchStats::new())?, 315);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        let testfile = get_input_file(file!())?;
+        let grid = Grid::from_digit_matrix_file(&testfile)?;
+        assert_eq!(part_1(&grid, &mut SearchStats::new())?, 458);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        let testfile = get_input_file(file!())?;
+        let grid = Grid::from_digit_matrix_file(&testfile)?;
+        assert_eq!(part_2(&grid, &mut SearchStats::new())?, 2800);
+        Ok(())
+    }
+}
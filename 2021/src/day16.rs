@@ -0,0 +1,796 @@
+use aoc_util::{
+    bits::{BitReader, BitVec},
+    cli::Args,
+    ensure,
+    errors::{failure, AocError, AocResult},
+    failure,
+    io::read_input,
+    solver::Solver,
+};
+use std::fmt;
+
+pub struct Day16;
+
+impl Solver for Day16 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<u64> {
+    part_1(input.trim())
+}
+
+/// Evaluates with the default `EvalOptions`; `main` exposes the others.
+pub fn part2(input: &str) -> AocResult<u128> {
+    part_2(input.trim(), EvalOptions::default())
+}
+
+#[derive(Debug)]
+enum PacketTypeId {
+    OperatorSum = 0,
+    OperatorProd = 1,
+    OperatorMin = 2,
+    OperatorMax = 3,
+    Literal = 4,
+    OperatorGt = 5,
+    OperatorLt = 6,
+    OperatorEq = 7,
+}
+
+impl TryFrom<u8> for PacketTypeId {
+    type Error = AocError;
+
+    fn try_from(v: u8) -> Result<Self, Self::Error> {
+        match v {
+            x if x == PacketTypeId::OperatorSum as u8 => Ok(PacketTypeId::OperatorSum),
+            x if x == PacketTypeId::OperatorProd as u8 => Ok(PacketTypeId::OperatorProd),
+            x if x == PacketTypeId::OperatorMin as u8 => Ok(PacketTypeId::OperatorMin),
+            x if x == PacketTypeId::OperatorMax as u8 => Ok(PacketTypeId::OperatorMax),
+            x if x == PacketTypeId::Literal as u8 => Ok(PacketTypeId::Literal),
+            x if x == PacketTypeId::OperatorGt as u8 => Ok(PacketTypeId::OperatorGt),
+            x if x == PacketTypeId::OperatorLt as u8 => Ok(PacketTypeId::OperatorLt),
+            x if x == PacketTypeId::OperatorEq as u8 => Ok(PacketTypeId::OperatorEq),
+            _ => Err(AocError::Unsupported(format!("packet type id {v}"))),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum Packet {
+    Literal(LiteralPacket),
+    Operator(OperatorPacket),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Header {
+    version: u8,
+    type_id: u8,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+struct LiteralPacket {
+    header: Header,
+    /// Literals may be up to 128 bits; whether that's acceptable is up to `eval`.
+    value: u128,
+}
+
+/// Short names for the packet types, indexed by type ID.
+const TYPE_NAMES: [&str; 8] = ["sum", "product", "min", "max", "literal", "gt", "lt", "eq"];
+
+impl Packet {
+    /// Renders the packet as an arithmetic expression, e.g. `max(sum(2, 3), 7) < 12`.
+    /// Comparisons are infix and parenthesized when nested; the other operators are
+    /// written as function calls.
+    fn to_expression_string(&self) -> AocResult<String> {
+        use PacketTypeId::*;
+        let packet = match self {
+            Packet::Literal(packet) => return Ok(packet.value.to_string()),
+            Packet::Operator(packet) => packet,
+        };
+        let type_id = packet.header.type_id.try_into()?;
+        let name = match type_id {
+            OperatorSum => "sum",
+            OperatorProd => "product",
+            OperatorMin => "min",
+            OperatorMax => "max",
+            OperatorGt => ">",
+            OperatorLt => "<",
+            OperatorEq => "==",
+            Literal => failure!("Literal type ID in an operator packet?"),
+        };
+        let is_comparison = matches!(type_id, OperatorGt | OperatorLt | OperatorEq);
+        let args = packet
+            .payload
+            .iter()
+            .map(|p| {
+                let s = p.to_expression_string()?;
+                Ok(if is_comparison && p.is_comparison() {
+                    format!("({s})")
+                } else {
+                    s
+                })
+            })
+            .collect::<AocResult<Vec<_>>>()?;
+        if is_comparison {
+            ensure!(
+                args.len() == 2,
+                "Comparison packet with {} != 2 sub-packets",
+                args.len()
+            );
+            Ok(format!("{} {name} {}", args[0], args[1]))
+        } else {
+            Ok(format!("{name}({})", args.join(", ")))
+        }
+    }
+
+    fn is_comparison(&self) -> bool {
+        use PacketTypeId::*;
+        match self {
+            Packet::Literal(_) => false,
+            Packet::Operator(packet) => matches!(
+                packet.header.type_id.try_into(),
+                Ok(OperatorGt | OperatorLt | OperatorEq)
+            ),
+        }
+    }
+
+    fn header(&self) -> Header {
+        match self {
+            Packet::Literal(packet) => packet.header,
+            Packet::Operator(packet) => packet.header,
+        }
+    }
+
+    /// Renders the packet tree one packet per line, with sub-packets indented under
+    /// their operator, e.g. `v3 lt (2 sub-packets)` followed by `  v6 literal 10`.
+    fn to_tree_string(&self) -> String {
+        let mut out = String::new();
+        self.write_tree(0, &mut out);
+        out
+    }
+
+    fn write_tree(&self, depth: usize, out: &mut String) {
+        let header = self.header();
+        let name = TYPE_NAMES[usize::from(header.type_id & 7)];
+        let indent = 2 * depth;
+        out.push_str(&format!("{:indent$}v{} {name}", "", header.version));
+        match self {
+            Packet::Literal(packet) => out.push_str(&format!(" {}\n", packet.value)),
+            Packet::Operator(packet) => {
+                out.push_str(&format!(" ({} sub-packets)\n", packet.payload.len()));
+                for p in &packet.payload {
+                    p.write_tree(depth + 1, out);
+                }
+            }
+        }
+    }
+
+    fn stats(&self) -> PacketStats {
+        let mut stats = PacketStats::default();
+        self.add_stats(1, &mut stats);
+        stats
+    }
+
+    fn add_stats(&self, depth: usize, stats: &mut PacketStats) {
+        stats.packets += 1;
+        stats.depth = stats.depth.max(depth);
+        stats.by_type[usize::from(self.header().type_id & 7)] += 1;
+        match self {
+            Packet::Literal(packet) => {
+                stats.max_literal = stats.max_literal.max(Some(packet.value));
+            }
+            Packet::Operator(packet) => {
+                for p in &packet.payload {
+                    p.add_stats(depth + 1, stats);
+                }
+            }
+        }
+    }
+}
+
+/// Summary figures for a packet tree, as printed by `main --stats`.
+#[derive(Debug, Default, Eq, PartialEq)]
+struct PacketStats {
+    packets: usize,
+    /// The number of packets on the longest path from the root; a lone literal has
+    /// depth 1.
+    depth: usize,
+    /// The number of packets of each type, indexed by type ID.
+    by_type: [usize; 8],
+    max_literal: Option<u128>,
+}
+
+impl fmt::Display for PacketStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Packets: {}", self.packets)?;
+        writeln!(f, "Depth: {}", self.depth)?;
+        for (name, count) in TYPE_NAMES.iter().zip(self.by_type) {
+            if count > 0 {
+                writeln!(f, "  {name}: {count}")?;
+            }
+        }
+        match self.max_literal {
+            Some(v) => write!(f, "Max literal: {v}"),
+            None => write!(f, "Max literal: none"),
+        }
+    }
+}
+
+/// How an operator packet encodes the extent of its sub-packets.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum LengthType {
+    /// Length type ID 0: the total length in bits of the sub-packets.
+    TotalBits,
+    /// Length type ID 1: the number of sub-packets.
+    Count,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+struct OperatorPacket {
+    header: Header,
+    length_type: LengthType,
+    payload: Vec<Packet>,
+}
+
+/// General packet structure:
+/// vvvttt[Literal specific | Operator specific]
+/// vvv encode the packet's version; ttt encode the packet's type.
+///
+/// All integer values are MSBit-first.
+///
+/// Literal specific:
+/// [(N-1) * 1[bbbb], 0[bbbb], M * 0
+/// where the encoded literal is formed by the concatenation of all bits b.
+/// The M trailing zeros are for padding, in order to make the number of bits
+/// b + the number of trailing zeros a multiple of 16.
+///
+/// Operator specific:
+/// [l[15 * t | 11 * p]SSS...]
+/// l is the Length Type ID bit:
+/// l = 0 => the following 15 bits encode the total length in bits of the operator packet's
+///          sub-packets.
+/// l = 1 => the following 11 bits encode the number of operator packet's sub-packets.
+///
+/// The remaining bits encode the operator packet's sub-packets.
+fn parse(bits: &str) -> AocResult<Packet> {
+    let bv = BitVec::from_hex_str(bits)?;
+    parse_packet(&mut bv.reader())
+}
+
+fn parse_packet(reader: &mut BitReader) -> AocResult<Packet> {
+    use PacketTypeId::*;
+
+    let version: u8 = reader.take(3)?.try_into()?;
+    let type_id: u8 = reader.take(3)?.try_into()?;
+    let header = Header { version, type_id };
+
+    match type_id.try_into()? {
+        OperatorSum | OperatorProd | OperatorMin | OperatorMax | OperatorGt | OperatorLt
+        | OperatorEq => parse_operator_packet(reader, &header),
+        Literal => parse_literal_packet(reader, &header),
+    }
+}
+
+fn parse_operator_packet(reader: &mut BitReader, header: &Header) -> AocResult<Packet> {
+    let mut payload = Vec::new();
+
+    let length_type = if reader.take_bit()? {
+        LengthType::Count
+    } else {
+        LengthType::TotalBits
+    };
+
+    if length_type == LengthType::TotalBits {
+        let len = reader.take(15)? as usize;
+        let end = reader.position() + len;
+        while reader.position() < end {
+            payload.push(parse_packet(reader)?);
+        }
+        ensure!(
+            reader.position() == end,
+            "Sub-packets overran their length of {len} bits by {}",
+            reader.position() - end
+        );
+    } else {
+        for _ in 0..reader.take(11)? {
+            payload.push(parse_packet(reader)?);
+        }
+    }
+    Ok(Packet::Operator(OperatorPacket {
+        header: *header,
+        length_type,
+        payload,
+    }))
+}
+
+fn parse_literal_packet(reader: &mut BitReader, header: &Header) -> AocResult<Packet> {
+    let mut value: u128 = 0;
+    let mut nibble_count = 0;
+    let mut keep_parsing = true;
+    while keep_parsing {
+        // One more nibble to parse even after keep_parsing becomes false.
+        keep_parsing = reader.take_bit()?;
+        value = (value << 4) | u128::from(reader.take(4)?);
+        nibble_count += 1;
+        ensure!(nibble_count <= 32, "Literal > 128 bits");
+    }
+
+    Ok(Packet::Literal(LiteralPacket {
+        header: *header,
+        value,
+    }))
+}
+
+/// The inverse of `parse`: serializes `packet` to hex, using each operator packet's
+/// `length_type`. The result is zero-padded to a whole number of hex digits.
+fn encode(packet: &Packet) -> AocResult<String> {
+    let mut bv = BitVec::new();
+    encode_packet(packet, &mut bv)?;
+    Ok(bv.to_hex_string())
+}
+
+fn encode_packet(packet: &Packet, bv: &mut BitVec) -> AocResult<()> {
+    match packet {
+        Packet::Literal(packet) => {
+            bv.push_bits(packet.header.version.into(), 3)?;
+            bv.push_bits(packet.header.type_id.into(), 3)?;
+            let nibble_count = (128 - packet.value.leading_zeros() as usize)
+                .div_ceil(4)
+                .max(1);
+            for i in (0..nibble_count).rev() {
+                bv.push_bit(i != 0);
+                bv.push_bits(((packet.value >> (4 * i)) & 0xF) as u64, 4)?;
+            }
+        }
+        Packet::Operator(packet) => {
+            bv.push_bits(packet.header.version.into(), 3)?;
+            bv.push_bits(packet.header.type_id.into(), 3)?;
+            match packet.length_type {
+                LengthType::TotalBits => {
+                    let mut sub_bits = BitVec::new();
+                    for sub in &packet.payload {
+                        encode_packet(sub, &mut sub_bits)?;
+                    }
+                    bv.push_bit(false);
+                    bv.push_bits(sub_bits.len() as u64, 15)?;
+                    bv.append(&sub_bits);
+                }
+                LengthType::Count => {
+                    bv.push_bit(true);
+                    bv.push_bits(packet.payload.len() as u64, 11)?;
+                    for sub in &packet.payload {
+                        encode_packet(sub, bv)?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn sum_versions(packet: &Packet) -> AocResult<u64> {
+    match packet {
+        Packet::Literal(packet) => Ok(packet.header.version as u64),
+        Packet::Operator(packet) => {
+            let mut sum = packet.header.version as u64;
+            for packet in &packet.payload {
+                sum += sum_versions(packet)?;
+            }
+            Ok(sum)
+        }
+    }
+}
+
+fn part_1(bits: &str) -> AocResult<u64> {
+    let top_level_packet = parse(bits)?;
+    sum_versions(&top_level_packet)
+}
+
+/// What `eval` does when a value doesn't fit.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+enum Overflow {
+    /// Fail with an error.
+    #[default]
+    Checked,
+    /// Clamp to the largest representable value.
+    Saturating,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct EvalOptions {
+    overflow: Overflow,
+    /// Allow values (literals and intermediate results) up to `u128::MAX` rather than
+    /// `u64::MAX`.
+    wide: bool,
+}
+
+impl EvalOptions {
+    fn max(&self) -> u128 {
+        if self.wide {
+            u128::MAX
+        } else {
+            u64::MAX.into()
+        }
+    }
+
+    /// Applies the overflow policy to the result of an operation: `None` means the
+    /// result overflowed u128 itself.
+    fn fit(&self, value: Option<u128>, what: &str) -> AocResult<u128> {
+        match (value.filter(|v| *v <= self.max()), self.overflow) {
+            (Some(v), _) => Ok(v),
+            (None, Overflow::Saturating) => Ok(self.max()),
+            (None, Overflow::Checked) => failure(format!(
+                "{what} overflowed {} bits",
+                if self.wide { 128 } else { 64 }
+            )),
+        }
+    }
+}
+
+fn eval(packet: &Packet, opts: EvalOptions) -> AocResult<u128> {
+    use PacketTypeId::*;
+    let packet = match packet {
+        Packet::Literal(packet) => return opts.fit(Some(packet.value), "Literal"),
+        Packet::Operator(packet) => packet,
+    };
+    let args = packet
+        .payload
+        .iter()
+        .map(|p| eval(p, opts))
+        .collect::<AocResult<Vec<_>>>()?;
+    let type_id = packet.header.type_id.try_into()?;
+    match type_id {
+        OperatorSum => args
+            .iter()
+            .try_fold(0, |acc: u128, v| opts.fit(acc.checked_add(*v), "Sum")),
+        OperatorProd => args
+            .iter()
+            .try_fold(1, |acc: u128, v| opts.fit(acc.checked_mul(*v), "Product")),
+        OperatorMin => Ok(*args.iter().min().ok_or("No min?")?),
+        OperatorMax => Ok(*args.iter().max().ok_or("No max?")?),
+        Literal => failure("Literal type ID in an operator packet?"),
+        OperatorGt | OperatorLt | OperatorEq => {
+            ensure!(
+                args.len() == 2,
+                "{:?} packet with {} != 2 sub-packets",
+                type_id,
+                args.len()
+            );
+            let result = match type_id {
+                OperatorGt => args[0] > args[1],
+                OperatorLt => args[0] < args[1],
+                _ => args[0] == args[1],
+            };
+            Ok(result.into())
+        }
+    }
+}
+
+fn part_2(bits: &str, opts: EvalOptions) -> AocResult<u128> {
+    let top_level_packet = parse(bits)?;
+    eval(&top_level_packet, opts)
+}
+
+/// Like `solver::run_cli`, but with flags to dump the packet (as an expression, a tree,
+/// or summary statistics) instead, or to change how part 2 handles overflow.
+pub fn main() -> AocResult<()> {
+    let args = Args::from_env(
+        &[
+            "--dump-ast",
+            "--dump-tree",
+            "--saturating",
+            "--stats",
+            "--wide",
+        ],
+        &[],
+    )?;
+    let input = read_input(&args.input_path(16)?)?;
+    let line = input.lines().next().ok_or("No input?")?;
+    debug_assert_eq!(
+        parse(&encode(&parse(line)?)?)?,
+        parse(line)?,
+        "Packet encoding doesn't round trip"
+    );
+    if args.flag("--dump-ast") || args.flag("--dump-tree") || args.flag("--stats") {
+        let packet = parse(line)?;
+        if args.flag("--dump-tree") {
+            print!("{}", packet.to_tree_string());
+        }
+        if args.flag("--stats") {
+            println!("{}", packet.stats());
+        }
+        if args.flag("--dump-ast") {
+            println!("{}", packet.to_expression_string()?);
+        }
+        return Ok(());
+    }
+    if args.wants_part(1) {
+        println!("Part 1: {}", part_1(line)?);
+    }
+    let opts = EvalOptions {
+        overflow: if args.flag("--saturating") {
+            Overflow::Saturating
+        } else {
+            Overflow::Checked
+        },
+        wide: args.flag("--wide"),
+    };
+    if args.wants_part(2) {
+        println!("Part 2: {}", part_2(line, opts)?);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file, read_lines};
+
+    fn literal(version: u8, value: u128) -> Packet {
+        Packet::Literal(LiteralPacket {
+            header: Header {
+                version,
+                type_id: PacketTypeId::Literal as u8,
+            },
+            value,
+        })
+    }
+
+    fn operator(version: u8, type_id: PacketTypeId, length_type: LengthType) -> Packet {
+        Packet::Operator(OperatorPacket {
+            header: Header {
+                version,
+                type_id: type_id as u8,
+            },
+            length_type,
+            payload: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn encode_examples() -> AocResult<()> {
+        assert_eq!(encode(&literal(6, 2021))?, "D2FE28");
+        assert_eq!(encode(&literal(0, 0))?, "100");
+
+        let mut lt = operator(1, PacketTypeId::OperatorLt, LengthType::TotalBits);
+        let mut max = operator(7, PacketTypeId::OperatorMax, LengthType::Count);
+        if let (Packet::Operator(lt), Packet::Operator(max)) = (&mut lt, &mut max) {
+            lt.payload = vec![literal(6, 10), literal(2, 20)];
+            max.payload = vec![literal(2, 1), literal(4, 2), literal(1, 3)];
+        }
+        // The puzzle's examples, minus their trailing zero padding.
+        assert_eq!(encode(&lt)?, "38006F4529120");
+        assert_eq!(encode(&max)?, "EE00D40C82306");
+        assert_eq!(parse(&encode(&lt)?)?, lt);
+        assert_eq!(parse(&encode(&max)?)?, max);
+
+        let big = literal(3, u128::MAX);
+        assert_eq!(parse(&encode(&big)?)?, big);
+        Ok(())
+    }
+
+    #[test]
+    fn expression_string() -> AocResult<()> {
+        let exprs = read_lines(&get_test_file(file!())?)?
+            .iter()
+            .skip(4)
+            .map(|line| parse(line)?.to_expression_string())
+            .collect::<AocResult<Vec<_>>>()?;
+        assert_eq!(
+            exprs,
+            vec![
+                "sum(1, 2)",
+                "product(6, 9)",
+                "min(7, 8, 9)",
+                "max(7, 8, 9)",
+                "5 < 15",
+                "5 > 15",
+                "5 == 15",
+                "sum(1, 3) == product(2, 2)",
+                "2021",
+            ]
+        );
+
+        let mut lt = operator(0, PacketTypeId::OperatorLt, LengthType::Count);
+        let mut eq = operator(0, PacketTypeId::OperatorEq, LengthType::Count);
+        if let (Packet::Operator(lt), Packet::Operator(eq)) = (&mut lt, &mut eq) {
+            lt.payload = vec![literal(0, 1), literal(0, 2)];
+            eq.payload = vec![literal(0, 3)];
+        }
+        if let Packet::Operator(eq_inner) = &mut eq {
+            eq_inner.payload.push(lt);
+        }
+        assert_eq!(eq.to_expression_string()?, "3 == (1 < 2)");
+        Ok(())
+    }
+
+    #[test]
+    fn stats_and_tree() -> AocResult<()> {
+        let stats = parse("8A004A801A8002F478")?.stats();
+        assert_eq!(stats.packets, 4);
+        assert_eq!(stats.depth, 4);
+        assert_eq!(stats.by_type[PacketTypeId::Literal as usize], 1);
+        assert_eq!(stats.max_literal, Some(15));
+
+        let packet = parse("9C0141080250320F1802104A08")?;
+        assert_eq!(
+            packet.stats().to_string(),
+            "Packets: 7\nDepth: 3\n  sum: 1\n  product: 1\n  literal: 4\n  eq: 1\n\
+             Max literal: 3"
+        );
+        assert_eq!(
+            packet.to_tree_string(),
+            "v4 eq (2 sub-packets)\n\
+             \x20 v2 sum (2 sub-packets)\n\
+             \x20   v2 literal 1\n\
+             \x20   v4 literal 3\n\
+             \x20 v6 product (2 sub-packets)\n\
+             \x20   v0 literal 2\n\
+             \x20   v2 literal 2\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn eval_overflow() -> AocResult<()> {
+        let checked = EvalOptions::default();
+        let saturating = EvalOptions {
+            overflow: Overflow::Saturating,
+            wide: false,
+        };
+        let wide = EvalOptions {
+            overflow: Overflow::Checked,
+            wide: true,
+        };
+
+        let mut prod = operator(0, PacketTypeId::OperatorProd, LengthType::Count);
+        if let Packet::Operator(p) = &mut prod {
+            p.payload = vec![literal(0, 1 << 40), literal(0, 1 << 40)];
+        }
+        assert!(eval(&prod, checked).is_err());
+        assert_eq!(eval(&prod, saturating)?, u64::MAX.into());
+        assert_eq!(eval(&prod, wide)?, 1 << 80);
+
+        let big = literal(0, u128::from(u64::MAX) + 1);
+        assert!(eval(&big, checked).is_err());
+        assert_eq!(eval(&big, saturating)?, u64::MAX.into());
+        assert_eq!(eval(&big, wide)?, u128::from(u64::MAX) + 1);
+        // Parsing itself accepts literals of up to 128 bits.
+        assert_eq!(
+            eval(&parse(&encode(&big)?)?, wide)?,
+            u128::from(u64::MAX) + 1
+        );
+
+        let mut sum = operator(0, PacketTypeId::OperatorSum, LengthType::Count);
+        if let Packet::Operator(p) = &mut sum {
+            p.payload = vec![literal(0, u128::MAX), literal(0, 1)];
+        }
+        assert!(eval(&sum, wide).is_err());
+        let wide_saturating = EvalOptions {
+            overflow: Overflow::Saturating,
+            wide: true,
+        };
+        assert_eq!(eval(&sum, wide_saturating)?, u128::MAX);
+        Ok(())
+    }
+
+    #[test]
+    fn encode_round_trip() -> AocResult<()> {
+        for line in read_lines(&get_test_file(file!())?)? {
+            let packet = parse(&line)?;
+            assert_eq!(parse(&encode(&packet)?)?, packet);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_test_1() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_1(&lines[0])?, 16);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_test_2() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_1(&lines[1])?, 12);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_test_3() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_1(&lines[2])?, 23);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_test_4() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_1(&lines[3])?, 31);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        assert_eq!(part_1(&lines[0])?, 971);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test_1() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_2(&lines[4], EvalOptions::default())?, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test_2() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_2(&lines[5], EvalOptions::default())?, 54);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test_3() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_2(&lines[6], EvalOptions::default())?, 7);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test_4() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_2(&lines[7], EvalOptions::default())?, 9);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test_5() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_2(&lines[8], EvalOptions::default())?, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test_6() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_2(&lines[9], EvalOptions::default())?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test_7() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_2(&lines[10], EvalOptions::default())?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test_8() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_2(&lines[11], EvalOptions::default())?, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test_9() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_2(&lines[12], EvalOptions::default())?, 2021);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        assert_eq!(part_2(&lines[0], EvalOptions::default())?, 831996589851);
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+use aoc_util::{
+    errors::{AocResult, LineContext},
+    failure,
+    parse::capture,
+    solver::Solver,
+};
+use std::cmp::max;
+
+pub struct Day17;
+
+impl Solver for Day17 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<i64> {
+    let target = parse_input(input)?;
+    match target.max_apex() {
+        Some(apex) => Ok(apex),
+        None => {
+            let vs = target.hits();
+            let apex = vs.iter().map(|&(_, vy)| triangle(vy.max(0))).max();
+            Ok(apex.ok_or("No velocity hits the target")?)
+        }
+    }
+}
+
+pub fn part2(input: &str) -> AocResult<usize> {
+    Ok(parse_input(input)?.hits().len())
+}
+
+/// The target area, which lies right of and below the launcher.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Target {
+    min_x: i64,
+    max_x: i64,
+    min_y: i64,
+    max_y: i64,
+}
+
+fn parse_input(input: &str) -> AocResult<Target> {
+    let line = input.lines().next().ok_or("No input?")?;
+    parse_target(line).at_line(1)
+}
+
+fn parse_target(line: &str) -> AocResult<Target> {
+    let (min_x, max_x, min_y, max_y): (i64, i64, i64, i64) =
+        capture("target area: x={}..{}, y={}..{}", line)?;
+    if min_x <= 0 || min_x > max_x || max_y >= 0 || min_y > max_y {
+        failure!("Assumption that 0 < x_min <= x_max and y_min <= y_max < 0 broken");
+    }
+    Ok(Target {
+        min_x,
+        max_x,
+        min_y,
+        max_y,
+    })
+}
+
+/// 1 + 2 + ... + n: how far a probe launched at speed `n` goes before drag (or
+/// gravity) stops it.
+fn triangle(n: i64) -> i64 {
+    n * (n + 1) / 2
+}
+
+impl Target {
+    /// The highest apex of any hit, worked out directly. A probe launched upwards at
+    /// `vy` comes back down through y = 0 at speed `vy + 1`, so the fastest that can
+    /// still hit has `vy = -min_y - 1`. That only works if some `vx` comes to rest
+    /// over the target, since the probe is in the air for a long time; if none does,
+    /// returns None.
+    fn max_apex(&self) -> Option<i64> {
+        let vy = -self.min_y - 1;
+        let stalls = (1..=self.max_x)
+            .take_while(|&vx| triangle(vx) <= self.max_x)
+            .any(|vx| triangle(vx) >= self.min_x && vx <= 2 * vy + 2);
+        stalls.then(|| triangle(vy))
+    }
+
+    /// Every initial velocity that puts the probe in the target after some step.
+    fn hits(&self) -> Vec<(i64, i64)> {
+        // Slower and it never reaches min_x; faster and it overshoots on the first step.
+        let vxs = (1..=self.max_x).filter(|&vx| triangle(vx) >= self.min_x);
+        // Faster downwards overshoots on the first step; faster upwards overshoots on
+        // the way back down (see `max_apex`).
+        vxs.flat_map(|vx| (self.min_y..-self.min_y).map(move |vy| (vx, vy)))
+            .filter(|&v| self.is_hit(v))
+            .collect()
+    }
+
+    fn is_hit(&self, (mut vx, mut vy): (i64, i64)) -> bool {
+        let (mut x, mut y) = (0, 0);
+        while x <= self.max_x && y >= self.min_y {
+            if x >= self.min_x && y <= self.max_y {
+                return true;
+            }
+            x += vx;
+            y += vy;
+            vx = max(vx - 1, 0);
+            vy -= 1;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file};
+    use std::fs;
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        assert_eq!(part1(&fs::read_to_string(get_test_file(file!())?)?)?, 45);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        assert_eq!(part1(&fs::read_to_string(get_input_file(file!())?)?)?, 5565);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        assert_eq!(part2(&fs::read_to_string(get_test_file(file!())?)?)?, 112);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        assert_eq!(part2(&fs::read_to_string(get_input_file(file!())?)?)?, 2118);
+        Ok(())
+    }
+
+    #[test]
+    fn apex() -> AocResult<()> {
+        // The analytic apex agrees with the highest of all the hits.
+        let target = parse_target("target area: x=20..30, y=-10..-5")?;
+        let highest = target
+            .hits()
+            .iter()
+            .map(|&(_, vy)| triangle(vy.max(0)))
+            .max();
+        assert_eq!(target.max_apex(), highest);
+        // No vx comes to rest in 16..=20, so the analytic shortcut doesn't apply.
+        let target = parse_target("target area: x=16..20, y=-10..-5")?;
+        assert_eq!(target.max_apex(), None);
+        assert!(!target.hits().is_empty());
+        assert!(parse_target("target area: x=20..30, y=5..10").is_err());
+        Ok(())
+    }
+}
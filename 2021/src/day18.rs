@@ -0,0 +1,320 @@
+use aoc_util::{
+    binarytree::{Node, NodeWrapper},
+    ensure,
+    errors::{malformed, AocResult, LineContext},
+    parse::numbered_blocks,
+    solver::Solver,
+};
+use std::cmp;
+
+pub struct Day18;
+
+impl Solver for Day18 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<i64> {
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    part_1(parse_input(&lines)?.remove(0))
+}
+
+pub fn part2(input: &str) -> AocResult<i64> {
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    #[cfg(not(feature = "parallel"))]
+    return part_2(parse_input(&lines)?.remove(0));
+    #[cfg(feature = "parallel")]
+    par_part_2(parse_input(&lines)?.remove(0))
+}
+
+fn add(left: &NodeWrapper, right: &NodeWrapper) -> AocResult<NodeWrapper> {
+    let sum = NodeWrapper::from(Node::new(None));
+    sum.set_left(Some(left));
+    sum.set_right(Some(right));
+    reduce(&sum)?;
+    Ok(sum)
+}
+
+/// Reduces `node` in place. The leaves are listed once, with their depths, and the list
+/// is patched as pairs explode and numbers split, so nothing is re-walked from the root.
+fn reduce(node: &NodeWrapper) -> AocResult<()> {
+    let mut leaves: Vec<(NodeWrapper, usize)> = node.leaves().collect();
+    explode_all(&mut leaves)?;
+    split_all(&mut leaves)?;
+    if cfg!(debug_assertions) {
+        node.validate()?;
+    }
+    Ok(())
+}
+
+/// Explodes every pair nested inside four pairs, left to right. Only a fresh sum can have
+/// these, and exploding one never makes another.
+fn explode_all(leaves: &mut Vec<(NodeWrapper, usize)>) -> AocResult<()> {
+    let mut i = 0;
+    while i < leaves.len() {
+        if leaves[i].1 < 5 {
+            i += 1;
+            continue;
+        }
+        // Pairs nested inside four pairs always consist of two regular numbers, so this
+        // is the left half of the pair, and the next leaf is the right half.
+        let left = &leaves[i].0;
+        let pair = left.get_parent().ok_or("Exploding number has no parent")?;
+        let right = left.sibling().ok_or("Exploding number has no sibling")?;
+        ensure!(
+            leaves.get(i + 1).is_some_and(|(r, _)| r.ptr_eq(&right)),
+            "Exploding pair isn't regular"
+        );
+        let left_val = left.get_data().ok_or("Exploding pair isn't regular")?;
+        let right_val = right.get_data().ok_or("Exploding pair isn't regular")?;
+        add_to_leaf(leaves, i.checked_sub(1), left_val)?;
+        add_to_leaf(leaves, Some(i + 2), right_val)?;
+
+        let zero: NodeWrapper = Node::new(Some(0)).into();
+        pair.replace_with(&zero)?;
+        leaves.splice(i..i + 2, [(zero, 4)]);
+        i += 1;
+    }
+    Ok(())
+}
+
+/// Splits regular numbers of 10 or more, leftmost first. Splitting a number inside four
+/// pairs makes a pair that explodes straight away, which leaves 0 in its place and can
+/// push the number to its left to 10 or more.
+fn split_all(leaves: &mut Vec<(NodeWrapper, usize)>) -> AocResult<()> {
+    let mut i = 0;
+    while i < leaves.len() {
+        let (leaf, depth) = leaves[i].clone();
+        let data = leaf.get_data().ok_or("Leaf with no data")?;
+        if data < 10 {
+            i += 1;
+            continue;
+        }
+        let (left_val, right_val) = (data / 2, data / 2 + data % 2);
+        if depth >= 4 {
+            leaf.set_data(Some(0));
+            add_to_leaf(leaves, i.checked_sub(1), left_val)?;
+            add_to_leaf(leaves, Some(i + 1), right_val)?;
+            i = i.saturating_sub(1);
+        } else {
+            let pair = NodeWrapper::new();
+            let left: NodeWrapper = Node::new(Some(left_val)).into();
+            let right: NodeWrapper = Node::new(Some(right_val)).into();
+            pair.set_left(Some(&left));
+            pair.set_right(Some(&right));
+            leaf.replace_with(&pair)?;
+            leaves.splice(i..=i, [(left, depth + 1), (right, depth + 1)]);
+        }
+    }
+    Ok(())
+}
+
+/// Adds `val` to the leaf at `idx`, if there is one.
+fn add_to_leaf(
+    leaves: &[(NodeWrapper, usize)],
+    idx: Option<usize>,
+    val: i64,
+) -> AocResult<()> {
+    if let Some((leaf, _)) = idx.and_then(|i| leaves.get(i)) {
+        let data = leaf.get_data().ok_or("Leaf with no data")?;
+        leaf.set_data(Some(data + val));
+    }
+    Ok(())
+}
+
+fn magnitude(node: &NodeWrapper) -> i64 {
+    if node.is_leaf() {
+        unreachable!("Shouldn't happen");
+    }
+
+    let left_mag = if let Some(left_data) = node.get_left().unwrap().get_data() {
+        left_data
+    } else {
+        magnitude(&node.get_left().unwrap())
+    };
+
+    let right_mag = if let Some(right_data) = node.get_right().unwrap().get_data() {
+        right_data
+    } else {
+        magnitude(&node.get_right().unwrap())
+    };
+
+    3 * left_mag + 2 * right_mag
+}
+
+fn parse_input(lines: &[String]) -> AocResult<Vec<Vec<NodeWrapper>>> {
+    numbered_blocks(lines)
+        .into_iter()
+        .map(|(start, block)| {
+            block
+                .iter()
+                .enumerate()
+                .map(|(i, l)| {
+                    if !l.is_ascii() {
+                        return malformed(start + i, "Non-ascii line");
+                    }
+                    NodeWrapper::from_ascii(l.as_bytes()).at_line(start + i)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn part_1(mut problem: Vec<NodeWrapper>) -> AocResult<i64> {
+    let mut sum = problem.remove(0);
+    for num in problem.into_iter() {
+        sum = add(&sum, &num)?;
+    }
+    Ok(magnitude(&sum))
+}
+
+#[cfg_attr(feature = "parallel", allow(dead_code))]
+fn part_2(problem: Vec<NodeWrapper>) -> AocResult<i64> {
+    let mut max = 0;
+    for (i, num_a) in problem.iter().enumerate() {
+        for (j, num_b) in problem.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+
+            // Super inefficient, but good enough for now.
+            let num_a_clone = NodeWrapper::from_ascii(num_a.to_string().as_bytes())?;
+            let num_b_clone = NodeWrapper::from_ascii(num_b.to_string().as_bytes())?;
+            max = cmp::max(max, magnitude(&add(&num_a_clone, &num_b_clone)?));
+
+            let num_a_clone = NodeWrapper::from_ascii(num_a.to_string().as_bytes())?;
+            let num_b_clone = NodeWrapper::from_ascii(num_b.to_string().as_bytes())?;
+            max = cmp::max(max, magnitude(&add(&num_b_clone, &num_a_clone)?));
+        }
+    }
+    Ok(max)
+}
+
+/// Like `part_2`, but tries the pairs in parallel. `NodeWrapper`s can't be shared
+/// between threads, so each sum is built from the numbers' text.
+#[cfg(feature = "parallel")]
+fn par_part_2(problem: Vec<NodeWrapper>) -> AocResult<i64> {
+    let nums: Vec<String> = problem.iter().map(|n| n.to_string()).collect();
+    let pairs: Vec<(usize, usize)> = (0..nums.len())
+        .flat_map(|i| {
+            (0..nums.len())
+                .filter(move |&j| j != i)
+                .map(move |j| (i, j))
+        })
+        .collect();
+    let sum_magnitude = |&(i, j): &(usize, usize)| -> AocResult<i64> {
+        let a = NodeWrapper::from_ascii(nums[i].as_bytes())?;
+        let b = NodeWrapper::from_ascii(nums[j].as_bytes())?;
+        Ok(magnitude(&add(&a, &b)?))
+    };
+    let mut max = 0;
+    for m in aoc_util::par::par_map(&pairs, |pair| {
+        sum_magnitude(pair).map_err(|e| e.to_string())
+    }) {
+        max = cmp::max(max, m?);
+    }
+    Ok(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file, read_lines};
+
+    #[test]
+    fn part_1_test_1() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_1(parse_input(&lines)?.remove(0))?, 3488);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_test_2() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_1(parse_input(&lines)?.remove(1))?, 143);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_test_3() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_1(parse_input(&lines)?.remove(2))?, 1384);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_test_4() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_1(parse_input(&lines)?.remove(3))?, 445);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_test_5() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_1(parse_input(&lines)?.remove(4))?, 791);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_test_6() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_1(parse_input(&lines)?.remove(5))?, 1137);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_test_7() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_1(parse_input(&lines)?.remove(6))?, 4140);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_test_8() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_1(parse_input(&lines)?.remove(7))?, 1384);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_test_9() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_1(parse_input(&lines)?.remove(8))?, 1384);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        assert_eq!(part_1(parse_input(&lines)?.remove(0))?, 3411);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test_1() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(part_2(parse_input(&lines)?.remove(6))?, 3993);
+        Ok(())
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn par_part_2_test_1() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(par_part_2(parse_input(&lines)?.remove(6))?, 3993);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        assert_eq!(part_2(parse_input(&lines)?.remove(0))?, 4680);
+        Ok(())
+    }
+}
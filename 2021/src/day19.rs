@@ -0,0 +1,691 @@
+use aoc_util::{
+    cli::Args,
+    debug,
+    errors::{malformed, AocResult, LineContext},
+    failure,
+    fasthash::FastMap,
+    io::read_input,
+    log::Progress,
+    parse::{numbered_blocks, split_parse},
+    solver::Solver,
+    timing::timed,
+};
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, BinaryHeap};
+use std::error;
+use std::fmt;
+use std::fs;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+
+pub struct Day19;
+
+impl Solver for Day19 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<usize> {
+    Ok(reconstruct(input)?.beacons.len())
+}
+
+pub fn part2(input: &str) -> AocResult<i64> {
+    reconstruct(input)?.max_scanner_distance()
+}
+
+/// The scanners and beacons, all in scanner 0's coordinates.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BeaconMap {
+    /// Each scanner's position, in input order.
+    pub scanners: Vec<Point3>,
+    /// Every distinct beacon, sorted.
+    pub beacons: Vec<Point3>,
+}
+
+impl BeaconMap {
+    /// The largest Manhattan distance between two scanners.
+    pub fn max_scanner_distance(&self) -> AocResult<i64> {
+        let scanners = &self.scanners;
+        scanners
+            .iter()
+            .flat_map(|&a| scanners.iter().map(move |&b| (a - b).magnitude()))
+            .max()
+            .ok_or_else(|| "No scanners".into())
+    }
+}
+
+/// One line per scanner ("scanner 0 at 0,0,0"), then one per beacon ("beacon 1,2,3").
+impl fmt::Display for BeaconMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, p) in self.scanners.iter().enumerate() {
+            writeln!(f, "scanner {i} at {p}")?;
+        }
+        for p in &self.beacons {
+            writeln!(f, "beacon {p}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Aligns the scanners in `input` and maps every beacon. Both parts need this; `main`
+/// does it once for both.
+pub fn reconstruct(input: &str) -> AocResult<BeaconMap> {
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    let mut progress = Progress::log("Scanners aligned");
+    solve(parse_input(&lines)?, &mut progress)
+}
+
+/// Like `solver::run_cli`, but with `--map PATH` to also write the reconstructed map
+/// to PATH ("-" for stdout).
+pub fn main() -> AocResult<()> {
+    let args = Args::from_env(&[], &["--map"])?;
+    let input = read_input(&args.input_path(19)?)?;
+    let map = reconstruct(&input)?;
+    match args.option("--map") {
+        Some("-") => print!("{map}"),
+        Some(path) => fs::write(path, map.to_string())?,
+        None => (),
+    }
+    if args.wants_part(1) {
+        println!("Part 1: {}", map.beacons.len());
+    }
+    if args.wants_part(2) {
+        println!("Part 2: {}", map.max_scanner_distance()?);
+    }
+    Ok(())
+}
+
+const N_ALIGN: u32 = 12;
+/// Two scanners that see the same `N_ALIGN` beacons share at least this many squared
+/// distances between beacons, whatever their orientations.
+const N_SHARED_DISTANCES: usize = (N_ALIGN * (N_ALIGN - 1) / 2) as usize;
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+/// The input's "x,y,z" format.
+impl fmt::Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+impl Add for Point3 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+/// Inner product.
+impl Mul for Point3 {
+    type Output = i64;
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+}
+
+impl FromStr for Point3 {
+    type Err = Box<dyn error::Error>;
+
+    fn from_str(s: &str) -> AocResult<Self> {
+        let [x, y, z] = split_parse::<i64>(s, ",")?[..] else {
+            failure!("Expected three coordinates, got {s:?}");
+        };
+        Ok(Point3 { x, y, z })
+    }
+}
+
+impl Point3 {
+    fn new(x: i64, y: i64, z: i64) -> Self {
+        Point3 { x, y, z }
+    }
+
+    fn magnitude(&self) -> i64 {
+        self.x.abs() + self.y.abs() + self.z.abs()
+    }
+
+    fn orient(&self, orientation: Orientation) -> Self {
+        match orientation {
+            Orientation::PlusX => Point3::new(self.x, self.y, self.z),
+            Orientation::PlusY => Point3::new(-self.y, self.x, self.z),
+            Orientation::PlusZ => Point3::new(self.z, self.y, -self.x),
+            Orientation::MinusX => Point3::new(-self.x, self.y, -self.z),
+            Orientation::MinusY => Point3::new(self.y, -self.x, self.z),
+            Orientation::MinusZ => Point3::new(-self.z, self.y, self.x),
+        }
+    }
+
+    fn rotate(&self, orientation: Orientation, rotation: Rotation) -> Self {
+        match orientation {
+            Orientation::PlusX | Orientation::MinusX => match rotation {
+                Rotation::_0 => Point3::new(self.x, self.y, self.z),
+                Rotation::_90 => Point3::new(self.x, -self.z, self.y),
+                Rotation::_180 => Point3::new(self.x, -self.y, -self.z),
+                Rotation::_270 => Point3::new(self.x, self.z, -self.y),
+            },
+            Orientation::PlusY | Orientation::MinusY => match rotation {
+                Rotation::_0 => Point3::new(self.x, self.y, self.z),
+                Rotation::_90 => Point3::new(self.z, self.y, -self.x),
+                Rotation::_180 => Point3::new(-self.x, self.y, -self.z),
+                Rotation::_270 => Point3::new(-self.z, self.y, self.x),
+            },
+            Orientation::PlusZ | Orientation::MinusZ => match rotation {
+                Rotation::_0 => Point3::new(self.x, self.y, self.z),
+                Rotation::_90 => Point3::new(-self.y, self.x, self.z),
+                Rotation::_180 => Point3::new(-self.x, -self.y, self.z),
+                Rotation::_270 => Point3::new(self.y, -self.x, self.z),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Orientation {
+    PlusX,
+    PlusY,
+    PlusZ,
+    MinusX,
+    MinusY,
+    MinusZ,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Rotation {
+    _0,
+    _90,
+    _180,
+    _270,
+}
+
+#[derive(Clone, Debug)]
+struct Problem {
+    scanners: Vec<Scanner>,
+}
+
+const ORIENTATIONS: [Orientation; 6] = [
+    Orientation::PlusX,
+    Orientation::PlusY,
+    Orientation::PlusZ,
+    Orientation::MinusX,
+    Orientation::MinusY,
+    Orientation::MinusZ,
+];
+
+const ROTATIONS: [Rotation; 4] =
+    [Rotation::_0, Rotation::_90, Rotation::_180, Rotation::_270];
+
+#[derive(Clone, Copy, Debug)]
+struct CoordinateSystem {
+    orientation: Orientation,
+    rotation: Rotation,
+}
+
+#[derive(Clone, Debug)]
+struct Scanner {
+    data: Vec<Point3>,
+    /// None indicates an position.
+    position: Option<Point3>,
+    /// None indicates an unknown coordinate system.
+    coordinate_system: Option<CoordinateSystem>,
+    /// sorted_squared_dists[i] = {d, j, k} is the squared distance
+    /// from data point j to data point k. Note that if {d, j, k}
+    /// is present, {d, k, j} won't be due to deduplication.
+    /// The case where j == k is also deduplicated.
+    sorted_squared_dists: Vec<(i64, usize, usize)>,
+}
+
+impl Scanner {
+    fn new(
+        data: Vec<Point3>,
+        position: Option<Point3>,
+        coordinate_system: Option<CoordinateSystem>,
+    ) -> Self {
+        let mut squared_dists = BinaryHeap::new();
+        for (i, p0) in data.iter().enumerate() {
+            squared_dists.append(
+                &mut data
+                    .iter()
+                    .enumerate()
+                    .skip(i + 1) // Avoid d_i * d_i and counting distances twice.
+                    .map(|(j, p1)| ((*p1 - *p0) * (*p1 - *p0), i, j))
+                    .collect::<BinaryHeap<_>>(),
+            );
+        }
+        Scanner {
+            data,
+            position,
+            coordinate_system,
+            sorted_squared_dists: squared_dists.into_sorted_vec(),
+        }
+    }
+
+    /// How many squared distances between pairs of beacons `self` and `other` have in
+    /// common, counting repeats.
+    fn shared_distances(&self, other: &Scanner) -> usize {
+        let (a, b) = (&self.sorted_squared_dists, &other.sorted_squared_dists);
+        let (mut i, mut j, mut shared) = (0, 0, 0);
+        while i < a.len() && j < b.len() {
+            match a[i].0.cmp(&b[j].0) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    shared += 1;
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        shared
+    }
+
+    /// Try to derive the coordinate system and offset of `other` relative to `self`.
+    fn try_derive_coordinate_system_and_offset(
+        &self,
+        other: &Scanner,
+    ) -> Option<(CoordinateSystem, Point3)> {
+        let mut sqdist_to_idx_pairs = FastMap::default();
+        for sqd in &self.sorted_squared_dists {
+            let mut start = 0;
+            while let Ok(idx) =
+                &other.sorted_squared_dists[start..].binary_search_by_key(&sqd.0, |&d| d.0)
+            {
+                let entry = sqdist_to_idx_pairs.entry(sqd).or_insert(Vec::new());
+                entry.push((
+                    (sqd.1, sqd.2),
+                    (
+                        other.sorted_squared_dists[start + *idx].1,
+                        other.sorted_squared_dists[start + *idx].2,
+                    ),
+                ));
+
+                if start + *idx == other.sorted_squared_dists.len() - 1 {
+                    break;
+                } else {
+                    start += *idx + 1;
+                }
+            }
+        }
+        // Find the indices of self.data which occur at least NUM_ALIGN - 1 times (in either
+        // position) in sqdist_to_idx_pairs .
+        let mut self_index_counts = FastMap::default();
+        let mut other_index_counts = FastMap::default();
+        for (_, v) in sqdist_to_idx_pairs {
+            for e in v {
+                let entry = self_index_counts.entry(e.0 .0).or_insert(0);
+                *entry += 1;
+                let entry = self_index_counts.entry(e.0 .1).or_insert(0);
+                *entry += 1;
+                let entry = other_index_counts.entry(e.1 .0).or_insert(0);
+                *entry += 1;
+                let entry = other_index_counts.entry(e.1 .1).or_insert(0);
+                *entry += 1;
+            }
+        }
+
+        let self_indices = self_index_counts
+            .into_iter()
+            .filter(|&(_, v)| v >= N_ALIGN - 1)
+            .map(|(k, _)| k)
+            .collect::<Vec<_>>();
+        let other_indices = other_index_counts
+            .into_iter()
+            .filter(|&(_, v)| v >= N_ALIGN - 1)
+            .map(|(k, _)| k)
+            .collect::<Vec<_>>();
+
+        if self_indices.len() < N_ALIGN as usize || other_indices.len() < N_ALIGN as usize {
+            return None;
+        }
+
+        // Find the alignment.
+        let aligned_self_points = {
+            let mut asp = Vec::with_capacity(N_ALIGN as usize);
+            for i in &self_indices {
+                asp.push(self.data[*i]);
+            }
+            asp
+        };
+        let mut ori = None;
+        let mut rot = None;
+        let mut ofs = None;
+        for orientation in ORIENTATIONS {
+            for rotation in ROTATIONS {
+                let mut aligned_other_points = Vec::with_capacity(N_ALIGN as usize);
+                for i in &other_indices {
+                    aligned_other_points.push(other.data[*i]);
+                }
+                aligned_other_points = aligned_other_points
+                    .iter()
+                    .map(|p| p.orient(orientation))
+                    .map(|p| p.rotate(orientation, rotation))
+                    .collect();
+                let mut offsets2counts = FastMap::default();
+                for sp in &aligned_self_points {
+                    for op in &aligned_other_points {
+                        let entry = offsets2counts.entry(*sp - *op).or_insert(0);
+                        *entry += 1;
+                    }
+                }
+                if let Some((true_ofs, _)) =
+                    offsets2counts.iter().find(|(_, v)| **v >= N_ALIGN)
+                {
+                    ofs = Some(*true_ofs);
+                    ori = Some(orientation);
+                    rot = Some(rotation);
+                    break;
+                }
+            }
+        }
+        if ori.is_none() || rot.is_none() {
+            return None;
+        }
+
+        Some((
+            CoordinateSystem {
+                orientation: ori.unwrap(),
+                rotation: rot.unwrap(),
+            },
+            ofs.unwrap(),
+        ))
+    }
+
+    fn align_measurements(&mut self, coordinate_system: CoordinateSystem, offset: Point3) {
+        self.data = self
+            .data
+            .iter()
+            .map(|p| p.orient(coordinate_system.orientation))
+            .map(|p| p.rotate(coordinate_system.orientation, coordinate_system.rotation))
+            .map(|p| p + offset)
+            .collect();
+    }
+}
+
+fn parse_input(lines: &[String]) -> AocResult<Problem> {
+    let _t = timed("parse");
+    let mut scanners = Vec::new();
+    for (start, block) in numbered_blocks(lines) {
+        if !block[0].starts_with("---") {
+            return malformed(start, "expected a scanner header");
+        }
+        let data = block[1..]
+            .iter()
+            .enumerate()
+            .map(|(i, l)| Point3::from_str(l).at_line(start + 1 + i))
+            .collect::<AocResult<_>>()?;
+        scanners.push(Scanner::new(data, None, None));
+    }
+    Ok(Problem { scanners })
+}
+
+fn solve(mut problem: Problem, progress: &mut Progress) -> AocResult<BeaconMap> {
+    problem.scanners[0].coordinate_system = Some(CoordinateSystem {
+        orientation: ORIENTATIONS[0],
+        rotation: ROTATIONS[0],
+    });
+    problem.scanners[0].position = Some(Point3 { x: 0, y: 0, z: 0 });
+    let mut scanners_to_align: Vec<usize> = (1..problem.scanners.len()).collect();
+    let mut aligned_scanners: Vec<usize> = vec![0];
+    let may_overlap = {
+        let _t = timed("overlap candidates");
+        overlap_candidates(&problem)
+    };
+
+    let _t = timed("align");
+    while !scanners_to_align.is_empty() {
+        #[cfg(not(feature = "parallel"))]
+        let found = find_alignments(
+            &problem,
+            &may_overlap,
+            &aligned_scanners,
+            &scanners_to_align,
+        );
+        #[cfg(feature = "parallel")]
+        let found = par_find_alignments(
+            &problem,
+            &may_overlap,
+            &aligned_scanners,
+            &scanners_to_align,
+        );
+        if found.is_empty() {
+            failure!("Couldn't align scanners {scanners_to_align:?}");
+        }
+        for (scanner_idx, cs, position) in found {
+            problem.scanners[scanner_idx].coordinate_system = Some(cs);
+            problem.scanners[scanner_idx].position = Some(position);
+            problem.scanners[scanner_idx].align_measurements(cs, position);
+            aligned_scanners.push(scanner_idx);
+            scanners_to_align.retain(|&i| i != scanner_idx);
+            debug!("Aligned scanner {scanner_idx} at {position:?}");
+        }
+        progress.report(
+            aligned_scanners.len() as u64,
+            Some(problem.scanners.len() as u64),
+        );
+    }
+
+    let scanners = problem
+        .scanners
+        .iter()
+        .map(|s| s.position.ok_or("Unaligned scanner"))
+        .collect::<Result<_, _>>()?;
+    let beacons: BTreeSet<Point3> = problem
+        .scanners
+        .into_iter()
+        .flat_map(|s| s.data.into_iter())
+        .collect();
+    Ok(BeaconMap {
+        scanners,
+        beacons: beacons.into_iter().collect(),
+    })
+}
+
+/// `may_overlap[i][j]` is false if scanners `i` and `j` can't possibly see `N_ALIGN` of
+/// the same beacons, because they don't have enough squared distances in common. The
+/// distances don't change as scanners are aligned, so this is worked out once, and
+/// spares most pairs the search over orientations.
+fn overlap_candidates(problem: &Problem) -> Vec<Vec<bool>> {
+    let scanners = &problem.scanners;
+    scanners
+        .iter()
+        .map(|a| {
+            scanners
+                .iter()
+                .map(|b| a.shared_distances(b) >= N_SHARED_DISTANCES)
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the first `(scanner, coordinate system, position)` found for a scanner in
+/// `to_align` that overlaps one of the `aligned` scanners.
+///
+/// It's wasteful to try to force the 'chaining' of scanners from scanner 0,
+/// since we waste work on aligning scanners that, while they may align, aren't
+/// the next pair in the chain. Is *is* simpler this way though.
+#[cfg(not(feature = "parallel"))]
+fn find_alignments(
+    problem: &Problem,
+    may_overlap: &[Vec<bool>],
+    aligned: &[usize],
+    to_align: &[usize],
+) -> Vec<(usize, CoordinateSystem, Point3)> {
+    for aligned_idx in aligned {
+        for scanner_idx in to_align {
+            if !may_overlap[*aligned_idx][*scanner_idx] {
+                continue;
+            }
+            if let Some((cs, position)) = problem.scanners[*aligned_idx]
+                .try_derive_coordinate_system_and_offset(&problem.scanners[*scanner_idx])
+            {
+                return vec![(*scanner_idx, cs, position)];
+            }
+        }
+    }
+    Vec::new()
+}
+
+/// Like `find_alignments`, but tries every scanner in `to_align` at once and returns
+/// all of those that overlap an aligned scanner.
+#[cfg(feature = "parallel")]
+fn par_find_alignments(
+    problem: &Problem,
+    may_overlap: &[Vec<bool>],
+    aligned: &[usize],
+    to_align: &[usize],
+) -> Vec<(usize, CoordinateSystem, Point3)> {
+    aoc_util::par::par_map(to_align, |&scanner_idx| {
+        aligned.iter().find_map(|&aligned_idx| {
+            if !may_overlap[aligned_idx][scanner_idx] {
+                return None;
+            }
+            problem.scanners[aligned_idx]
+                .try_derive_coordinate_system_and_offset(&problem.scanners[scanner_idx])
+                .map(|(cs, position)| (scanner_idx, cs, position))
+        })
+    })
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file, read_lines};
+
+    #[test]
+    fn point_align() -> AocResult<()> {
+        let p = Point3::new(1, 2, 3);
+        assert_eq!(
+            p.orient(Orientation::PlusX)
+                .rotate(Orientation::PlusX, Rotation::_90)
+                .rotate(Orientation::PlusX, Rotation::_90)
+                .rotate(Orientation::PlusX, Rotation::_90)
+                .rotate(Orientation::PlusX, Rotation::_90),
+            p
+        );
+        assert_eq!(
+            p.orient(Orientation::PlusX)
+                .rotate(Orientation::PlusX, Rotation::_180)
+                .rotate(Orientation::PlusX, Rotation::_90)
+                .rotate(Orientation::PlusX, Rotation::_270),
+            p.rotate(Orientation::PlusX, Rotation::_180)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(
+            solve(parse_input(&lines)?, &mut Progress::none())?
+                .beacons
+                .len(),
+            79
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn overlap_test() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        let may_overlap = overlap_candidates(&parse_input(&lines)?);
+        // The example's scanners overlap in pairs 0-1, 1-3, 1-4 and 2-4.
+        for (i, row) in may_overlap.iter().enumerate() {
+            for (j, &overlap) in row.iter().enumerate() {
+                let expected = i == j
+                    || [(0, 1), (1, 3), (1, 4), (2, 4)].contains(&(i.min(j), i.max(j)));
+                assert_eq!(overlap, expected, "scanners {i} and {j}");
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn progress_test() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        let mut reports = Vec::new();
+        solve(
+            parse_input(&lines)?,
+            &mut Progress::new(|done, total| reports.push((done, total))),
+        )?;
+        assert!(reports.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(reports.last(), Some(&(5, Some(5))));
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        assert_eq!(
+            solve(parse_input(&lines)?, &mut Progress::none())?
+                .beacons
+                .len(),
+            308
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        let map = solve(parse_input(&lines)?, &mut Progress::none())?;
+        assert_eq!(map.max_scanner_distance()?, 3621);
+        // Positions from the puzzle's walkthrough.
+        assert_eq!(map.scanners[2], Point3::new(1105, -1205, 1229));
+        assert_eq!(map.scanners[4], Point3::new(-20, -1133, 1061));
+        assert!(map.beacons.contains(&Point3::new(459, -707, 401)));
+        let shown = map.to_string();
+        assert!(shown.starts_with("scanner 0 at 0,0,0\nscanner 1 at 68,-1246,-43\n"));
+        assert_eq!(shown.lines().count(), 5 + 79);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        assert_eq!(
+            solve(parse_input(&lines)?, &mut Progress::none())?.max_scanner_distance()?,
+            12124
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use aoc_util::{
+    ensure,
+    errors::{malformed, AocResult, LineContext},
+    grid::Grid,
+    solver::Solver,
+};
+
+pub struct Day20;
+
+impl Solver for Day20 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<usize> {
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    let (filter, image) = parse_input(&lines)?;
+    solve(&filter, &image, 2)
+}
+
+pub fn part2(input: &str) -> AocResult<usize> {
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    let (filter, image) = parse_input(&lines)?;
+    solve(&filter, &image, 50)
+}
+
+fn parse_input(lines: &[String]) -> AocResult<(Grid, Grid)> {
+    let map_func = |c| match c {
+        '.' => Some(0),
+        '#' => Some(1),
+        _ => None,
+    };
+    ensure!(
+        lines.len() >= 3,
+        "Expected a filter, a blank line and an image"
+    );
+    let filter = Grid::from_symbol_matrix(&lines[0..1], map_func)?;
+    if !&lines[1].trim().is_empty() {
+        return malformed(2, "Non-empty separating line");
+    }
+    let image = Grid::from_symbol_matrix(&lines[2..], map_func).at_line(3)?;
+    Ok((filter, image))
+}
+
+fn solve(filter: &Grid, image: &Grid, n_iter: usize) -> AocResult<usize> {
+    let filter = filter.vec();
+    ensure!(
+        filter.len() == 512,
+        "Filter has {} entries, expected 512",
+        filter.len()
+    );
+    let index = |w: &[u8; 9]| w.iter().fold(0usize, |acc, &v| (acc << 1) | usize::from(v));
+
+    // The infinite background is all one value, which the filter maps as a whole: when
+    // filter[0] is lit, an unlit background lights up, and it may go dark again.
+    let mut image = image.clone();
+    let mut background = 0;
+    for _ in 0..n_iter {
+        image = image.convolve_3x3(background, |w| filter[index(w)]);
+        background = filter[index(&[background; 9])];
+    }
+    ensure!(background == 0, "Infinitely many pixels are lit");
+    Ok(image.vec().iter().filter(|&&v| v == 1).count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file, read_lines};
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        let (filter, image) = parse_input(&lines)?;
+        assert_eq!(solve(&filter, &image, 2)?, 35);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        let (filter, image) = parse_input(&lines)?;
+        assert_eq!(solve(&filter, &image, 2)?, 5819);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        let (filter, image) = parse_input(&lines)?;
+        assert_eq!(solve(&filter, &image, 50)?, 3351);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        let (filter, image) = parse_input(&lines)?;
+        assert_eq!(solve(&filter, &image, 50)?, 18516);
+        Ok(())
+    }
+
+    #[test]
+    fn flashing_background() -> AocResult<()> {
+        // A filter that inverts every pixel: the background flashes on and off.
+        let inverse: Vec<u8> = (0..512).map(|i| u8::from(i & 16 == 0)).collect();
+        let filter = Grid::from_slice(&inverse, 1, 512)?;
+        let image = Grid::from_slice(&[1, 0, 0], 1, 3)?;
+        assert!(solve(&filter, &image, 1).is_err());
+        // Inverting twice gives back the original image, with its one lit pixel.
+        assert_eq!(solve(&filter, &image, 2)?, 1);
+        Ok(())
+    }
+}
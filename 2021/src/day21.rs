@@ -0,0 +1,153 @@
+use aoc_util::{
+    ensure,
+    errors::{AocResult, LineContext},
+    game::count_outcomes,
+    parse::ints,
+    solver::Solver,
+};
+use std::cmp;
+
+pub struct Day21;
+
+impl Solver for Day21 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<u64> {
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    let (p1_start, p2_start) = parse_input(&lines)?;
+    part_1(p1_start, p2_start)
+}
+
+pub fn part2(input: &str) -> AocResult<u64> {
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    let (p1_start, p2_start) = parse_input(&lines)?;
+    part_2(p1_start, p2_start)
+}
+
+fn part_1(p1_start: u64, p2_start: u64) -> AocResult<u64> {
+    let mut die_state = 99;
+    let mut roll_count = 0;
+    let mut score = [0, 0];
+    let mut pos = [p1_start - 1, p2_start - 1];
+    let mut active_player = 0;
+    while score[0] < 1000 && score[1] < 1000 {
+        let mut move_count = 0;
+        for _ in 0..3 {
+            die_state = (die_state + 1) % 100;
+            let die_value = die_state + 1;
+            move_count += die_value;
+            roll_count += 1;
+        }
+        pos[active_player] = (pos[active_player] + move_count) % 10;
+        let pos_score = pos[active_player] + 1;
+        score[active_player] += pos_score;
+        active_player ^= 1;
+    }
+    let losing_player_score = if score[0] >= 1000 { score[1] } else { score[0] };
+    Ok(losing_player_score * roll_count)
+}
+
+const WINNING_SCORE: u8 = 21;
+
+#[derive(Clone, Copy, Hash, Eq, PartialEq, Debug)]
+struct GameState {
+    scores: [u8; 2],
+    /// 0-based.
+    positions: [u8; 2],
+    /// The player whose turn it is.
+    turn: usize,
+}
+
+impl GameState {
+    /// How many of the 27 rolls of three 3-sided dice sum to 3, 4, ..., 9.
+    const MULTIPLICITIES: [u64; 7] = [1, 3, 6, 7, 6, 3, 1];
+
+    fn outgoing(self) -> impl Iterator<Item = (GameState, u64)> {
+        (3..=9u8)
+            .zip(Self::MULTIPLICITIES)
+            .map(move |(roll_sum, ways)| {
+                let mut next = self;
+                let pos = &mut next.positions[self.turn];
+                *pos = (*pos + roll_sum) % 10;
+                next.scores[self.turn] += *pos + 1;
+                next.turn ^= 1;
+                (next, ways)
+            })
+    }
+
+    fn winner(&self) -> Option<usize> {
+        self.scores.iter().position(|&s| s >= WINNING_SCORE)
+    }
+}
+
+/// The number of universes won by the player who wins in more of them.
+fn part_2(p1_start: u64, p2_start: u64) -> AocResult<u64> {
+    let start = GameState {
+        scores: [0, 0],
+        positions: [u8::try_from(p1_start)? - 1, u8::try_from(p2_start)? - 1],
+        turn: 0,
+    };
+    let outcomes = count_outcomes(start, |s| s.outgoing(), |s| s.winner().is_some())?;
+    let mut wins = [0, 0];
+    for (state, ways) in outcomes {
+        wins[state.winner().ok_or("Game ended without a winner?")?] += ways;
+    }
+    Ok(cmp::max(wins[0], wins[1]))
+}
+
+fn parse_input(lines: &[String]) -> AocResult<(u64, u64)> {
+    ensure!(lines.len() == 2, "Too many input lines");
+    let mut start: [u64; 2] = [0, 0];
+    for (i, l) in lines.iter().enumerate() {
+        start[i] = *ints::<u64>(l)
+            .last()
+            .ok_or("No starting position?")
+            .at_line(i + 1)?;
+    }
+    Ok((start[0], start[1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file, read_lines};
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        let (p1_start, p2_start) = parse_input(&lines)?;
+        assert_eq!(part_1(p1_start, p2_start)?, 739785);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        let (p1_start, p2_start) = parse_input(&lines)?;
+        assert_eq!(part_1(p1_start, p2_start)?, 908595);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        let (p1_start, p2_start) = parse_input(&lines)?;
+        assert_eq!(part_2(p1_start, p2_start)?, 444356092776315);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        let (p1_start, p2_start) = parse_input(&lines)?;
+        assert_eq!(part_2(p1_start, p2_start)?, 91559198282731);
+        Ok(())
+    }
+}
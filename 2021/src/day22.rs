@@ -0,0 +1,294 @@
+use aoc_util::{
+    cli::Args,
+    cuboid::{sweep_volume, Cuboid, PolyCuboid, PolyHashCuboid},
+    ensure,
+    errors::{failure, AocResult, LineContext},
+    io::read_input,
+    solver::Solver,
+    timing::timed,
+};
+
+pub struct Day22;
+
+impl Solver for Day22 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<i64> {
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    part_1(&parse_input(&lines)?)
+}
+
+pub fn part2(input: &str) -> AocResult<i64> {
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    part_2(&parse_input(&lines)?)
+}
+
+#[derive(Clone, Debug)]
+struct Op {
+    to_state: bool,
+    cuboid: Cuboid,
+}
+
+fn parse_input(lines: &[String]) -> AocResult<Vec<Op>> {
+    let _t = timed("parse");
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, l)| parse_op(l).at_line(i + 1))
+        .collect()
+}
+
+fn parse_op(line: &str) -> AocResult<Op> {
+    let mut split = line.split_whitespace();
+    let to_state = match split.next() {
+        Some("on") => true,
+        Some("off") => false,
+        _ => failure("Bad on/off")?,
+    };
+    let cuboid = split.next().ok_or("No cuboid?")?.parse::<Cuboid>()?;
+    Ok(Op { to_state, cuboid })
+}
+
+/// Like `solver::run_cli`, but with `--verify` to first check the volumes from
+/// `PolyCuboid` against two independent algorithms (see `verify`).
+pub fn main() -> AocResult<()> {
+    let args = Args::from_env(&["--verify"], &[])?;
+    let lines: Vec<String> = read_input(&args.input_path(22)?)?
+        .lines()
+        .map(String::from)
+        .collect();
+    let ops = parse_input(&lines)?;
+    if args.flag("--verify") {
+        verify(&ops)?;
+        println!("Volumes agree");
+    }
+    if args.wants_part(1) {
+        println!("Part 1: {}", part_1(&ops)?);
+    }
+    if args.wants_part(2) {
+        println!("Part 2: {}", part_2(&ops)?);
+    }
+    Ok(())
+}
+
+/// The ops that make up the initialization procedure: those within 50 of the origin.
+fn initialization_ops(ops: &[Op]) -> AocResult<Vec<Op>> {
+    let region = Cuboid::new(-50, 50, -50, 50, -50, 50)?;
+    Ok(ops
+        .iter()
+        .filter(|o| region.contains(&o.cuboid))
+        .cloned()
+        .collect())
+}
+
+fn poly_volume(ops: &[Op]) -> i64 {
+    let _t = timed("volume");
+    let mut polycuboid = PolyCuboid::new();
+    for op in ops {
+        if op.to_state {
+            polycuboid.insert(&op.cuboid);
+        } else {
+            polycuboid.delete(&op.cuboid);
+        }
+    }
+    polycuboid.volume()
+}
+
+fn part_1(ops: &[Op]) -> AocResult<i64> {
+    Ok(poly_volume(&initialization_ops(ops)?))
+}
+
+fn part_2(ops: &[Op]) -> AocResult<i64> {
+    Ok(poly_volume(ops))
+}
+
+/// Checks that `PolyCuboid` agrees with `cuboid::sweep_volume` on all of `ops`, and with
+/// both that and `PolyHashCuboid`'s voxel-by-voxel count on the initialization ops,
+/// where there are few enough voxels to count.
+fn verify(ops: &[Op]) -> AocResult<()> {
+    let as_pairs = |ops: &[Op]| -> Vec<(bool, Cuboid)> {
+        ops.iter().map(|o| (o.to_state, o.cuboid.clone())).collect()
+    };
+
+    let init = initialization_ops(ops)?;
+    let mut voxels = PolyHashCuboid::new();
+    for op in &init {
+        if op.to_state {
+            voxels.insert(&op.cuboid);
+        } else {
+            voxels.delete(&op.cuboid);
+        }
+    }
+    let (poly, sweep, hash) = (
+        poly_volume(&init),
+        sweep_volume(&as_pairs(&init)),
+        voxels.volume(),
+    );
+    ensure!(
+        poly == sweep && sweep == hash,
+        "Initialization volumes disagree: PolyCuboid {poly}, sweep {sweep}, voxels {hash}"
+    );
+
+    let (poly, sweep) = (poly_volume(ops), sweep_volume(&as_pairs(ops)));
+    ensure!(
+        poly == sweep,
+        "Full volumes disagree: PolyCuboid {poly}, sweep {sweep}"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file, read_lines};
+
+    #[test]
+    fn parse_error_line() {
+        let lines = ["on x=0..1,y=0..1,z=0..1", "of x=0..1,y=0..1,z=0..1"].map(String::from);
+        let err = parse_input(&lines).unwrap_err();
+        assert_eq!(err.to_string(), "Line 2: Bad on/off");
+    }
+
+    #[test]
+    fn simple_test1() -> AocResult<()> {
+        let ops = vec![Op {
+            to_state: true,
+            cuboid: Cuboid::new(0, 1, 0, 1, 0, 1)?,
+        }];
+        assert_eq!(part_1(&ops)?, 8);
+        Ok(())
+    }
+
+    #[test]
+    fn simple_test2() -> AocResult<()> {
+        let ops = vec![
+            Op {
+                to_state: true,
+                cuboid: Cuboid::new(0, 1, 0, 1, 0, 1)?,
+            },
+            Op {
+                to_state: false,
+                cuboid: Cuboid::new(0, 1, 0, 1, 0, 1)?,
+            },
+        ];
+        assert_eq!(part_1(&ops)?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn simple_test3() -> AocResult<()> {
+        let ops = vec![
+            Op {
+                to_state: true,
+                cuboid: Cuboid::new(0, 1, 0, 1, 0, 1)?,
+            },
+            Op {
+                to_state: true,
+                cuboid: Cuboid::new(2, 3, 2, 3, 2, 3)?,
+            },
+        ];
+        assert_eq!(part_1(&ops)?, 16);
+        Ok(())
+    }
+
+    #[test]
+    fn simple_test4() -> AocResult<()> {
+        let ops = vec![
+            Op {
+                to_state: true,
+                cuboid: Cuboid::new(0, 1, 0, 1, 0, 1)?,
+            },
+            Op {
+                to_state: false,
+                cuboid: Cuboid::new(2, 3, 2, 3, 2, 3)?,
+            },
+        ];
+        assert_eq!(part_1(&ops)?, 8);
+        Ok(())
+    }
+
+    #[test]
+    fn simple_test5() -> AocResult<()> {
+        let vs = vec![
+            "on x=10..12,y=10..12,z=10..12".to_string(),
+            "on x=11..13,y=11..13,z=11..13".to_string(),
+            "off x=9..11,y=9..11,z=9..11".to_string(),
+            "on x=10..10,y=10..10,z=10..10".to_string(),
+        ];
+        let ops = parse_input(&vs)?;
+        assert_eq!(part_1(&ops)?, 39);
+        Ok(())
+    }
+
+    #[test]
+    fn simple_test6() -> AocResult<()> {
+        let vs = vec![
+            "on x=-20..26,y=-36..17,z=-47..7".to_string(),
+            "on x=-20..33,y=-21..23,z=-26..28".to_string(),
+            "on x=-22..28,y=-29..23,z=-38..16".to_string(),
+            "on x=-46..7,y=-6..46,z=-50..-1".to_string(),
+            "on x=-49..1,y=-3..46,z=-24..28".to_string(),
+            "on x=2..47,y=-22..22,z=-23..27".to_string(),
+            "on x=-27..23,y=-28..26,z=-21..29".to_string(),
+            "on x=-39..5,y=-6..47,z=-3..44".to_string(),
+            "on x=-30..21,y=-8..43,z=-13..34".to_string(),
+            "on x=-22..26,y=-27..20,z=-29..19".to_string(),
+            "off x=-48..-32,y=26..41,z=-47..-37".to_string(),
+            "on x=-12..35,y=6..50,z=-50..-2".to_string(),
+            "off x=-48..-32,y=-32..-16,z=-15..-5".to_string(),
+            "on x=-18..26,y=-33..15,z=-7..46".to_string(),
+            "off x=-40..-22,y=-38..-28,z=23..41".to_string(),
+            "on x=-16..35,y=-41..10,z=-47..6".to_string(),
+            "off x=-32..-23,y=11..30,z=-14..3".to_string(),
+            "on x=-49..-5,y=-3..45,z=-29..18".to_string(),
+        ];
+        let ops = parse_input(&vs)?;
+        assert_eq!(part_1(&ops)?, 592902);
+        Ok(())
+    }
+
+    #[test]
+    fn verify_test() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        verify(&parse_input(&lines)?)
+    }
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        let ops = parse_input(&lines)?;
+        assert_eq!(part_1(&ops)?, 590784);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        let ops = parse_input(&lines)?;
+        assert_eq!(part_1(&ops)?, 561032);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        let ops = parse_input(&lines)?;
+        assert_eq!(part_2(&ops)?, 39769202357779);
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        let ops = parse_input(&lines)?;
+        assert_eq!(part_2(&ops)?, 1322825263376414);
+        Ok(())
+    }
+}
@@ -0,0 +1,648 @@
+use aoc_util::{
+    bitset::SmallSet64,
+    cli::Args,
+    ensure,
+    errors::{malformed, malformed_at, AocResult},
+    info,
+    io::read_input,
+    log::Progress,
+    search::{astar_with_stats, ida_star_with_stats, SearchStats},
+    solver::Solver,
+    timing::timed,
+};
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeSet;
+use std::hash::{Hash, Hasher};
+
+pub struct Day23;
+
+impl Solver for Day23 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<u64> {
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    let mut stats = SearchStats::new();
+    let mut progress = Progress::log("Part 1 states expanded");
+    let answer = part_1(&lines, &mut stats, &mut progress)?;
+    info!("Part 1 search: {stats}");
+    Ok(answer)
+}
+
+pub fn part2(input: &str) -> AocResult<u64> {
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    let mut stats = SearchStats::new();
+    let mut progress = Progress::log("Part 2 states expanded");
+    let answer = part_2(&lines, &mut stats, &mut progress)?;
+    info!("Part 2 search: {stats}");
+    Ok(answer)
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialOrd, PartialEq, Ord)]
+enum Amph {
+    A,
+    B,
+    C,
+    D,
+}
+
+impl Amph {
+    fn weight(&self) -> u64 {
+        match self {
+            A => 1,
+            B => 10,
+            C => 100,
+            D => 1000,
+        }
+    }
+
+    fn dest(&self) -> usize {
+        match self {
+            A => 0,
+            B => 1,
+            C => 2,
+            D => 3,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialOrd, PartialEq, Ord)]
+enum Location {
+    /// (room_idx \in 0..4, room_part_idx \in 0..room_depth)
+    Room((usize, usize)),
+    /// hall_idx \in 0..11
+    Hall(usize),
+}
+
+use Amph::*;
+use Location::*;
+
+#[derive(Clone, Copy, Debug, Eq, PartialOrd, PartialEq, Ord)]
+struct Move {
+    amph: Amph,
+    from: Location,
+    to: Location,
+}
+
+impl Move {
+    fn new(amph: Amph, from: Location, to: Location) -> Self {
+        Move { amph, from, to }
+    }
+}
+
+/// The Zobrist key for `amph` at `loc`. An instance's hash is the xor of the keys of its
+/// occupied locations, so a move updates it with two xors. The keys are splitmix64
+/// outputs, which is as good as a table of random numbers and needs no table.
+fn zobrist_key(loc: Location, amph: Amph) -> u64 {
+    let loc = match loc {
+        Room((room, room_part)) => (1 << 32) | (room << 16) | room_part,
+        Hall(hall_part) => hall_part,
+    } as u64;
+    let mut z = (loc << 2 | amph.dest() as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[derive(Clone, Debug, Eq, PartialOrd, PartialEq, Ord)]
+struct Instance {
+    /// `rooms[i][j]` is room `i`, room part `j`. Room part `0` is closest to the hall.
+    rooms: [Vec<Option<Amph>>; 4],
+    /// Maps from room index i to the hall part that connects to it.
+    room2hall: [usize; 4],
+    hall: Vec<Option<Amph>>,
+    room_depth: usize,
+    /// The Zobrist hash of the occupied locations, kept up to date by `apply_move`.
+    zobrist: u64,
+}
+
+/// Hashes just the Zobrist value, so the search's table of best costs doesn't rehash
+/// the rooms and hall of every state it looks up. Equality still compares the whole
+/// state, so a hash collision only costs time.
+impl Hash for Instance {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.zobrist);
+    }
+}
+
+impl Instance {
+    /// Computes the Zobrist hash from scratch.
+    fn zobrist(&self) -> u64 {
+        let rooms = self.rooms.iter().enumerate().flat_map(|(room, parts)| {
+            parts
+                .iter()
+                .enumerate()
+                .filter_map(move |(part, a)| Some(zobrist_key(Room((room, part)), (*a)?)))
+        });
+        let hall = (self.hall.iter().enumerate())
+            .filter_map(|(hall_part, a)| Some(zobrist_key(Hall(hall_part), (*a)?)));
+        rooms.chain(hall).fold(0, |h, k| h ^ k)
+    }
+
+    /// Returns the path travelled along `mv.from -> mv.to`. Does not include the starting
+    /// location, `mv.from`. Ignores collision with `Amph`s.
+    fn path(&self, mv: Move) -> Vec<Location> {
+        let mut path = Vec::with_capacity(14);
+        match (mv.from, mv.to) {
+            (Room(from), Room(to)) => {
+                for i in (0..from.1).rev() {
+                    path.push(Room((from.0, i)));
+                }
+
+                let hall_start = self.room2hall[from.0];
+                let hall_end = self.room2hall[to.0];
+                let hall_vec: Vec<Location> = if hall_start < hall_end {
+                    (hall_start..=hall_end).map(Hall).collect()
+                } else {
+                    (hall_end..=hall_start).rev().map(Hall).collect()
+                };
+                path.extend(hall_vec);
+
+                for i in 0..=to.1 {
+                    path.push(Room((to.0, i)));
+                }
+            }
+            (Room(from), Hall(to)) => {
+                for i in (0..from.1).rev() {
+                    path.push(Room((from.0, i)));
+                }
+
+                let hall_start = self.room2hall[from.0];
+                let hall_end = to;
+                let hall_vec: Vec<Location> = if hall_start < hall_end {
+                    (hall_start..=hall_end).map(Hall).collect()
+                } else {
+                    (hall_end..=hall_start).rev().map(Hall).collect()
+                };
+                path.extend(hall_vec);
+            }
+            (Hall(from), Room(to)) => {
+                let hall_start = from;
+                let hall_end = self.room2hall[to.0];
+                let hall_vec: Vec<Location> = if hall_start < hall_end {
+                    (hall_start + 1..=hall_end).map(Hall).collect()
+                } else {
+                    (hall_end..=hall_start - 1).rev().map(Hall).collect()
+                };
+                path.extend(hall_vec);
+
+                for i in 0..=to.1 {
+                    path.push(Room((to.0, i)));
+                }
+            }
+            (Hall(_), Hall(_)) => panic!("Invalid hall to hall move {:?}", mv),
+        }
+        path
+    }
+
+    fn occupied(&self, loc: Location) -> bool {
+        match loc {
+            Room((room, room_part)) => self.rooms[room][room_part].is_some(),
+            Hall(hall_part) => self.hall[hall_part].is_some(),
+        }
+    }
+
+    /// Returns Some(cost) if `mv` is possible without collision, otherwise None.
+    fn cost(&self, mv: Move) -> Option<u64> {
+        let path = self.path(mv);
+        for loc in &path {
+            if self.occupied(*loc) {
+                return None;
+            }
+        }
+        Some(path.len() as u64 * mv.amph.weight())
+    }
+
+    fn apply_move(&self, mv: Move) -> Self {
+        let mut out = self.clone();
+        match mv.to {
+            Room(to) => out.rooms[to.0][to.1] = Some(mv.amph),
+            Hall(to) => out.hall[to] = Some(mv.amph),
+        }
+        match mv.from {
+            Room(from) => out.rooms[from.0][from.1] = None,
+            Hall(from) => out.hall[from] = None,
+        }
+        out.zobrist ^= zobrist_key(mv.from, mv.amph) ^ zobrist_key(mv.to, mv.amph);
+        out
+    }
+
+    /// (cost, move)
+    fn moves(&self) -> Vec<(u64, Move)> {
+        // Store (dist_from_dest, cost, move). The first part of the tuple
+        // is for heuristic purposes.
+        let mut moves = BTreeSet::new();
+        // `Amph`s never stop in the hall right outside a room.
+        let doors: SmallSet64 = self.room2hall.into_iter().collect();
+        let (hall_occupied, hall_unoccupied): (Vec<_>, Vec<_>) = self
+            .hall
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| !doors.contains(i))
+            .partition(|(_, a)| a.is_some());
+        let (room_parts_occupied, room_parts_unoccupied): (Vec<_>, Vec<_>) = self
+            .rooms
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(i, a)| (i / self.room_depth, i % self.room_depth, a))
+            .partition(|(_, _, a)| a.is_some());
+
+        for (h, a) in &hall_occupied {
+            for (i, j, _) in &room_parts_unoccupied {
+                if a.unwrap().dest() == *i {
+                    let mut valid_move = true;
+                    for b in self.rooms[*i][j + 1..self.room_depth].iter() {
+                        // Always move as deep into the room as possible.
+                        // Ensure room is occupied only by other Amphs of the same variant.
+                        if b.is_none() || (b.is_some() && b != *a) {
+                            valid_move = false;
+                            break;
+                        }
+                    }
+                    if valid_move {
+                        let mv = Move::new(a.unwrap(), Hall(*h), Room((*i, *j)));
+                        if let Some(cost) = self.cost(mv) {
+                            moves.insert((0, cost, mv));
+                        }
+                    }
+                }
+            }
+        }
+        for (i, j, a) in &room_parts_occupied {
+            for (h, _) in &hall_unoccupied {
+                let valid_move = if *i == a.unwrap().dest() {
+                    if *j == self.room_depth - 1 {
+                        false
+                    } else {
+                        self.rooms[*i][j + 1..self.room_depth]
+                            .iter()
+                            .any(|b| b.is_none() || *b != **a)
+                    }
+                } else {
+                    true
+                };
+
+                if valid_move {
+                    let mv = Move::new(a.unwrap(), Room((*i, *j)), Hall(*h));
+                    if let Some(cost) = self.cost(mv) {
+                        moves.insert((
+                            (*h as isize - self.room2hall[*i] as isize).abs(),
+                            cost,
+                            mv,
+                        ));
+                    }
+                }
+            }
+        }
+        moves.into_iter().map(|(_, c, m)| (c, m)).collect()
+    }
+
+    /// A lower bound on the cost of solving from here, found by letting every `Amph`
+    /// that still has to move walk through the others to the top of its room, and then
+    /// filling each room from the bottom.
+    fn lower_bound(&self) -> u64 {
+        let mut cost = 0;
+        // How many more `Amph`s each room needs.
+        let mut missing = [0; 4];
+        for (i, room) in self.rooms.iter().enumerate() {
+            // `Amph`s already at the bottom of their own room never have to move.
+            let settled = room
+                .iter()
+                .rev()
+                .take_while(|a| a.is_some_and(|a| a.dest() == i))
+                .count();
+            missing[i] = self.room_depth - settled;
+            for (j, a) in room[..self.room_depth - settled].iter().enumerate() {
+                let Some(a) = a else { continue };
+                // Out into the hall, then over to the destination room and into it. An
+                // `Amph` in its own room has to step aside and back to let others out.
+                let hall_steps = if a.dest() == i {
+                    2
+                } else {
+                    self.room2hall[i].abs_diff(self.room2hall[a.dest()])
+                };
+                cost += (j as u64 + 1 + hall_steps as u64 + 1) * a.weight();
+            }
+        }
+        for (h, a) in self.hall.iter().enumerate() {
+            if let Some(a) = a {
+                cost += (h.abs_diff(self.room2hall[a.dest()]) as u64 + 1) * a.weight();
+            }
+        }
+        // The costs above only get each `Amph` to the top of its room. Filling a room
+        // with `k` more takes 0 + 1 + ... + (k - 1) further steps.
+        for (amph, k) in [A, B, C, D].into_iter().zip(missing) {
+            cost += (k * k.saturating_sub(1) / 2) as u64 * amph.weight();
+        }
+        cost
+    }
+
+    fn is_solution(&self) -> bool {
+        for (i, r) in self.rooms.iter().enumerate() {
+            if !r.iter().all(|a| {
+                if let Some(a) = a {
+                    return a.dest() == i;
+                }
+                false
+            }) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+fn parse_input(lines: &[String]) -> AocResult<Instance> {
+    let _t = timed("parse");
+    let mut it = lines.iter();
+    let hall_width = it
+        .nth(1)
+        .ok_or("No hall?")?
+        .chars()
+        .filter(|c| *c == '.')
+        .count();
+    let hall = vec![None; hall_width];
+    let mut rooms: [Vec<Option<Amph>>; 4] = [Vec::new(), Vec::new(), Vec::new(), Vec::new()];
+    let mut room2hall = [0; 4];
+    let mut room_depth = 0;
+    for i in 0.. {
+        let line = it.next().ok_or(format!("No room part {i}?"))?;
+        if line.trim().chars().all(|c| c == '#') {
+            break;
+        }
+        room_depth += 1;
+        // The hall line is the second line of the input and rooms start on the third.
+        let lineno = i + 3;
+        let mut roomparts = Vec::new();
+        for (j, c) in line.chars().enumerate() {
+            let amph = match c {
+                'A' => A,
+                'B' => B,
+                'C' => C,
+                'D' => D,
+                '#' | ' ' => continue,
+                x => {
+                    return malformed_at(
+                        lineno,
+                        j + 1,
+                        format!("Bad char {x:?} in room line"),
+                    )
+                }
+            };
+            roomparts.push((j - 1, amph));
+        }
+        if roomparts.len() != 4 {
+            return malformed(lineno, format!("expected 4 rooms, got {}", roomparts.len()));
+        }
+        for r in 0..4 {
+            room2hall[r] = roomparts[r].0;
+            rooms[r].insert(i, Some(roomparts[r].1));
+        }
+    }
+    let mut instance = Instance {
+        rooms,
+        room2hall,
+        hall,
+        room_depth,
+        zobrist: 0,
+    };
+    instance.zobrist = instance.zobrist();
+    Ok(instance)
+}
+
+/// How many expanded states between progress reports.
+const PROGRESS_INTERVAL: u64 = 1 << 16;
+
+fn solve(
+    instance: Instance,
+    stats: &mut SearchStats,
+    progress: &mut Progress,
+) -> AocResult<u64> {
+    search(instance, Instance::lower_bound, stats, progress)
+}
+
+/// Which search algorithm to solve with.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Backend {
+    AStar,
+    /// Slower, but keeps only the current path in memory. Part 1 only: part 2's bound
+    /// rises a couple of units per pass, so it would take thousands of passes.
+    IdaStar,
+}
+
+/// Like `solver::run_cli`, but with an `--ida-star` flag to search with IDA* instead of
+/// A*, which must come with `--part 1`.
+pub fn main() -> AocResult<()> {
+    let args = Args::from_env(&["--ida-star"], &[])?;
+    let backend = if args.flag("--ida-star") {
+        ensure!(
+            !args.wants_part(2),
+            "--ida-star is too slow for part 2; add --part 1"
+        );
+        Backend::IdaStar
+    } else {
+        Backend::AStar
+    };
+    let input = read_input(&args.input_path(23)?)?;
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    for part in [1, 2] {
+        if !args.wants_part(part) {
+            continue;
+        }
+        let _t = timed(format!("part {part}"));
+        let instance = if part == 1 {
+            parse_input(&lines)?
+        } else {
+            parse_input(&unfold(&lines))?
+        };
+        let mut stats = SearchStats::new();
+        let label = format!("Part {part} states expanded");
+        let mut progress = Progress::log(&label);
+        let answer = search_with(backend, instance, &mut stats, &mut progress)?;
+        println!("Part {part}: {answer}");
+        info!("Part {part} search: {stats}");
+    }
+    Ok(())
+}
+
+fn search_with(
+    backend: Backend,
+    instance: Instance,
+    stats: &mut SearchStats,
+    progress: &mut Progress,
+) -> AocResult<u64> {
+    match backend {
+        Backend::AStar => search(instance, Instance::lower_bound, stats, progress),
+        Backend::IdaStar => {
+            let (_, cost) = ida_star_with_stats(
+                instance,
+                |inst| {
+                    inst.moves()
+                        .into_iter()
+                        .map(|(cost, mv)| (inst.apply_move(mv), cost))
+                        .collect()
+                },
+                Instance::lower_bound,
+                Instance::is_solution,
+                stats,
+            )
+            .ok_or("No solution")?;
+            Ok(cost)
+        }
+    }
+}
+
+fn search<H: Fn(&Instance) -> u64>(
+    instance: Instance,
+    heuristic: H,
+    stats: &mut SearchStats,
+    progress: &mut Progress,
+) -> AocResult<u64> {
+    let _t = timed("search");
+    let expanded = Cell::new(0u64);
+    let progress = RefCell::new(progress);
+    let (_, cost) = astar_with_stats(
+        instance,
+        |inst| {
+            expanded.set(expanded.get() + 1);
+            if expanded.get().is_multiple_of(PROGRESS_INTERVAL) {
+                progress.borrow_mut().report(expanded.get(), None);
+            }
+            inst.moves()
+                .into_iter()
+                .map(|(cost, mv)| (inst.apply_move(mv), cost))
+                .collect()
+        },
+        heuristic,
+        Instance::is_solution,
+        stats,
+    )
+    .ok_or("No solution")?;
+    Ok(cost)
+}
+
+fn part_1(
+    lines: &[String],
+    stats: &mut SearchStats,
+    progress: &mut Progress,
+) -> AocResult<u64> {
+    solve(parse_input(lines)?, stats, progress)
+}
+
+fn part_2(
+    lines: &[String],
+    stats: &mut SearchStats,
+    progress: &mut Progress,
+) -> AocResult<u64> {
+    solve(parse_input(&unfold(lines))?, stats, progress)
+}
+
+/// Adds the two extra room lines that part 2 reveals.
+fn unfold(lines: &[String]) -> Vec<String> {
+    let mut lines = lines.to_vec();
+    lines.insert(3, "  #D#C#B#A#".to_string());
+    lines.insert(4, "  #D#B#A#C#".to_string());
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file, read_lines};
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(
+            part_1(&lines, &mut SearchStats::new(), &mut Progress::none())?,
+            12521
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        assert_eq!(
+            part_1(&lines, &mut SearchStats::new(), &mut Progress::none())?,
+            15109
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_test() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        assert_eq!(
+            part_2(&lines, &mut SearchStats::new(), &mut Progress::none())?,
+            44169
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        let mut stats = SearchStats::new();
+        assert_eq!(part_2(&lines, &mut stats, &mut Progress::none())?, 53751);
+
+        // Without the heuristic, the search has to expand many more states.
+        let mut dijkstra_stats = SearchStats::new();
+        let cost = search(
+            parse_input(&unfold(&lines))?,
+            |_| 0,
+            &mut dijkstra_stats,
+            &mut Progress::none(),
+        )?;
+        assert_eq!(cost, 53751);
+        assert!(
+            stats.nodes_expanded < dijkstra_stats.nodes_expanded,
+            "A*: {stats}; Dijkstra: {dijkstra_stats}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn ida_star_backend() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        let cost = search_with(
+            Backend::IdaStar,
+            parse_input(&lines)?,
+            &mut SearchStats::new(),
+            &mut Progress::none(),
+        )?;
+        assert_eq!(cost, 12521);
+        Ok(())
+    }
+
+    #[test]
+    fn incremental_zobrist() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        let mut inst = parse_input(&unfold(&lines))?;
+        let mut seen = vec![inst.zobrist];
+        // Follow the first move out of each state, checking every other successor too.
+        for _ in 0..8 {
+            let moves = inst.moves();
+            let Some(&(_, first)) = moves.first() else {
+                break;
+            };
+            for (_, mv) in moves {
+                let next = inst.apply_move(mv);
+                assert_eq!(next.zobrist, next.zobrist(), "{mv:?}");
+                assert_ne!(next.zobrist, inst.zobrist);
+            }
+            inst = inst.apply_move(first);
+            assert!(!seen.contains(&inst.zobrist));
+            seen.push(inst.zobrist);
+        }
+        assert!(seen.len() > 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,1393 @@
+use aoc_util::{
+    cli::Args,
+    errors::{failure, AocError, AocResult},
+    failure,
+    fasthash::{FastMap, FastSet},
+    io::read_input,
+    log::Progress,
+    solver::Solver,
+    timing::timed,
+};
+use std::cmp::{max, min};
+use std::error;
+use std::fmt;
+use std::io::{self, Write};
+use std::slice;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Register(i64);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum RegisterName {
+    W = 0,
+    X = 1,
+    Y = 2,
+    Z = 3,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum RVal {
+    Reg(RegisterName),
+    Val(i64),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Instruction {
+    Inp(RegisterName),
+    Add((RegisterName, RVal)),
+    Mul((RegisterName, RVal)),
+    Div((RegisterName, RVal)),
+    Mod((RegisterName, RVal)),
+    Eql((RegisterName, RVal)),
+    Neq((RegisterName, RVal)),
+    Set((RegisterName, RVal)),
+    /// Relative jump.
+    Jmp(RVal),
+    /// Relative jump by the second operand if the first is nonzero.
+    Jnz((RVal, RVal)),
+    /// Relative jump by the second operand if the first is zero.
+    Jez((RVal, RVal)),
+}
+
+use Instruction::*;
+use RVal::*;
+use RegisterName::*;
+
+pub struct Day24;
+
+impl Solver for Day24 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> AocResult<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<i64> {
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    let mut progress = Progress::log("Part 1 stages");
+    solve(&parse_input(&lines)?, false, &mut progress)
+}
+
+pub fn part2(input: &str) -> AocResult<i64> {
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    let mut progress = Progress::log("Part 2 stages");
+    solve(&parse_input(&lines)?, true, &mut progress)
+}
+
+impl Instruction {
+    fn is_jump(&self) -> bool {
+        matches!(self, Jmp(_) | Jnz(_) | Jez(_))
+    }
+}
+
+/// The values `inp` can produce: model number digits.
+const DIGITS: Interval = Interval { lo: 1, hi: 9 };
+
+/// An inclusive range of values a register may hold. Bounds saturate instead of
+/// overflowing.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Interval {
+    lo: i64,
+    hi: i64,
+}
+
+impl Interval {
+    const FULL: Interval = Interval {
+        lo: i64::MIN,
+        hi: i64::MAX,
+    };
+
+    fn new(lo: i64, hi: i64) -> Self {
+        Interval { lo, hi }
+    }
+
+    fn constant(value: i64) -> Self {
+        Interval::new(value, value)
+    }
+
+    fn as_constant(self) -> Option<i64> {
+        (self.lo == self.hi).then_some(self.lo)
+    }
+
+    fn contains(self, value: i64) -> bool {
+        self.lo <= value && value <= self.hi
+    }
+
+    /// The smallest interval containing all of `values`.
+    fn hull(values: [i64; 4]) -> Self {
+        Interval::new(*values.iter().min().unwrap(), *values.iter().max().unwrap())
+    }
+
+    fn add(self, rhs: Self) -> Self {
+        Interval::new(
+            self.lo.saturating_add(rhs.lo),
+            self.hi.saturating_add(rhs.hi),
+        )
+    }
+
+    fn mul(self, rhs: Self) -> Self {
+        Interval::hull([
+            self.lo.saturating_mul(rhs.lo),
+            self.lo.saturating_mul(rhs.hi),
+            self.hi.saturating_mul(rhs.lo),
+            self.hi.saturating_mul(rhs.hi),
+        ])
+    }
+
+    fn div(self, rhs: Self) -> Self {
+        if rhs.contains(0) {
+            // Division by zero is an error at run time, so anything goes.
+            return Interval::FULL;
+        }
+        // Truncating division is monotonic in each argument while the divisor keeps its
+        // sign, so the extremes are at the corners.
+        Interval::hull([
+            self.lo.saturating_div(rhs.lo),
+            self.lo.saturating_div(rhs.hi),
+            self.hi.saturating_div(rhs.lo),
+            self.hi.saturating_div(rhs.hi),
+        ])
+    }
+
+    fn rem(self, rhs: Self) -> Self {
+        if rhs.contains(0) {
+            return Interval::FULL;
+        }
+        if let Some(m) = rhs.as_constant().filter(|&m| m > 0) {
+            // A nonnegative range narrower than the modulus that doesn't wrap maps
+            // straight through.
+            if self.lo >= 0 && self.hi - self.lo < m && self.lo % m <= self.hi % m {
+                return Interval::new(self.lo % m, self.hi % m);
+            }
+        }
+        // |lhs % rhs| < |rhs| and |lhs % rhs| <= |lhs|, with the sign of lhs.
+        let m = rhs.lo.unsigned_abs().max(rhs.hi.unsigned_abs()) - 1;
+        let m = i64::try_from(m).unwrap_or(i64::MAX);
+        Interval::new(self.lo.min(0).max(-m), self.hi.max(0).min(m))
+    }
+
+    fn eql(self, rhs: Self) -> Self {
+        match (self.as_constant(), rhs.as_constant()) {
+            (Some(a), Some(b)) if a == b => Interval::constant(1),
+            _ if self.hi < rhs.lo || rhs.hi < self.lo => Interval::constant(0),
+            _ => Interval::new(0, 1),
+        }
+    }
+
+    fn neq(self, rhs: Self) -> Self {
+        let eq = self.eql(rhs);
+        Interval::new(1 - eq.hi, 1 - eq.lo)
+    }
+}
+
+/// Register ranges at some point in a program, indexed by `RegisterName`.
+type RangeFacts = [Interval; 4];
+
+fn rval_range(facts: &RangeFacts, rval: RVal) -> Interval {
+    match rval {
+        Reg(reg) => facts[reg as usize],
+        Val(val) => Interval::constant(val),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Program {
+    instructions: Vec<Instruction>,
+}
+
+impl Program {
+    fn from_listing<S: AsRef<str>>(lines: &[S]) -> AocResult<Self> {
+        Ok(Self {
+            instructions: lines
+                .iter()
+                .enumerate()
+                .map(|(i, l)| {
+                    l.as_ref().parse::<Instruction>().map_err(|e| {
+                        AocError::malformed(i + 1, format!("{:?}: {e}", l.as_ref()))
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Renders the program one instruction per line, in a form `from_listing` accepts.
+    fn to_listing(&self) -> Vec<String> {
+        self.instructions.iter().map(|i| i.to_string()).collect()
+    }
+
+    fn subprogram(&self, start_stage_idx: usize, stop_stage_idx: usize) -> AocResult<Self> {
+        let start = self
+            .instructions
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, instr)| if let Inp(_) = instr { Some(idx) } else { None })
+            .nth(start_stage_idx)
+            .ok_or(format!("Couldn't find start_stage_idx {start_stage_idx}"))?;
+        let end = self
+            .instructions
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, instr)| if let Inp(_) = instr { Some(idx) } else { None })
+            .nth(stop_stage_idx)
+            .map_or(self.instructions.len(), |idx| idx);
+
+        Ok(Program {
+            instructions: self.instructions[start..end].to_vec(),
+        })
+    }
+
+    fn optimize(&mut self) {
+        // Every pass here either drops instructions, which would shift the targets of
+        // relative jumps, or can't follow jumps at all, so only straight-line programs
+        // (like MONAD) get optimized.
+        if self.instructions.iter().any(Instruction::is_jump) {
+            return;
+        }
+        let mut new_instructions = Vec::with_capacity(self.instructions.len());
+        let mut search_add = None;
+        let mut skip_eq = false;
+
+        for (i, instr) in self.instructions.iter().enumerate() {
+            if skip_eq {
+                skip_eq = false;
+                continue;
+            }
+
+            if let Mul((regname, Val(0))) = instr {
+                new_instructions.push(Set((*regname, Val(0))));
+                search_add = Some(regname);
+            } else if let Add((regname, Val(v))) = instr {
+                if Some(regname) == search_add {
+                    search_add = None;
+                    new_instructions.push(Set((*regname, Val(*v))));
+                } else {
+                    search_add = None;
+                    new_instructions.push(instr.clone());
+                }
+            } else if let Eql((regname, Reg(reg))) = instr {
+                search_add = None;
+                if let Some(Eql((regname2, Val(0)))) = self.instructions.get(i + 1) {
+                    if regname == regname2 {
+                        new_instructions.push(Neq((*regname, Reg(*reg))));
+                        skip_eq = true;
+                        continue;
+                    }
+                }
+                new_instructions.push(instr.clone());
+            } else if let Div((_, Val(1))) = instr {
+                search_add = None;
+            } else {
+                search_add = None;
+                new_instructions.push(instr.clone());
+            }
+        }
+        self.instructions = new_instructions;
+        self.propagate_ranges([Interval::constant(0); 4]);
+        self.eliminate_dead_stores();
+    }
+
+    /// Computes the range of every register before each instruction, given their
+    /// ranges on entry and that every `inp` reads a digit. Element `i` holds the ranges
+    /// before instruction `i`, and the last element the ranges once the program ends.
+    /// Programs with jumps aren't supported.
+    fn analyze(&self, entry: RangeFacts) -> AocResult<Vec<RangeFacts>> {
+        let mut facts = Vec::with_capacity(self.instructions.len() + 1);
+        facts.push(entry);
+        for (i, instr) in self.instructions.iter().enumerate() {
+            let mut regs = facts[i];
+            let (dest, value) = match *instr {
+                Inp(reg) => (reg, DIGITS),
+                Add((reg, rval)) => (reg, regs[reg as usize].add(rval_range(&regs, rval))),
+                Mul((reg, rval)) => (reg, regs[reg as usize].mul(rval_range(&regs, rval))),
+                Div((reg, rval)) => (reg, regs[reg as usize].div(rval_range(&regs, rval))),
+                Mod((reg, rval)) => (reg, regs[reg as usize].rem(rval_range(&regs, rval))),
+                Eql((reg, rval)) => (reg, regs[reg as usize].eql(rval_range(&regs, rval))),
+                Neq((reg, rval)) => (reg, regs[reg as usize].neq(rval_range(&regs, rval))),
+                Set((reg, rval)) => (reg, rval_range(&regs, rval)),
+                Jmp(_) | Jnz(_) | Jez(_) => {
+                    failure!("Can't analyze jump at instruction {i}")
+                }
+            };
+            regs[dest as usize] = value;
+            facts.push(regs);
+        }
+        Ok(facts)
+    }
+
+    /// Replaces instructions whose result is known from `analyze` with a `set`,
+    /// substitutes known register operands with constants, and drops the resulting
+    /// identity operations. This is what removes divisions of values already smaller
+    /// than the divisor and `eql`s that can't go both ways.
+    fn propagate_ranges(&mut self, entry: RangeFacts) {
+        let Ok(facts) = self.analyze(entry) else {
+            return;
+        };
+        let mut new_instructions = Vec::with_capacity(self.instructions.len());
+        for (i, instr) in self.instructions.iter().enumerate() {
+            let (before, after) = (&facts[i], &facts[i + 1]);
+            let constant = |rval: RVal| match rval_range(before, rval).as_constant() {
+                Some(c) => Val(c),
+                None => rval,
+            };
+            let instr = match *instr {
+                Inp(_) => instr.clone(),
+                Add((reg, _)) | Mul((reg, _)) | Div((reg, _)) | Mod((reg, _))
+                | Eql((reg, _)) | Neq((reg, _)) | Set((reg, _))
+                    if after[reg as usize].as_constant().is_some() =>
+                {
+                    Set((reg, Val(after[reg as usize].lo)))
+                }
+                Add((reg, rval)) => Add((reg, constant(rval))),
+                Mul((reg, rval)) => Mul((reg, constant(rval))),
+                Div((reg, rval)) => Div((reg, constant(rval))),
+                Mod((reg, rval)) => Mod((reg, constant(rval))),
+                Eql((reg, rval)) => Eql((reg, constant(rval))),
+                Neq((reg, rval)) => Neq((reg, constant(rval))),
+                Set((reg, rval)) => Set((reg, constant(rval))),
+                Jmp(_) | Jnz(_) | Jez(_) => instr.clone(),
+            };
+            if !matches!(
+                instr,
+                Add((_, Val(0))) | Mul((_, Val(1))) | Div((_, Val(1)))
+            ) {
+                new_instructions.push(instr);
+            }
+        }
+        self.instructions = new_instructions;
+    }
+
+    /// Drops instructions whose result is overwritten before it's read. Every register
+    /// is considered live once the program ends, and `inp`s are always kept since they
+    /// consume input.
+    fn eliminate_dead_stores(&mut self) {
+        let mut live = [true; 4];
+        let mut keep = vec![true; self.instructions.len()];
+        for (i, instr) in self.instructions.iter().enumerate().rev() {
+            let (dest, reads_dest, src) = match *instr {
+                Inp(reg) => (reg, false, None),
+                Set((reg, rval)) => (reg, false, Some(rval)),
+                Add((reg, rval)) | Mul((reg, rval)) | Div((reg, rval))
+                | Mod((reg, rval)) | Eql((reg, rval)) | Neq((reg, rval)) => {
+                    (reg, true, Some(rval))
+                }
+                // Not supported; leave the program alone.
+                Jmp(_) | Jnz(_) | Jez(_) => return,
+            };
+            if !live[dest as usize] && !matches!(instr, Inp(_)) {
+                keep[i] = false;
+                continue;
+            }
+            live[dest as usize] = reads_dest;
+            if let Some(Reg(reg)) = src {
+                live[reg as usize] = true;
+            }
+        }
+        let mut keep = keep.into_iter();
+        self.instructions.retain(|_| keep.next().unwrap());
+    }
+}
+
+/// For each stage `i` of a MONAD program, the largest `z` it can start with and still
+/// end the program with `z == 0`, derived from the stage's range facts. Values past
+/// the last stage are `0`. A limit of -1 means no `z` works.
+fn z_limits(program: &Program) -> AocResult<Vec<i64>> {
+    let num_stages = program
+        .instructions
+        .iter()
+        .filter(|i| matches!(i, Inp(_)))
+        .count();
+    let mut limits = vec![0; num_stages + 1];
+    for stage in (0..num_stages).rev() {
+        let subprogram = program.subprogram(stage, stage + 1)?;
+        let limit = limits[stage + 1];
+        // The smallest z out of the stage for any z in at least `t`. Nondecreasing in t,
+        // so binary search for the last t that can still get under the limit.
+        let min_z_out = |t: i64| -> AocResult<i64> {
+            let mut entry = [Interval::FULL; 4];
+            entry[Z as usize] = Interval::new(t, i64::MAX);
+            Ok(subprogram.analyze(entry)?.last().unwrap()[Z as usize].lo)
+        };
+        let (mut lo, mut hi) = (0, 1 << 50);
+        if min_z_out(lo)? > limit {
+            limits[stage] = -1;
+            continue;
+        }
+        while lo < hi {
+            let mid = lo + (hi - lo + 1) / 2;
+            if min_z_out(mid)? <= limit {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        limits[stage] = lo;
+    }
+    Ok(limits)
+}
+
+/// One executed instruction, with the registers as it left them.
+#[derive(Clone, Debug, PartialEq)]
+struct TraceStep {
+    pc: usize,
+    instr: Instruction,
+    registers: [i64; 4],
+}
+
+impl fmt::Display for TraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [w, x, y, z] = self.registers;
+        write!(
+            f,
+            "{:4}  {:<12} w={w} x={x} y={y} z={z}",
+            self.pc,
+            self.instr.to_string()
+        )
+    }
+}
+
+/// How many instructions a `Cpu` executes per run before giving up on the program
+/// halting, unless told otherwise. A MONAD stage takes 18.
+pub const DEFAULT_STEP_LIMIT: u64 = 1 << 28;
+
+#[derive(Clone)]
+pub struct Cpu {
+    registers: [Register; 4],
+    /// Index of the next instruction to execute.
+    pc: usize,
+    /// Instructions executed since the last `exec` or `reset`.
+    steps: u64,
+    step_limit: u64,
+    /// Every step executed since tracing was turned on, if it is.
+    trace: Option<Vec<TraceStep>>,
+}
+
+impl Default for Cpu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cpu {
+    pub fn new() -> Self {
+        Self {
+            registers: [Register(0); 4],
+            pc: 0,
+            steps: 0,
+            step_limit: DEFAULT_STEP_LIMIT,
+            trace: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        for r in &mut self.registers {
+            r.0 = 0;
+        }
+        self.pc = 0;
+        self.steps = 0;
+    }
+
+    /// Makes runs fail once they've executed `limit` instructions without halting.
+    pub fn set_step_limit(&mut self, limit: u64) {
+        self.step_limit = limit;
+    }
+
+    /// Starts (or stops) recording a `TraceStep` for every executed instruction.
+    fn set_tracing(&mut self, on: bool) {
+        self.trace = on.then(Vec::new);
+    }
+
+    /// Returns the steps recorded so far, leaving tracing on with an empty trace.
+    fn take_trace(&mut self) -> Vec<TraceStep> {
+        self.trace.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    fn read_register(&self, regname: RegisterName) -> i64 {
+        self.registers[regname as usize].0
+    }
+
+    fn write_register(&mut self, regname: RegisterName, value: i64) {
+        self.registers[regname as usize].0 = value;
+    }
+
+    fn read_rval(&self, rval: RVal) -> i64 {
+        match rval {
+            Reg(reg) => self.read_register(reg),
+            Val(val) => val,
+        }
+    }
+
+    fn extract_operands(&self, regname: RegisterName, rval: RVal) -> (i64, i64) {
+        (self.read_register(regname), self.read_rval(rval))
+    }
+
+    /// Moves the program counter by `offset`. Jumping before the first instruction
+    /// halts the program, just like jumping past the last one.
+    fn jump(&mut self, offset: RVal) {
+        let offset = self.read_rval(offset);
+        self.pc = isize::try_from(offset)
+            .ok()
+            .and_then(|o| self.pc.checked_add_signed(o))
+            .unwrap_or(usize::MAX);
+    }
+
+    fn add(&mut self, regname: RegisterName, rval: RVal) {
+        let (lhs, rhs) = self.extract_operands(regname, rval);
+        self.write_register(regname, lhs + rhs);
+    }
+
+    fn mul(&mut self, regname: RegisterName, rval: RVal) {
+        let (lhs, rhs) = self.extract_operands(regname, rval);
+        self.write_register(regname, lhs * rhs);
+    }
+
+    fn div(&mut self, regname: RegisterName, rval: RVal) {
+        let (lhs, rhs) = self.extract_operands(regname, rval);
+        self.write_register(regname, lhs / rhs);
+    }
+
+    fn rem(&mut self, regname: RegisterName, rval: RVal) {
+        let (lhs, rhs) = self.extract_operands(regname, rval);
+        self.write_register(regname, lhs % rhs);
+    }
+
+    fn eql(&mut self, regname: RegisterName, rval: RVal) {
+        let (lhs, rhs) = self.extract_operands(regname, rval);
+        self.write_register(regname, if lhs == rhs { 1 } else { 0 });
+    }
+
+    fn neq(&mut self, regname: RegisterName, rval: RVal) {
+        let (lhs, rhs) = self.extract_operands(regname, rval);
+        self.write_register(regname, if lhs == rhs { 0 } else { 1 });
+    }
+
+    /// Executes `instr` and advances the program counter.
+    fn exec_instr(
+        &mut self,
+        instr: &Instruction,
+        input: &mut slice::Iter<i8>,
+    ) -> AocResult<()> {
+        match instr {
+            Jmp(offset) => {
+                self.jump(*offset);
+                return Ok(());
+            }
+            Jnz((cond, offset)) if self.read_rval(*cond) != 0 => {
+                self.jump(*offset);
+                return Ok(());
+            }
+            Jez((cond, offset)) if self.read_rval(*cond) == 0 => {
+                self.jump(*offset);
+                return Ok(());
+            }
+            Jnz(_) | Jez(_) => (),
+            Inp(regname) => self.write_register(
+                *regname,
+                *input.next().ok_or("Input buffer underrun?")? as i64,
+            ),
+            Add((regname, rval)) => self.add(*regname, *rval),
+            Mul((regname, rval)) => self.mul(*regname, *rval),
+            Div((regname, rval)) => self.div(*regname, *rval),
+            Mod((regname, rval)) => self.rem(*regname, *rval),
+            Eql((regname, rval)) => self.eql(*regname, *rval),
+            Neq((regname, rval)) => self.neq(*regname, *rval),
+            Set((regname, rval)) => self.write_register(*regname, self.read_rval(*rval)),
+        }
+        self.pc += 1;
+        Ok(())
+    }
+
+    /// Runs `program` from its first instruction until the program counter leaves it.
+    /// Registers are left as they are, so callers can seed them beforehand.
+    fn exec(&mut self, program: &Program, input: &[i8]) -> AocResult<()> {
+        self.pc = 0;
+        self.steps = 0;
+        self.run_until(program, &mut input.iter(), |_| false)?;
+        Ok(())
+    }
+
+    /// Runs `program` from the current program counter, stopping after any instruction
+    /// once `breakpoint` returns true for the CPU's state. Returns whether it stopped at
+    /// a breakpoint rather than by leaving the program; in the former case, calling
+    /// this again with the same `input` resumes where it left off. Fails if the step
+    /// limit is reached first.
+    fn run_until<F>(
+        &mut self,
+        program: &Program,
+        input: &mut slice::Iter<i8>,
+        mut breakpoint: F,
+    ) -> AocResult<bool>
+    where
+        F: FnMut(&Cpu) -> bool,
+    {
+        while let Some(instr) = program.instructions.get(self.pc) {
+            let pc = self.pc;
+            if self.steps == self.step_limit {
+                failure!("No halt after {} steps; stopped at pc {pc}", self.steps);
+            }
+            self.steps += 1;
+            self.exec_instr(instr, input)?;
+            if let Some(trace) = &mut self.trace {
+                trace.push(TraceStep {
+                    pc,
+                    instr: instr.clone(),
+                    registers: self.registers.map(|r| r.0),
+                });
+            }
+            if breakpoint(self) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+impl fmt::Display for RegisterName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            W => "w",
+            X => "x",
+            Y => "y",
+            Z => "z",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl fmt::Display for RVal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Reg(reg) => write!(f, "{reg}"),
+            Val(val) => write!(f, "{val}"),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Inp(reg) => write!(f, "inp {reg}"),
+            Add((reg, rval)) => write!(f, "add {reg} {rval}"),
+            Mul((reg, rval)) => write!(f, "mul {reg} {rval}"),
+            Div((reg, rval)) => write!(f, "div {reg} {rval}"),
+            Mod((reg, rval)) => write!(f, "mod {reg} {rval}"),
+            Eql((reg, rval)) => write!(f, "eql {reg} {rval}"),
+            Neq((reg, rval)) => write!(f, "neq {reg} {rval}"),
+            Set((reg, rval)) => write!(f, "set {reg} {rval}"),
+            Jmp(offset) => write!(f, "jmp {offset}"),
+            Jnz((cond, offset)) => write!(f, "jnz {cond} {offset}"),
+            Jez((cond, offset)) => write!(f, "jez {cond} {offset}"),
+        }
+    }
+}
+
+fn parse_register_name(regname: &str) -> AocResult<RegisterName> {
+    match regname {
+        "w" => Ok(W),
+        "x" => Ok(X),
+        "y" => Ok(Y),
+        "z" => Ok(Z),
+        x => failure(format!("Bad register name {x}")),
+    }
+}
+
+fn parse_rval(rval: &str) -> AocResult<RVal> {
+    match rval {
+        "w" | "x" | "y" | "z" => Ok(Reg(parse_register_name(rval)?)),
+        x => Ok(Val(x.parse::<i64>()?)),
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = Box<dyn error::Error>;
+    fn from_str(s: &str) -> AocResult<Instruction> {
+        let mut split = s.split(' ');
+        let instr = match split.next().ok_or("No opcode?")? {
+            "inp" => Inp(parse_register_name(
+                split.next().ok_or("No register name?")?,
+            )?),
+            "add" => Add((
+                parse_register_name(split.next().ok_or("No register name?")?)?,
+                parse_rval(split.next().ok_or("No rval?")?)?,
+            )),
+            "mul" => Mul((
+                parse_register_name(split.next().ok_or("No register name?")?)?,
+                parse_rval(split.next().ok_or("No rval?")?)?,
+            )),
+            "div" => Div((
+                parse_register_name(split.next().ok_or("No register name?")?)?,
+                parse_rval(split.next().ok_or("No rval?")?)?,
+            )),
+            "mod" => Mod((
+                parse_register_name(split.next().ok_or("No register name?")?)?,
+                parse_rval(split.next().ok_or("No rval?")?)?,
+            )),
+            "eql" => Eql((
+                parse_register_name(split.next().ok_or("No register name?")?)?,
+                parse_rval(split.next().ok_or("No rval?")?)?,
+            )),
+            "neq" => Neq((
+                parse_register_name(split.next().ok_or("No register name?")?)?,
+                parse_rval(split.next().ok_or("No rval?")?)?,
+            )),
+            "set" => Set((
+                parse_register_name(split.next().ok_or("No register name?")?)?,
+                parse_rval(split.next().ok_or("No rval?")?)?,
+            )),
+            "jmp" => Jmp(parse_rval(split.next().ok_or("No offset?")?)?),
+            "jnz" => Jnz((
+                parse_rval(split.next().ok_or("No condition?")?)?,
+                parse_rval(split.next().ok_or("No offset?")?)?,
+            )),
+            "jez" => Jez((
+                parse_rval(split.next().ok_or("No condition?")?)?,
+                parse_rval(split.next().ok_or("No offset?")?)?,
+            )),
+            x => failure!("Bad opcode {x}"),
+        };
+        if let Some(extra) = split.next() {
+            failure!("Unexpected operand {extra}");
+        }
+
+        Ok(instr)
+    }
+}
+
+fn parse_input(lines: &[String]) -> AocResult<Program> {
+    let _t = timed("parse");
+    let mut prog = Program::from_listing(lines)?;
+    let _o = timed("optimize");
+    prog.optimize();
+    Ok(prog)
+}
+
+fn solve(program: &Program, find_min: bool, progress: &mut Progress) -> AocResult<i64> {
+    let _t = timed("search");
+    // Maps from zout -> input used to get that zout.
+    let mut zt = Arc::new(FastMap::default());
+    let mut ztactive = vec![];
+    for _ in 0..9 {
+        ztactive.push(Arc::new(Mutex::new(FastMap::default())));
+    }
+    let target_input = if find_min {
+        99999999999999i64
+    } else {
+        11111111111111i64
+    };
+    Arc::get_mut(&mut zt).unwrap().insert(0, 0);
+    let limits = z_limits(program)?;
+
+    for i in 0..=13 {
+        let subprogram = Arc::new(program.subprogram(i, i + 1)?);
+        let limit = limits[i + 1];
+        let mut handles = vec![];
+        for j in 1..=9 {
+            let zt = Arc::clone(&zt);
+            let zta = Arc::clone(&ztactive[j - 1]);
+            let subprogram = Arc::clone(&subprogram);
+            handles.push(thread::spawn(move || -> Result<(), String> {
+                let mut target_input = target_input;
+                let mut zta = zta.lock().unwrap();
+                zta.clear();
+                for (zout, input) in &*zt {
+                    let mut cpu = Cpu::new();
+                    cpu.write_register(Z, *zout);
+                    // AocResult's errors can't cross threads; their messages can.
+                    cpu.exec(&subprogram, &[j as i8])
+                        .map_err(|e| e.to_string())?;
+                    let z = cpu.read_register(Z);
+                    let new_input = 10 * *input + j as i64;
+                    if i < 13 {
+                        if z > limit {
+                            // Too big to ever get back down to 0.
+                            continue;
+                        }
+                        zta.entry(z)
+                            .and_modify(|e| {
+                                if (find_min && new_input < *e)
+                                    || (!find_min && new_input > *e)
+                                {
+                                    *e = new_input;
+                                }
+                            })
+                            .or_insert(new_input);
+                    } else if z == 0 {
+                        target_input = if find_min {
+                            min(target_input, new_input)
+                        } else {
+                            max(target_input, new_input)
+                        };
+                        if target_input == new_input {
+                            zta.insert(j as i64, target_input);
+                        }
+                    }
+                }
+                Ok(())
+            }));
+        }
+        for handle in handles {
+            handle.join().unwrap()?;
+        }
+        let zt = Arc::get_mut(&mut zt).unwrap();
+        zt.clear();
+        for zta in &ztactive {
+            for (k, v) in &*zta.lock().unwrap() {
+                zt.entry(*k)
+                    .and_modify(|e| {
+                        if (find_min && v < e) || (!find_min && v > e) {
+                            *e = *v;
+                        }
+                    })
+                    .or_insert(*v);
+            }
+        }
+        progress.report(i as u64 + 1, Some(14));
+    }
+
+    let out = if find_min {
+        zt.values().min()
+    } else {
+        zt.values().max()
+    };
+    Ok(*out.unwrap())
+}
+
+/// Runs a single stage on `z` and `digit`, returning the new `z`.
+fn run_stage(stage: &Program, z: i64, digit: i8) -> AocResult<i64> {
+    let mut cpu = Cpu::new();
+    cpu.write_register(Z, z);
+    cpu.exec(stage, &[digit])?;
+    Ok(cpu.read_register(Z))
+}
+
+/// For each stage, maps every `z` that can reach it to the number of digit sequences
+/// that take it from there to `z == 0` at the end. Only `z`s with at least one such
+/// sequence are kept. The final element is `{0: 1}`.
+fn suffix_counts(program: &Program) -> AocResult<Vec<FastMap<i64, u64>>> {
+    let limits = z_limits(program)?;
+    let num_stages = limits.len() - 1;
+    let stages = (0..num_stages)
+        .map(|i| program.subprogram(i, i + 1))
+        .collect::<AocResult<Vec<_>>>()?;
+
+    // Forwards: which z values can enter each stage at all.
+    let mut reachable = vec![FastSet::from_iter([0])];
+    for (i, stage) in stages.iter().enumerate() {
+        let mut next = FastSet::default();
+        for &z in &reachable[i] {
+            for digit in 1..=9 {
+                let z = run_stage(stage, z, digit)?;
+                if z <= limits[i + 1] {
+                    next.insert(z);
+                }
+            }
+        }
+        reachable.push(next);
+    }
+
+    // Backwards: how many ways each of them has to finish.
+    let mut counts = vec![FastMap::default(); num_stages + 1];
+    counts[num_stages].insert(0, 1);
+    for (i, stage) in stages.iter().enumerate().rev() {
+        for &z in &reachable[i] {
+            let mut count = 0;
+            for digit in 1..=9 {
+                count += counts[i + 1]
+                    .get(&run_stage(stage, z, digit)?)
+                    .unwrap_or(&0);
+            }
+            if count > 0 {
+                counts[i].insert(z, count);
+            }
+        }
+    }
+    Ok(counts)
+}
+
+/// Writes every valid model number to `out` in ascending order, one per line, without
+/// holding them all in memory. `counts` comes from `suffix_counts`.
+fn write_valid(
+    program: &Program,
+    counts: &[FastMap<i64, u64>],
+    out: &mut impl Write,
+) -> AocResult<()> {
+    let stages = (0..counts.len() - 1)
+        .map(|i| program.subprogram(i, i + 1))
+        .collect::<AocResult<Vec<_>>>()?;
+    // Depth first, with (stage, z, model number so far, next digit to try).
+    let mut stack = vec![(0, 0, 0, 1)];
+    while let Some((i, z, prefix, digit)) = stack.pop() {
+        if i == stages.len() {
+            writeln!(out, "{prefix}")?;
+            continue;
+        }
+        if digit > 9 {
+            continue;
+        }
+        stack.push((i, z, prefix, digit + 1));
+        let next_z = run_stage(&stages[i], z, digit)?;
+        if counts[i + 1].contains_key(&next_z) {
+            stack.push((i + 1, next_z, 10 * prefix + digit as i64, 1));
+        }
+    }
+    Ok(())
+}
+
+/// Splits a model number into its digits, most significant first.
+fn digits(model_number: i64) -> Vec<i8> {
+    model_number
+        .to_string()
+        .bytes()
+        .map(|b| (b - b'0') as i8)
+        .collect()
+}
+
+/// Prints every step `program` takes on `model_number` to stderr.
+fn print_trace(program: &Program, model_number: i64) -> AocResult<()> {
+    let mut cpu = Cpu::new();
+    cpu.set_tracing(true);
+    cpu.exec(program, &digits(model_number))?;
+    eprintln!("Trace for {model_number}:");
+    for step in cpu.take_trace() {
+        eprintln!("{step}");
+    }
+    Ok(())
+}
+
+/// Like `solver::run_cli`, but with flags to print the optimized program, every valid
+/// model number, or traces of the answers instead.
+pub fn main() -> AocResult<()> {
+    let args = Args::from_env(&["--all", "--listing", "--trace"], &[])?;
+    let lines: Vec<String> = read_input(&args.input_path(24)?)?
+        .lines()
+        .map(String::from)
+        .collect();
+    let program = parse_input(&lines)?;
+    if args.flag("--listing") {
+        // The optimized program, for diffing against the input.
+        for line in program.to_listing() {
+            println!("{line}");
+        }
+        return Ok(());
+    }
+    if args.flag("--all") {
+        let counts = suffix_counts(&program)?;
+        let mut out = io::BufWriter::new(io::stdout().lock());
+        write_valid(&program, &counts, &mut out)?;
+        out.flush()?;
+        eprintln!("{} valid model numbers", counts[0].get(&0).unwrap_or(&0));
+        return Ok(());
+    }
+    for (part, find_min) in [(1, false), (2, true)] {
+        if !args.wants_part(part) {
+            continue;
+        }
+        let _t = timed(format!("part {part}"));
+        let label = format!("Part {part} stages");
+        let model_number = solve(&program, find_min, &mut Progress::log(&label))?;
+        if args.flag("--trace") {
+            print_trace(&program, model_number)?;
+        }
+        println!("Part {part}: {model_number}");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, read_lines};
+
+    #[test]
+    fn simple_tests() -> AocResult<()> {
+        let mut cpu = Cpu::new();
+
+        // X <- negation of first input.
+        #[rustfmt::skip]
+        let prog = Program::from_listing(&[
+            "inp x",
+            "mul x -1"
+        ])?;
+        let input = [5];
+        cpu.exec(&prog, &input)?;
+        assert_eq!(cpu.read_register(X), -5);
+
+        cpu.reset();
+        assert_eq!(cpu.read_register(X), 0);
+
+        // Z <- second input / first input == 3.
+        #[rustfmt::skip]
+        let prog = Program::from_listing(&[
+            "inp z",
+            "inp x",
+            "mul z 3",
+            "eql z x"
+        ])?;
+        let input = [-3, -9];
+        cpu.exec(&prog, &input)?;
+        assert_eq!(cpu.read_register(Z), 1);
+        cpu.reset();
+
+        let input = [2, -9];
+        cpu.exec(&prog, &input)?;
+        assert_eq!(cpu.read_register(Z), 0);
+        cpu.reset();
+
+        // Z <- bit 0 of first input, Y <- bit 1, X <- bit 2, W <- bit 3.
+        #[rustfmt::skip]
+        let prog = Program::from_listing(&[
+            "inp w",
+            "add z w",
+            "mod z 2",
+            "div w 2",
+            "add y w",
+            "mod y 2",
+            "div w 2",
+            "add x w",
+            "mod x 2",
+            "div w 2",
+            "mod w 2",
+        ])?;
+        let input = [7];
+        cpu.exec(&prog, &input)?;
+        assert_eq!(cpu.read_register(Z), 1);
+        assert_eq!(cpu.read_register(Y), 1);
+        assert_eq!(cpu.read_register(X), 1);
+        assert_eq!(cpu.read_register(W), 0);
+        cpu.reset();
+
+        let input = [8];
+        cpu.exec(&prog, &input)?;
+        assert_eq!(cpu.read_register(Z), 0);
+        assert_eq!(cpu.read_register(Y), 0);
+        assert_eq!(cpu.read_register(X), 0);
+        assert_eq!(cpu.read_register(W), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn jumps() -> AocResult<()> {
+        let mut cpu = Cpu::new();
+
+        // Z <- first input * second input, by repeated addition.
+        #[rustfmt::skip]
+        let prog = Program::from_listing(&[
+            "inp x",
+            "inp y",
+            "jez y 4",
+            "add z x",
+            "add y -1",
+            "jmp -3",
+            "set w z",
+        ])?;
+        cpu.exec(&prog, &[7, 6])?;
+        assert_eq!(cpu.read_register(Z), 42);
+        assert_eq!(cpu.read_register(W), 42);
+        cpu.reset();
+
+        cpu.exec(&prog, &[7, 0])?;
+        assert_eq!(cpu.read_register(Z), 0);
+        cpu.reset();
+
+        // A negative count never reaches 0, so the loop only ends at the step limit.
+        cpu.set_step_limit(1000);
+        let err = cpu.exec(&prog, &[7, -1]).unwrap_err();
+        assert_eq!(err.to_string(), "No halt after 1000 steps; stopped at pc 4");
+        // The limit is per run.
+        cpu.reset();
+        cpu.exec(&prog, &[7, 6])?;
+        assert_eq!(cpu.read_register(Z), 42);
+        cpu.reset();
+
+        // Jumping out of the program in either direction halts it.
+        #[rustfmt::skip]
+        let prog = Program::from_listing(&[
+            "inp w",
+            "jnz w -5",
+            "set x 1",
+            "jnz 1 5",
+            "set y 1",
+        ])?;
+        cpu.exec(&prog, &[1])?;
+        assert_eq!(cpu.read_register(X), 0);
+        cpu.reset();
+        cpu.exec(&prog, &[0])?;
+        assert_eq!(cpu.read_register(X), 1);
+        assert_eq!(cpu.read_register(Y), 0);
+
+        let err = Program::from_listing(&["inp w", "jnz w", "add x 1"]).unwrap_err();
+        assert!(err.to_string().starts_with("Line 2:"), "{err}");
+        let err = Program::from_listing(&["inp w", "add x 1 2"]).unwrap_err();
+        assert!(err.to_string().starts_with("Line 2:"), "{err}");
+        let err = Program::from_listing(&["foo w"]).unwrap_err();
+        assert!(err.to_string().contains("Bad opcode foo"), "{err}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn trace_and_breakpoints() -> AocResult<()> {
+        #[rustfmt::skip]
+        let prog = Program::from_listing(&[
+            "inp x",
+            "inp y",
+            "jez y 4",
+            "add z x",
+            "add y -1",
+            "jmp -3",
+        ])?;
+        let mut cpu = Cpu::new();
+        cpu.set_tracing(true);
+        cpu.exec(&prog, &[7, 2])?;
+        let trace = cpu.take_trace();
+        let pcs: Vec<_> = trace.iter().map(|s| s.pc).collect();
+        assert_eq!(pcs, [0, 1, 2, 3, 4, 5, 2, 3, 4, 5, 2]);
+        assert_eq!(
+            trace[3],
+            TraceStep {
+                pc: 3,
+                instr: Add((Z, Reg(X))),
+                registers: [0, 7, 2, 7]
+            }
+        );
+        assert_eq!(trace[3].to_string(), "   3  add z x      w=0 x=7 y=2 z=7");
+        assert!(cpu.take_trace().is_empty());
+
+        // Stop every time z changes, then resume.
+        cpu.reset();
+        cpu.set_tracing(false);
+        let input = [5, 3];
+        let mut input = input.iter();
+        let mut zs = vec![];
+        let mut last_z = 0;
+        while cpu.run_until(&prog, &mut input, |cpu| cpu.read_register(Z) != last_z)? {
+            last_z = cpu.read_register(Z);
+            zs.push((cpu.pc(), last_z));
+        }
+        assert_eq!(zs, [(4, 5), (4, 10), (4, 15)]);
+        assert!(cpu.take_trace().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn listing_round_trip() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        let mut program = Program::from_listing(&lines)?;
+        assert_eq!(program.to_listing(), lines);
+        program.optimize();
+        assert_eq!(Program::from_listing(&program.to_listing())?, program);
+
+        #[rustfmt::skip]
+        let mut prog = Program::from_listing(&[
+            "inp w",
+            "mul x 0",
+            "add x 26",
+            "div z 1",
+            "eql x w",
+            "eql x 0",
+            "jnz x -2",
+        ])?;
+        let original = prog.clone();
+        prog.optimize();
+        assert_eq!(
+            prog.to_listing(),
+            ["inp w", "mul x 0", "add x 26", "div z 1", "eql x w", "eql x 0", "jnz x -2"]
+        );
+        // Only w = 1 gets out of the loop.
+        let (mut a, mut b) = (Cpu::new(), Cpu::new());
+        a.exec(&original, &[1])?;
+        b.exec(&prog, &[1])?;
+        for reg in [W, X, Y, Z] {
+            assert_eq!(a.read_register(reg), b.read_register(reg), "{reg:?}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn optimize_with_jumps() -> AocResult<()> {
+        // Adds w to z three times. The loop body holds a `div z 1` and an `eql; eql 0`
+        // pair that the peephole pass would drop or merge, moving the jump target.
+        #[rustfmt::skip]
+        let original = Program::from_listing(&[
+            "inp w",
+            "mul x 0",
+            "add x 3",
+            "add z w",
+            "div z 1",
+            "add x -1",
+            "mul y 0",
+            "eql y x",
+            "eql y 0",
+            "jnz y -6",
+        ])?;
+        let mut optimized = original.clone();
+        optimized.optimize();
+        for w in 1..=9 {
+            let (mut a, mut b) = (Cpu::new(), Cpu::new());
+            a.exec(&original, &[w])?;
+            b.exec(&optimized, &[w])?;
+            assert_eq!(a.read_register(Z), 3 * i64::from(w));
+            for reg in [W, X, Y, Z] {
+                assert_eq!(
+                    a.read_register(reg),
+                    b.read_register(reg),
+                    "{reg:?}, w = {w}"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn range_analysis() -> AocResult<()> {
+        #[rustfmt::skip]
+        let prog = Program::from_listing(&[
+            "inp w",
+            "add x w",
+            "add x 10",
+            "eql x w",
+            "mod z 26",
+            "div z 26",
+            "add y w",
+            "mul y -3",
+            "mod y 5",
+        ])?;
+        let mut entry = [Interval::constant(0); 4];
+        entry[Z as usize] = Interval::new(0, 25);
+        let facts = prog.analyze(entry)?;
+        assert_eq!(facts.len(), prog.instructions.len() + 1);
+        assert_eq!(facts[1][W as usize], DIGITS);
+        assert_eq!(facts[3][X as usize], Interval::new(11, 19));
+        assert_eq!(facts[4][X as usize], Interval::constant(0));
+        assert_eq!(facts[5][Z as usize], Interval::new(0, 25));
+        assert_eq!(facts[6][Z as usize], Interval::constant(0));
+        assert_eq!(facts[8][Y as usize], Interval::new(-27, -3));
+        assert_eq!(facts[9][Y as usize], Interval::new(-4, 0));
+
+        let mut jumpy = Program::from_listing(&["jmp 1"])?;
+        assert!(jumpy.analyze(entry).is_err());
+        jumpy.optimize();
+        assert_eq!(jumpy.to_listing(), ["jmp 1"]);
+
+        // The impossible eql becomes a constant, the division of a value that's
+        // already 0 goes away, and so do the stores that are never read.
+        #[rustfmt::skip]
+        let mut prog = Program::from_listing(&[
+            "inp w",
+            "mul x 0",
+            "add x z",
+            "add x 12",
+            "eql x w",
+            "div z 26",
+            "add y 25",
+            "mul y x",
+            "add y 1",
+            "mul z y",
+            "mul y 0",
+            "add y w",
+            "add z y",
+        ])?;
+        prog.optimize();
+        assert_eq!(
+            prog.to_listing(),
+            ["inp w", "set x 0", "set z 0", "set y 0", "add y w", "add z y"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn optimized_matches_original() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        let original = Program::from_listing(&lines)?;
+        let optimized = parse_input(&lines)?;
+        assert!(optimized.instructions.len() < original.instructions.len());
+
+        let mut seed = 1u64;
+        for _ in 0..200 {
+            let input: Vec<i8> = (0..14)
+                .map(|_| {
+                    seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                    (seed >> 33) as i8 % 9 + 1
+                })
+                .collect();
+            let (mut a, mut b) = (Cpu::new(), Cpu::new());
+            a.exec(&original, &input)?;
+            b.exec(&optimized, &input)?;
+            assert_eq!(a.read_register(Z), b.read_register(Z), "{input:?}");
+        }
+
+        let limits = z_limits(&optimized)?;
+        // The first stage was specialized for z == 0 on entry, so its limit is moot.
+        assert_eq!(
+            limits[1..],
+            [
+                25, 675, 17575, 456975, 11881375, 456975, 17575, 456975, 17575, 456975,
+                17575, 675, 25, 0
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        let program = parse_input(&lines)?;
+        let mut cpu = Cpu::new();
+        cpu.exec(&program, &[1, 9, 9, 8, 9, 2, 9, 7, 9, 4, 9, 5, 1, 8])?;
+        let z = cpu.read_register(Z);
+        assert_eq!(z, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn all_valid() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        let program = parse_input(&lines)?;
+        let counts = suffix_counts(&program)?;
+        let mut out = Vec::new();
+        write_valid(&program, &counts, &mut out)?;
+        let valid: Vec<i64> = String::from_utf8(out)?
+            .lines()
+            .map(|l| l.parse())
+            .collect::<Result<_, _>>()?;
+        assert_eq!(valid.len() as u64, counts[0][&0]);
+        assert!(valid.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(valid.first(), Some(&19518121316118));
+        assert_eq!(valid.last(), Some(&29989297949519));
+        assert_eq!(valid.len(), 4480);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        let program = parse_input(&lines)?;
+        assert_eq!(
+            solve(&program, false, &mut Progress::none())?,
+            29989297949519
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part_2_input() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        let program = parse_input(&lines)?;
+        assert_eq!(
+            solve(&program, true, &mut Progress::none())?,
+            19518121316118
+        );
+        Ok(())
+    }
+}
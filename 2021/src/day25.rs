@@ -0,0 +1,102 @@
+use aoc_util::{
+    errors::AocResult,
+    grid::{Grid, NeighbourPattern},
+    point::Point,
+    solver::Solver,
+};
+
+pub struct Day25;
+
+impl Solver for Day25 {
+    fn part1(&self, input: &str) -> AocResult<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    /// There's no second puzzle on day 25.
+    fn part2(&self, _input: &str) -> AocResult<String> {
+        Ok(String::from("n/a"))
+    }
+}
+
+pub fn part1(input: &str) -> AocResult<usize> {
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    part_1(&parse_input(&lines)?)
+}
+
+const EMPTY: u8 = 0;
+const EAST: u8 = 1;
+const SOUTH: u8 = 2;
+
+fn parse_input(lines: &[String]) -> AocResult<Grid> {
+    let map_func = |c| match c {
+        '.' => Some(EMPTY),
+        '>' => Some(EAST),
+        'v' => Some(SOUTH),
+        _ => None,
+    };
+    let mut grid = Grid::from_symbol_matrix(lines, map_func)?;
+    grid.make_toroidal(true);
+    Ok(grid)
+}
+
+/// Moves every member of `herd` that faces an empty cell, all at once. Returns the new
+/// grid and whether anything moved.
+fn move_herd(grid: &Grid, herd: u8) -> AocResult<(Grid, bool)> {
+    // Index into a Compass4 neighbourhood, which goes N W E S.
+    let facing = if herd == EAST { 2 } else { 3 };
+    let mut next = grid.clone();
+    let mut moved = false;
+    for i in 0..grid.num_rows() {
+        for j in 0..grid.num_cols() {
+            let p = Point::new(i, j);
+            if grid.at(p)? != herd {
+                continue;
+            }
+            let neighbours = grid.neighbourhood(p, NeighbourPattern::Compass4)?;
+            let (target, v) = neighbours[facing].ok_or("Grid isn't toroidal?")?;
+            if v == EMPTY {
+                next.set(target, herd)?;
+                next.set(p, EMPTY)?;
+                moved = true;
+            }
+        }
+    }
+    Ok((next, moved))
+}
+
+/// The first step on which no sea cucumber moves.
+fn part_1(grid: &Grid) -> AocResult<usize> {
+    let mut grid = grid.clone();
+    let mut steps = 1;
+    loop {
+        let (east_moved, moved_east) = move_herd(&grid, EAST)?;
+        let (south_moved, moved_south) = move_herd(&east_moved, SOUTH)?;
+        if !moved_east && !moved_south {
+            return Ok(steps);
+        }
+        grid = south_moved;
+        steps += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::{get_input_file, get_test_file, read_lines};
+
+    #[test]
+    fn part_1_test() -> AocResult<()> {
+        let lines = read_lines(&get_test_file(file!())?)?;
+        let grid = parse_input(&lines)?;
+        assert_eq!(part_1(&grid)?, 58);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1_input() -> AocResult<()> {
+        let lines = read_lines(&get_input_file(file!())?)?;
+        let grid = parse_input(&lines)?;
+        assert_eq!(part_1(&grid)?, 498);
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+//! Puzzle inputs compiled into the binary with `include_str!`, so that solvers can run
+//! without a filesystem (e.g. in a CI sandbox or on WASM). The `embed-tests` feature
+//! embeds each day's example input, and `embed-inputs` the personal puzzle inputs.
+
+/// Expands to an array of the 25 days' `data/` files of the given kind, in day order.
+/// Day 24 has no example, so its entry is given separately.
+#[allow(unused_macros)]
+macro_rules! day_files {
+    ($kind:literal, $day24:expr) => {
+        [
+            include_str!(concat!("../data/01_", $kind, ".txt")),
+            include_str!(concat!("../data/02_", $kind, ".txt")),
+            include_str!(concat!("../data/03_", $kind, ".txt")),
+            include_str!(concat!("../data/04_", $kind, ".txt")),
+            include_str!(concat!("../data/05_", $kind, ".txt")),
+            include_str!(concat!("../data/06_", $kind, ".txt")),
+            include_str!(concat!("../data/07_", $kind, ".txt")),
+            include_str!(concat!("../data/08_", $kind, ".txt")),
+            include_str!(concat!("../data/09_", $kind, ".txt")),
+            include_str!(concat!("../data/10_", $kind, ".txt")),
+            include_str!(concat!("../data/11_", $kind, ".txt")),
+            include_str!(concat!("../data/12_", $kind, ".txt")),
+            include_str!(concat!("../data/13_", $kind, ".txt")),
+            include_str!(concat!("../data/14_", $kind, ".txt")),
+            include_str!(concat!("../data/15_", $kind, ".txt")),
+            include_str!(concat!("../data/16_", $kind, ".txt")),
+            include_str!(concat!("../data/17_", $kind, ".txt")),
+            include_str!(concat!("../data/18_", $kind, ".txt")),
+            include_str!(concat!("../data/19_", $kind, ".txt")),
+            include_str!(concat!("../data/20_", $kind, ".txt")),
+            include_str!(concat!("../data/21_", $kind, ".txt")),
+            include_str!(concat!("../data/22_", $kind, ".txt")),
+            include_str!(concat!("../data/23_", $kind, ".txt")),
+            $day24,
+            include_str!(concat!("../data/25_", $kind, ".txt")),
+        ]
+    };
+}
+
+#[cfg(feature = "embed-tests")]
+static TESTS: [&str; 25] = day_files!("test", "");
+
+#[cfg(feature = "embed-inputs")]
+static INPUTS: [&str; 25] = day_files!("input", include_str!("../data/24_input.txt"));
+
+/// `files[day - 1]`, unless it's missing or empty.
+#[allow(dead_code)]
+fn lookup(files: &[&'static str; 25], day: u32) -> Option<&'static str> {
+    let day = usize::try_from(day).ok()?;
+    files
+        .get(day.checked_sub(1)?)
+        .copied()
+        .filter(|f| !f.is_empty())
+}
+
+/// `day`'s example input, if it has one.
+#[cfg(feature = "embed-tests")]
+pub fn test_input(day: u32) -> Option<&'static str> {
+    lookup(&TESTS, day)
+}
+
+/// `day`'s puzzle input.
+#[cfg(feature = "embed-inputs")]
+pub fn input(day: u32) -> Option<&'static str> {
+    lookup(&INPUTS, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_util::io::get_day_file;
+    use std::fs;
+
+    #[cfg(feature = "embed-tests")]
+    #[test]
+    fn embedded_tests() -> aoc_util::errors::AocResult<()> {
+        assert_eq!(
+            test_input(1),
+            Some(fs::read_to_string(get_day_file(1, "test"))?.as_str())
+        );
+        assert_eq!(test_input(24), None);
+        assert_eq!(test_input(0), None);
+        assert_eq!(test_input(26), None);
+        Ok(())
+    }
+
+    #[cfg(feature = "embed-inputs")]
+    #[test]
+    fn embedded_inputs() -> aoc_util::errors::AocResult<()> {
+        for day in 1..=25 {
+            assert_eq!(
+                input(day),
+                Some(fs::read_to_string(get_day_file(day, "input"))?.as_str())
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+//! Advent of Code 2021. Each `dayNN` module exposes `part1` and `part2`, which take the
+//! puzzle input as a string and return the typed answer, plus a `DayNN` implementing
+//! `Solver` on top of them for the runner and the `NN` binaries.
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+#[cfg(any(feature = "embed-tests", feature = "embed-inputs"))]
+pub mod embedded;
+
+use aoc_util::solver::Registry;
+
+pub const YEAR: u32 = 2021;
+
+/// Every day's solver.
+pub fn solvers() -> Registry {
+    aoc_util::registry! {
+        1 => day01::Day01,
+        2 => day02::Day02,
+        3 => day03::Day03,
+        4 => day04::Day04,
+        5 => day05::Day05,
+        6 => day06::Day06,
+        7 => day07::Day07,
+        8 => day08::Day08,
+        9 => day09::Day09,
+        10 => day10::Day10,
+        11 => day11::Day11,
+        12 => day12::Day12,
+        13 => day13::Day13,
+        14 => day14::Day14,
+        15 => day15::Day15,
+        16 => day16::Day16,
+        17 => day17::Day17,
+        18 => day18::Day18,
+        19 => day19::Day19,
+        20 => day20::Day20,
+        21 => day21::Day21,
+        22 => day22::Day22,
+        23 => day23::Day23,
+        24 => day24::Day24,
+        25 => day25::Day25,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_util::answers::{check_answers, Answers};
+    use aoc_util::errors::AocResult;
+    use aoc_util::io::read_input_file;
+    use aoc_util::net::input_path;
+
+    #[test]
+    fn all_days_registered() {
+        assert_eq!(
+            super::solvers()
+                .iter()
+                .map(|(day, _)| day)
+                .collect::<Vec<_>>(),
+            (1..=25).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn answers() -> AocResult<()> {
+        let answers = Answers::from_file("answers.toml")?;
+        let checked = check_answers(&super::solvers(), &answers, |day| {
+            read_input_file(&input_path(day))
+        })?;
+        assert_eq!(checked, 49);
+        Ok(())
+    }
+
+    /// Run with `cargo test --release --features perf-tests budgets`. Budgets are for
+    /// optimized code, so debug builds skip this.
+    #[cfg(all(feature = "perf-tests", not(debug_assertions)))]
+    #[test]
+    fn budgets() -> AocResult<()> {
+        use std::time::Duration;
+
+        /// For days without a `budget_ms`.
+        const DEFAULT_BUDGET: Duration = Duration::from_secs(1);
+        let answers = Answers::from_file("answers.toml")?;
+        aoc_util::answers::check_budgets(
+            &super::solvers(),
+            |day| answers.budget(day).unwrap_or(DEFAULT_BUDGET),
+            |day| read_input_file(&input_path(day)),
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+//! Benchmarks for the hot paths in aoc_util. Run with `cargo bench -p aoc-util`, adding
+//! `-- NAME` to run only the benchmarks whose names contain NAME.
+
+use aoc_util::{
+    bench::Bencher,
+    bits::BitVec,
+    cuboid::{Cuboid, PolyCuboid},
+    errors::AocResult,
+    fasthash::FastMap,
+    grid::{Grid, NeighbourPattern},
+    point::Point,
+};
+use std::collections::HashMap;
+
+/// Deterministic pseudo-random numbers, so that runs are comparable.
+fn lcg(seed: &mut u64) -> u64 {
+    *seed = seed
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+    *seed >> 33
+}
+
+fn main() -> AocResult<()> {
+    let bencher = Bencher::from_args();
+    let mut seed = 1;
+
+    let side = 200;
+    let cells: Vec<u8> = (0..side * side)
+        .map(|_| (lcg(&mut seed) % 9 + 1) as u8)
+        .collect();
+    let grid = Grid::from_slice(&cells, side, side)?;
+    bencher.bench("Grid::dijkstra 200x200", || {
+        grid.dijkstra(
+            Point::new(0, 0),
+            Point::new(side - 1, side - 1),
+            NeighbourPattern::Compass4,
+        )
+    });
+
+    // The same kind of grid as 2021 day 15 part 2: a random tile, repeated 5x5 with
+    // each repeat's costs raised by 1, wrapping from 9 back to 1.
+    let tile = Grid::from_slice(&cells[..100 * 100], 100, 100)?;
+    let tiled = tile.tiled(5, 5, |v, ti, tj| {
+        ((usize::from(v) + ti + tj + 8) % 9 + 1) as u8
+    })?;
+    let (start, finish) = (Point::new(0, 0), Point::new(499, 499));
+    bencher.bench("Grid::dijkstra 500x500 tiled", || {
+        tiled.dijkstra(start, finish, NeighbourPattern::Compass4)
+    });
+    bencher.bench("Grid::dial_shortest_path 500x500 tiled", || {
+        tiled.dial_shortest_path(start, finish, NeighbourPattern::Compass4)
+    });
+
+    let cuboids: Vec<Cuboid> = (0..60)
+        .map(|_| {
+            let mut corner = || {
+                let lo = (lcg(&mut seed) % 100) as i64;
+                (lo, lo + 1 + (lcg(&mut seed) % 40) as i64)
+            };
+            let ((x0, x1), (y0, y1), (z0, z1)) = (corner(), corner(), corner());
+            Cuboid::new(x0, x1, y0, y1, z0, z1)
+        })
+        .collect::<AocResult<_>>()?;
+    bencher.bench("PolyCuboid::insert x60", || {
+        let mut poly = PolyCuboid::new();
+        for c in &cuboids {
+            poly.insert(c);
+        }
+        poly.volume()
+    });
+
+    let points: Vec<(i64, i64)> = (0..100_000)
+        .map(|_| {
+            (
+                (lcg(&mut seed) % 1000) as i64,
+                (lcg(&mut seed) % 1000) as i64,
+            )
+        })
+        .collect();
+    bencher.bench("HashMap count 100k points", || {
+        let mut counts = HashMap::new();
+        for &p in &points {
+            *counts.entry(p).or_insert(0u32) += 1;
+        }
+        counts.len()
+    });
+    bencher.bench("FastMap count 100k points", || {
+        let mut counts = FastMap::default();
+        for &p in &points {
+            *counts.entry(p).or_insert(0u32) += 1;
+        }
+        counts.len()
+    });
+
+    let hex: String = (0..4096)
+        .map(|_| char::from_digit((lcg(&mut seed) % 16) as u32, 16).unwrap())
+        .collect();
+    let bits = BitVec::from_hex_str(&hex)?;
+    bencher.bench("BitVec::get_bits 13-bit reads", || {
+        (0..bits.len() - 13)
+            .step_by(13)
+            .map(|i| bits.get_bits(i, 13).unwrap())
+            .sum::<u64>()
+    });
+    Ok(())
+}
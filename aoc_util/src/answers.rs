@@ -0,0 +1,424 @@
+use crate::errors::{failure, malformed, AocError, AocResult};
+use crate::failure;
+use crate::solver::{solve_timed, Registry};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// Known answers to a year's puzzles, read from a TOML file like
+///
+/// ```toml
+/// [1]
+/// part1 = 1754
+/// part2 = "1789"
+/// ```
+///
+/// Only that subset of TOML is understood: a table per day, holding `part1` and/or
+/// `part2` keys whose values are integers or basic strings. Multi-line strings
+/// (`"""..."""`) are allowed, for answers that are drawn rather than computed. A day
+/// may also set `budget_ms`, an integer number of milliseconds it should be solved in
+/// (see `check_budgets`).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Answers {
+    days: BTreeMap<u32, [Option<String>; 2]>,
+    budgets: BTreeMap<u32, Duration>,
+}
+
+impl Answers {
+    pub fn from_file(path: &str) -> AocResult<Self> {
+        Self::parse(&fs::read_to_string(path)?).map_err(|e| format!("{path}: {e}").into())
+    }
+
+    pub fn parse(toml: &str) -> AocResult<Self> {
+        let mut answers = Answers::default();
+        let mut day = None;
+        let mut lines = toml.lines().enumerate();
+        while let Some((i, line)) = lines.next() {
+            let lineno = i + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix('[') {
+                let header = header.strip_suffix(']').ok_or_else(|| {
+                    AocError::malformed(lineno, "unterminated table header")
+                })?;
+                let d: u32 = header.trim().parse().map_err(|e| {
+                    AocError::malformed(lineno, format!("bad day {header:?}: {e}"))
+                })?;
+                if answers.days.insert(d, [None, None]).is_some() {
+                    return malformed(lineno, format!("day {d} appears twice"));
+                }
+                day = Some(d);
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| AocError::malformed(lineno, "expected key = value"))?;
+            let d = day
+                .ok_or_else(|| AocError::malformed(lineno, "key outside of a day table"))?;
+            let part = match key.trim() {
+                "part1" => 0,
+                "part2" => 1,
+                "budget_ms" => {
+                    let value = value.trim();
+                    let ms: u64 = value.parse().map_err(|e| {
+                        AocError::malformed(lineno, format!("bad budget {value:?}: {e}"))
+                    })?;
+                    if answers
+                        .budgets
+                        .insert(d, Duration::from_millis(ms))
+                        .is_some()
+                    {
+                        return malformed(lineno, "budget_ms given twice");
+                    }
+                    continue;
+                }
+                k => return malformed(lineno, format!("unknown key {k:?}")),
+            };
+            let value = value.trim();
+            let value = if let Some(rest) = value.strip_prefix("\"\"\"") {
+                // A newline right after the opening quotes isn't part of the string.
+                let mut raw = String::from(rest);
+                while !raw.ends_with("\"\"\"") || raw.len() < 3 {
+                    let (_, next) = lines.next().ok_or_else(|| {
+                        AocError::malformed(lineno, "unterminated multi-line string")
+                    })?;
+                    if !raw.is_empty() {
+                        raw.push('\n');
+                    }
+                    raw.push_str(next);
+                }
+                raw.truncate(raw.len() - 3);
+                unescape(&raw).map_err(|e| AocError::malformed(lineno, e.to_string()))?
+            } else if let Some(quoted) = value.strip_prefix('"') {
+                let quoted = quoted
+                    .strip_suffix('"')
+                    .ok_or_else(|| AocError::malformed(lineno, "unterminated string"))?;
+                unescape(quoted).map_err(|e| AocError::malformed(lineno, e.to_string()))?
+            } else {
+                value
+                    .parse::<i64>()
+                    .map_err(|e| {
+                        AocError::malformed(lineno, format!("bad value {value:?}: {e}"))
+                    })?
+                    .to_string()
+            };
+            let slot = &mut answers.days.get_mut(&d).unwrap()[part];
+            if slot.replace(value).is_some() {
+                return malformed(lineno, format!("{} given twice", key.trim()));
+            }
+        }
+        Ok(answers)
+    }
+
+    /// The expected answer to `part` (1 or 2) of `day`, if known.
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        let part = usize::try_from(part.checked_sub(1)?).ok()?;
+        self.days.get(&day)?.get(part)?.as_deref()
+    }
+
+    /// How long `day` should take to solve, if the file says.
+    pub fn budget(&self, day: u32) -> Option<Duration> {
+        self.budgets.get(&day).copied()
+    }
+
+    /// Days with at least a table in the file, in order.
+    pub fn days(&self) -> impl Iterator<Item = u32> + '_ {
+        self.days.keys().copied()
+    }
+}
+
+fn unescape(s: &str) -> AocResult<String> {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('"') => out.push('"'),
+            Some('\\') => out.push('\\'),
+            e => failure!("Unsupported escape \\{}", e.unwrap_or(' ')),
+        }
+    }
+    Ok(out)
+}
+
+/// Runs every solver in `registry` that has known answers against `input(day)`, and
+/// checks the results. All mismatches are reported together. Days with answers but
+/// no solver count as mismatches. Returns the number of answers checked.
+pub fn check_answers<F>(registry: &Registry, answers: &Answers, input: F) -> AocResult<usize>
+where
+    F: Fn(u32) -> AocResult<String>,
+{
+    let mut checked = 0;
+    let mut problems = Vec::new();
+    for day in answers.days() {
+        let Some(solver) = registry.get(day) else {
+            problems.push(format!("Day {day}: no solver"));
+            continue;
+        };
+        let input = match input(day) {
+            Ok(input) => input,
+            Err(e) => {
+                problems.push(format!("Day {day}: no input: {e}"));
+                continue;
+            }
+        };
+        for part in 1..=2 {
+            let Some(expected) = answers.get(day, part) else {
+                continue;
+            };
+            checked += 1;
+            let got = if part == 1 {
+                solver.part1(&input)
+            } else {
+                solver.part2(&input)
+            };
+            match got {
+                Ok(got) if got.trim_end() == expected.trim_end() => (),
+                Ok(got) => problems.push(format!(
+                    "Day {day} part {part}: expected {expected:?}, got {got:?}"
+                )),
+                Err(e) => problems.push(format!("Day {day} part {part}: {e}")),
+            }
+        }
+    }
+    if !problems.is_empty() {
+        return failure(problems.join("\n"));
+    }
+    Ok(checked)
+}
+
+/// Solves both parts of every day in `registry` for `input(day)`, and checks that each
+/// day takes no longer than `budget(day)` in total. All overruns are reported together,
+/// as are days that fail. Returns the number of days checked.
+pub fn check_budgets<B, F>(registry: &Registry, budget: B, input: F) -> AocResult<usize>
+where
+    B: Fn(u32) -> Duration,
+    F: Fn(u32) -> AocResult<String>,
+{
+    let mut problems = Vec::new();
+    for (day, solver) in registry.iter() {
+        let report = input(day).and_then(|input| solve_timed(day, solver, &input));
+        match report {
+            Ok(report) => {
+                let took: Duration = report.times.iter().sum();
+                let budget = budget(day);
+                if took > budget {
+                    problems.push(format!(
+                        "Day {day}: took {took:.1?}, over its budget of {budget:.1?}"
+                    ));
+                }
+            }
+            Err(e) => problems.push(format!("Day {day}: {e}")),
+        }
+    }
+    if !problems.is_empty() {
+        return failure(problems.join("\n"));
+    }
+    Ok(registry.len())
+}
+
+/// Writes a calendar of `year`'s puzzles: for each day, which parts have a solver in
+/// `registry` and which have a known answer in `answers`, followed by the number of
+/// stars backed by known answers.
+pub fn write_status(
+    year: u32,
+    registry: &Registry,
+    answers: &Answers,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let marks = |has: &dyn Fn(u32) -> bool| -> String {
+        [1, 2]
+            .map(|part| if has(part) { '*' } else { '.' })
+            .into_iter()
+            .collect()
+    };
+    writeln!(out, "{year}  Implemented  Verified")?;
+    let mut stars = 0;
+    for day in 1..=25 {
+        let implemented = registry.get(day).is_some();
+        let verified = |part| implemented && answers.get(day, part).is_some();
+        stars += [1, 2].into_iter().filter(|&part| verified(part)).count();
+        writeln!(
+            out,
+            "Day {day:>2}  {:<11}  {}",
+            marks(&|_| implemented),
+            marks(&verified)
+        )?;
+    }
+    writeln!(out, "{stars}/50 stars verified")
+}
+
+#[cfg(test)]
+mod answers_tests {
+    use super::*;
+    use crate::solver::Solver;
+
+    #[test]
+    fn parse() -> AocResult<()> {
+        let answers = Answers::parse(
+            r#"
+# Comments and blank lines are fine.
+[1]
+part1 = 1754
+part2 = "17\"89"
+
+[13]
+part2 = """
+#..#
+####"""
+
+[25]
+part1 = -3
+"#,
+        )?;
+        assert_eq!(answers.days().collect::<Vec<_>>(), [1, 13, 25]);
+        assert_eq!(answers.get(1, 1), Some("1754"));
+        assert_eq!(answers.get(1, 2), Some("17\"89"));
+        assert_eq!(answers.get(13, 1), None);
+        assert_eq!(answers.get(13, 2), Some("#..#\n####"));
+        assert_eq!(answers.get(25, 1), Some("-3"));
+        assert_eq!(answers.get(25, 0), None);
+        assert_eq!(answers.get(2, 1), None);
+        assert_eq!(answers.budget(1), None);
+
+        let answers = Answers::parse("[19]\npart1 = 308\nbudget_ms = 250\n")?;
+        assert_eq!(answers.budget(19), Some(Duration::from_millis(250)));
+        assert_eq!(answers.get(19, 1), Some("308"));
+
+        for bad in [
+            "part1 = 1",
+            "[1\npart1 = 1",
+            "[x]",
+            "[1]\n[1]",
+            "[1]\npart3 = 1",
+            "[1]\npart1 = 1\npart1 = 2",
+            "[1]\npart1 = \"1",
+            "[1]\npart1 = 1.5",
+            "[1]\npart1 = \"\"\"\n1",
+            "[1]\npart1 = \"\\q\"",
+            "[1]\nbudget_ms = \"5\"",
+            "[1]\nbudget_ms = -5",
+            "[1]\nbudget_ms = 5\nbudget_ms = 6",
+        ] {
+            assert!(Answers::parse(bad).is_err(), "{bad}");
+        }
+        Ok(())
+    }
+
+    struct Sum;
+
+    impl Solver for Sum {
+        fn part1(&self, input: &str) -> AocResult<String> {
+            let mut sum = 0;
+            for n in input.split_whitespace() {
+                sum += n.parse::<i64>()?;
+            }
+            Ok(sum.to_string())
+        }
+
+        fn part2(&self, _input: &str) -> AocResult<String> {
+            Ok("a\nb\n".to_string())
+        }
+    }
+
+    #[test]
+    fn check() -> AocResult<()> {
+        let registry = crate::registry! { 1 => Sum, 2 => Sum };
+        let answers = Answers::parse("[1]\npart1 = 6\npart2 = \"\"\"\na\nb\"\"\"\n[2]\n")?;
+        assert_eq!(
+            check_answers(&registry, &answers, |_| Ok("1 2 3".to_string()))?,
+            2
+        );
+
+        let answers = Answers::parse("[1]\npart1 = 7\n[2]\npart1 = 6\n[3]\npart1 = 1")?;
+        let err = check_answers(&registry, &answers, |day| match day {
+            2 => Ok("x".to_string()),
+            _ => Ok("1 2 3".to_string()),
+        })
+        .unwrap_err()
+        .to_string();
+        assert_eq!(err.lines().count(), 3, "{err}");
+        assert!(
+            err.contains("Day 1 part 1: expected \"7\", got \"6\""),
+            "{err}"
+        );
+        assert!(err.contains("Day 2 part 1: invalid digit"), "{err}");
+        assert!(err.contains("Day 3: no solver"), "{err}");
+
+        assert!(check_answers(&registry, &answers, |_| failure("gone")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn status() -> AocResult<()> {
+        let registry = crate::registry! { 1 => Sum, 2 => Sum };
+        let answers =
+            Answers::parse("[1]\npart1 = 6\npart2 = 3\n[2]\npart1 = 1\n[3]\npart1 = 9")?;
+        let mut out = Vec::new();
+        write_status(2021, &registry, &answers, &mut out)?;
+        let out = String::from_utf8(out)?;
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 27);
+        assert_eq!(lines[0], "2021  Implemented  Verified");
+        assert_eq!(lines[1], "Day  1  **           **");
+        assert_eq!(lines[2], "Day  2  **           *.");
+        // An answer without a solver doesn't count.
+        assert_eq!(lines[3], "Day  3  ..           ..");
+        assert_eq!(lines[26], "3/50 stars verified");
+        Ok(())
+    }
+
+    struct Slow;
+
+    impl Solver for Slow {
+        fn part1(&self, _input: &str) -> AocResult<String> {
+            std::thread::sleep(Duration::from_millis(20));
+            Ok(String::new())
+        }
+
+        fn part2(&self, _input: &str) -> AocResult<String> {
+            Ok(String::new())
+        }
+    }
+
+    #[test]
+    fn budgets() -> AocResult<()> {
+        let registry = crate::registry! { 1 => Sum, 2 => Slow };
+        let input = |_| Ok("1 2 3".to_string());
+        assert_eq!(
+            check_budgets(&registry, |_| Duration::from_secs(10), input)?,
+            2
+        );
+
+        let err = check_budgets(&registry, |_| Duration::from_millis(10), input)
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("Day 2: took "), "{err}");
+        assert!(err.ends_with("over its budget of 10.0ms"), "{err}");
+
+        let err = check_budgets(
+            &registry,
+            |_| Duration::from_secs(10),
+            |day| {
+                if day == 1 {
+                    Ok("x".to_string())
+                } else {
+                    Ok(String::new())
+                }
+            },
+        )
+        .unwrap_err()
+        .to_string();
+        assert_eq!(err, "Day 1: invalid digit found in string");
+        Ok(())
+    }
+}
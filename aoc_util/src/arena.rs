@@ -0,0 +1,213 @@
+//! A typed arena: values live in one `Vec` and are addressed by small `Id` handles, so
+//! trees, graphs and search nodes don't need a `Box` or `Rc` per node.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+
+/// A handle to a value in an `Arena<T>`. Only meaningful for the arena that produced it,
+/// and only until that value is removed: its slot may then be reused by a later `alloc`.
+pub struct Id<T> {
+    index: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Id<T> {
+    fn new(index: usize) -> Self {
+        Id {
+            index,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The slot this handle refers to. Slots are numbered from 0 in allocation order,
+    /// until removals make room for reuse.
+    pub fn index(self) -> usize {
+        self.index
+    }
+}
+
+// Derives would demand the same traits of `T`.
+impl<T> Clone for Id<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Id<T> {}
+
+impl<T> PartialEq for Id<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<T> Eq for Id<T> {}
+
+impl<T> PartialOrd for Id<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Id<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index.cmp(&other.index)
+    }
+}
+
+impl<T> Hash for Id<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Id({})", self.index)
+    }
+}
+
+/// Values of one type, allocated one at a time and freed one at a time. Freed slots go
+/// on a free list and are reused before the arena grows, so a structure that keeps
+/// replacing nodes stays the size of its largest live set.
+#[derive(Clone, Debug)]
+pub struct Arena<T> {
+    slots: Vec<Option<T>>,
+    free: Vec<usize>,
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Arena<T> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Arena {
+            slots: Vec::with_capacity(capacity),
+            free: Vec::new(),
+        }
+    }
+
+    /// Stores `value`, returning its handle.
+    pub fn alloc(&mut self, value: T) -> Id<T> {
+        if let Some(index) = self.free.pop() {
+            self.slots[index] = Some(value);
+            Id::new(index)
+        } else {
+            self.slots.push(Some(value));
+            Id::new(self.slots.len() - 1)
+        }
+    }
+
+    /// Takes the value for `id` out of the arena, freeing its slot. None if it was
+    /// already removed.
+    pub fn remove(&mut self, id: Id<T>) -> Option<T> {
+        let value = self.slots.get_mut(id.index)?.take()?;
+        self.free.push(id.index);
+        Some(value)
+    }
+
+    pub fn get(&self, id: Id<T>) -> Option<&T> {
+        self.slots.get(id.index)?.as_ref()
+    }
+
+    pub fn get_mut(&mut self, id: Id<T>) -> Option<&mut T> {
+        self.slots.get_mut(id.index)?.as_mut()
+    }
+
+    /// The number of live values.
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of slots, live or free: the most values that have been live at once.
+    pub fn slots(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Removes every value, keeping the allocation. Existing handles become invalid.
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.free.clear();
+    }
+
+    /// Iterates over the live values and their handles, in slot order.
+    pub fn iter(&self) -> impl Iterator<Item = (Id<T>, &T)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(i, v)| Some((Id::new(i), v.as_ref()?)))
+    }
+}
+
+/// Panics if `id` has been removed.
+impl<T> Index<Id<T>> for Arena<T> {
+    type Output = T;
+
+    fn index(&self, id: Id<T>) -> &T {
+        self.get(id)
+            .unwrap_or_else(|| panic!("{id:?} is not in the arena"))
+    }
+}
+
+/// Panics if `id` has been removed.
+impl<T> IndexMut<Id<T>> for Arena<T> {
+    fn index_mut(&mut self, id: Id<T>) -> &mut T {
+        self.get_mut(id)
+            .unwrap_or_else(|| panic!("{id:?} is not in the arena"))
+    }
+}
+
+#[cfg(test)]
+mod arena_tests {
+    use super::*;
+
+    #[test]
+    fn alloc_and_reuse() {
+        let mut arena = Arena::new();
+        let a = arena.alloc("a".to_string());
+        let b = arena.alloc("b".to_string());
+        assert_eq!(arena.len(), 2);
+        assert_ne!(a, b);
+        arena[b].push('!');
+        assert_eq!(arena[b], "b!");
+
+        assert_eq!(arena.remove(a).as_deref(), Some("a"));
+        assert_eq!(arena.remove(a), None);
+        assert_eq!(arena.get(a), None);
+        assert_eq!(arena.len(), 1);
+
+        // The freed slot is reused rather than growing the arena.
+        let c = arena.alloc("c".to_string());
+        assert_eq!(c.index(), a.index());
+        assert_eq!(arena.slots(), 2);
+        let live: Vec<&str> = arena.iter().map(|(_, s)| s.as_str()).collect();
+        assert_eq!(live, ["c", "b!"]);
+
+        arena.clear();
+        assert!(arena.is_empty());
+        assert_eq!(arena.get(b), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "not in the arena")]
+    fn removed() {
+        let mut arena = Arena::new();
+        let a = arena.alloc(1);
+        arena.remove(a);
+        let _ = arena[a];
+    }
+}
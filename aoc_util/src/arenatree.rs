@@ -0,0 +1,455 @@
+use crate::arena::{Arena, Id};
+use crate::errors::{malformed_at, AocResult};
+use crate::{ensure, failure};
+use std::fmt;
+
+/// Index of a node within a `Tree`. Only meaningful for the tree that produced it.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct NodeId(Id<Node>);
+
+#[derive(Clone, Copy)]
+enum Side {
+    Left,
+    Right,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Node {
+    data: Option<i64>,
+    left: Option<NodeId>,
+    right: Option<NodeId>,
+    parent: Option<NodeId>,
+}
+
+/// A binary tree whose nodes live in an `Arena`, addressed by `NodeId`. Unlike the
+/// `Rc<RefCell<Node>>` tree in `binarytree`, cloning is a plain O(n) copy of the arena,
+/// and there are no reference cycles to leak.
+///
+/// The slots of nodes removed from the tree are reused by later insertions, so repeated
+/// split/explode style edits don't grow the arena without bound.
+#[derive(Clone, Debug)]
+pub struct Tree {
+    nodes: Arena<Node>,
+    root: NodeId,
+}
+
+impl Tree {
+    /// Creates a tree consisting of a single root node holding `data`.
+    pub fn new(data: Option<i64>) -> Self {
+        let mut nodes = Arena::new();
+        let root = NodeId(nodes.alloc(Node {
+            data,
+            ..Node::default()
+        }));
+        Tree { nodes, root }
+    }
+
+    /// Parses a tree of the form "[[1,2],[3,[4,5]]]", as `NodeWrapper::from_ascii`
+    /// does. Leaves may be any (possibly negative) integer, and whitespace is allowed
+    /// between tokens.
+    pub fn from_ascii(ascii: &[u8]) -> AocResult<Self> {
+        let mut tree = Tree::new(None);
+        let mut pos = 0;
+        let root = tree.root;
+        tree.parse_into(ascii, &mut pos, root)?;
+        skip_whitespace(ascii, &mut pos);
+        if pos != ascii.len() {
+            return malformed_at(1, pos + 1, "trailing characters after tree");
+        }
+        Ok(tree)
+    }
+
+    /// Parses the element at `pos` into the existing node `node`.
+    fn parse_into(&mut self, ascii: &[u8], pos: &mut usize, node: NodeId) -> AocResult<()> {
+        skip_whitespace(ascii, pos);
+        match ascii.get(*pos) {
+            Some(b'[') => {
+                *pos += 1;
+                let left = self.add_node(None);
+                self.replace_child(node, Side::Left, Some(left));
+                self.parse_into(ascii, pos, left)?;
+                expect(ascii, pos, b',')?;
+                let right = self.add_node(None);
+                self.replace_child(node, Side::Right, Some(right));
+                self.parse_into(ascii, pos, right)?;
+                expect(ascii, pos, b']')
+            }
+            Some(b'-' | b'0'..=b'9') => {
+                let len = ascii[*pos + 1..]
+                    .iter()
+                    .take_while(|b| b.is_ascii_digit())
+                    .count()
+                    + 1;
+                let literal = std::str::from_utf8(&ascii[*pos..*pos + len])?;
+                let data = literal
+                    .parse::<i64>()
+                    .map_err(|e| format!("Invalid literal {literal:?}: {e}"))?;
+                self.set_data(node, Some(data));
+                *pos += len;
+                Ok(())
+            }
+            Some(c) => {
+                malformed_at(1, *pos + 1, format!("invalid character {:?}", *c as char))
+            }
+            None => malformed_at(1, *pos + 1, "unexpected end of input"),
+        }
+    }
+
+    pub fn root(&self) -> NodeId {
+        self.root
+    }
+
+    /// Number of nodes currently in the tree (or detached from it but not yet removed).
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Allocates a new node with no parent or children. Attach it with `set_left` or
+    /// `set_right`.
+    pub fn add_node(&mut self, data: Option<i64>) -> NodeId {
+        NodeId(self.nodes.alloc(Node {
+            data,
+            ..Node::default()
+        }))
+    }
+
+    pub fn data(&self, id: NodeId) -> Option<i64> {
+        self.nodes[id.0].data
+    }
+
+    pub fn set_data(&mut self, id: NodeId, data: Option<i64>) {
+        self.nodes[id.0].data = data;
+    }
+
+    pub fn left(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes[id.0].left
+    }
+
+    pub fn right(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes[id.0].right
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes[id.0].parent
+    }
+
+    pub fn is_leaf(&self, id: NodeId) -> bool {
+        self.left(id).is_none() && self.right(id).is_none()
+    }
+
+    pub fn has_data(&self, id: NodeId) -> bool {
+        self.data(id).is_some()
+    }
+
+    /// Makes `child` the left child of `id`. The previous left child, if any, is
+    /// removed along with its subtree, unless it's `child` itself. If `child` already
+    /// has a parent, it's moved, not shared. Fails if `child` is `id` or one of its
+    /// ancestors, which would make a cycle.
+    pub fn set_left(&mut self, id: NodeId, child: Option<NodeId>) -> AocResult<()> {
+        self.set_child(id, Side::Left, child)
+    }
+
+    /// Like `set_left`, for the right child.
+    pub fn set_right(&mut self, id: NodeId, child: Option<NodeId>) -> AocResult<()> {
+        self.set_child(id, Side::Right, child)
+    }
+
+    fn set_child(&mut self, id: NodeId, side: Side, child: Option<NodeId>) -> AocResult<()> {
+        if *self.slot(id, side) == child {
+            return Ok(());
+        }
+        if let Some(child) = child {
+            let mut ancestor = Some(id);
+            while let Some(a) = ancestor {
+                ensure!(a != child, "Can't attach {child:?} below itself");
+                ancestor = self.parent(a);
+            }
+            if let Some(old_parent) = self.parent(child) {
+                for side in [Side::Left, Side::Right] {
+                    if *self.slot(old_parent, side) == Some(child) {
+                        *self.slot(old_parent, side) = None;
+                    }
+                }
+                self.nodes[child.0].parent = None;
+            }
+        }
+        self.replace_child(id, side, child);
+        Ok(())
+    }
+
+    /// Frees the child of `id` on `side`, if any, and puts `child` in its place. `child`
+    /// must be detached, as new nodes are.
+    fn replace_child(&mut self, id: NodeId, side: Side, child: Option<NodeId>) {
+        if let Some(old) = self.slot(id, side).take() {
+            self.remove_subtree(old);
+        }
+        *self.slot(id, side) = child;
+        if let Some(child) = child {
+            self.nodes[child.0].parent = Some(id);
+        }
+    }
+
+    fn slot(&mut self, id: NodeId, side: Side) -> &mut Option<NodeId> {
+        let node = &mut self.nodes[id.0];
+        match side {
+            Side::Left => &mut node.left,
+            Side::Right => &mut node.right,
+        }
+    }
+
+    /// Frees `id` and all of its descendants.
+    fn remove_subtree(&mut self, id: NodeId) {
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            if let Some(node) = self.nodes.remove(id.0) {
+                stack.extend(node.left);
+                stack.extend(node.right);
+            }
+        }
+    }
+
+    /// Replaces the children of `id` with a single value, e.g. for a snailfish explode.
+    pub fn collapse(&mut self, id: NodeId, data: i64) {
+        self.replace_child(id, Side::Left, None);
+        self.replace_child(id, Side::Right, None);
+        self.set_data(id, Some(data));
+    }
+
+    /// Turns the leaf `id` into a pair of leaves holding `left` and `right`, e.g. for a
+    /// snailfish split.
+    pub fn split_leaf(&mut self, id: NodeId, left: i64, right: i64) {
+        let l = self.add_node(Some(left));
+        let r = self.add_node(Some(right));
+        self.replace_child(id, Side::Left, Some(l));
+        self.replace_child(id, Side::Right, Some(r));
+        self.set_data(id, None);
+    }
+
+    /// Returns a new tree whose root has a copy of `left` as its left subtree and a copy
+    /// of `right` as its right subtree.
+    pub fn join(left: &Tree, right: &Tree) -> Tree {
+        let mut tree = Tree::new(None);
+        let root = tree.root;
+        let l = tree.copy_subtree(left, left.root);
+        let r = tree.copy_subtree(right, right.root);
+        tree.replace_child(root, Side::Left, Some(l));
+        tree.replace_child(root, Side::Right, Some(r));
+        tree
+    }
+
+    /// Copies the subtree of `other` rooted at `id` into this arena, detached.
+    fn copy_subtree(&mut self, other: &Tree, id: NodeId) -> NodeId {
+        let new = self.add_node(other.data(id));
+        if let Some(left) = other.left(id) {
+            let l = self.copy_subtree(other, left);
+            self.replace_child(new, Side::Left, Some(l));
+        }
+        if let Some(right) = other.right(id) {
+            let r = self.copy_subtree(other, right);
+            self.replace_child(new, Side::Right, Some(r));
+        }
+        new
+    }
+
+    /// Iterates over the tree in preorder, yielding `(node, depth)`, with the root at
+    /// depth 0.
+    pub fn depth_first_iter(&self) -> impl Iterator<Item = (NodeId, usize)> + '_ {
+        let mut stack = vec![(self.root, 0)];
+        std::iter::from_fn(move || {
+            let (id, depth) = stack.pop()?;
+            // Push right first so that we pop left first.
+            stack.extend(self.right(id).map(|r| (r, depth + 1)));
+            stack.extend(self.left(id).map(|l| (l, depth + 1)));
+            Some((id, depth))
+        })
+    }
+
+    /// Checks that leaves have data, internal nodes have no data and exactly two
+    /// children, and every child's parent link points back at its parent.
+    pub fn validate(&self) -> AocResult<()> {
+        for (id, depth) in self.depth_first_iter() {
+            match (self.data(id), self.left(id), self.right(id)) {
+                (Some(_), None, None) | (None, Some(_), Some(_)) => (),
+                (None, None, None) => failure!("Leaf with no data at depth {depth}"),
+                (Some(_), _, _) => failure!("Internal node with data at depth {depth}"),
+                _ => failure!("Internal node with one child at depth {depth}"),
+            }
+            for child in [self.left(id), self.right(id)].into_iter().flatten() {
+                ensure!(
+                    self.parent(child) == Some(id),
+                    "Broken parent link at depth {}",
+                    depth + 1
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes `?` for a missing child or a node that's neither a leaf nor a pair, so a
+    /// tree that `validate` would reject can still be printed.
+    fn fmt_node(&self, id: Option<NodeId>, f: &mut fmt::Formatter) -> fmt::Result {
+        let Some(id) = id else {
+            return write!(f, "?");
+        };
+        match (self.data(id), self.left(id), self.right(id)) {
+            (Some(data), None, None) => write!(f, "{}", data),
+            (None, left, right) if left.is_some() || right.is_some() => {
+                write!(f, "[")?;
+                self.fmt_node(left, f)?;
+                write!(f, ",")?;
+                self.fmt_node(right, f)?;
+                write!(f, "]")
+            }
+            _ => write!(f, "?"),
+        }
+    }
+}
+
+impl fmt::Display for Tree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_node(Some(self.root), f)
+    }
+}
+
+fn skip_whitespace(ascii: &[u8], pos: &mut usize) {
+    while ascii.get(*pos).is_some_and(u8::is_ascii_whitespace) {
+        *pos += 1;
+    }
+}
+
+fn expect(ascii: &[u8], pos: &mut usize, expected: u8) -> AocResult<()> {
+    skip_whitespace(ascii, pos);
+    match ascii.get(*pos) {
+        Some(c) if *c == expected => {
+            *pos += 1;
+            Ok(())
+        }
+        Some(c) => malformed_at(
+            1,
+            *pos + 1,
+            format!("expected {:?}, found {:?}", expected as char, *c as char),
+        ),
+        None => malformed_at(
+            1,
+            *pos + 1,
+            format!("expected {:?}, found end of input", expected as char),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod arenatree_tests {
+    use super::*;
+
+    #[test]
+    fn tree_from_ascii() -> AocResult<()> {
+        for s in [
+            "[1,2]",
+            "[[1,2],3]",
+            "[[[[[1,2],3],[4,5]],6],[7,[[8,9],0]]]",
+            "[-10,[200,3]]",
+        ] {
+            assert_eq!(Tree::from_ascii(s.as_bytes())?.to_string(), s);
+        }
+        assert_eq!(
+            Tree::from_ascii(b" [ 1 ,\t[2,3]] ")?.to_string(),
+            "[1,[2,3]]"
+        );
+        for s in ["", "[1,2", "[1 2]", "[1,2,3]", "[1,2]x", "[a,1]"] {
+            assert!(Tree::from_ascii(s.as_bytes()).is_err(), "{s}");
+        }
+        assert_eq!(
+            Tree::from_ascii(b"[1 2]").unwrap_err().to_string(),
+            "Line 1, column 4: expected ',', found '2'"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn tree_depth_first_traversal() -> AocResult<()> {
+        let t = Tree::from_ascii(b"[[[[[1,2],3],[4,5]],6],[7,[[8,9],0]]]")?;
+        let (data, depths): (Vec<_>, Vec<_>) = t
+            .depth_first_iter()
+            .filter_map(|(id, depth)| t.data(id).map(|d| (d, depth)))
+            .unzip();
+        assert_eq!(data, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 0]);
+        assert_eq!(depths, vec![5, 5, 4, 4, 4, 2, 2, 4, 4, 3]);
+        Ok(())
+    }
+
+    #[test]
+    fn tree_editing() -> AocResult<()> {
+        let mut t = Tree::from_ascii(b"[[1,2],10]")?;
+        let copy = t.clone();
+        let root = t.root();
+        let pair = t.left(root).unwrap();
+        let ten = t.right(root).unwrap();
+        assert_eq!(t.len(), 5);
+
+        t.collapse(pair, 0);
+        t.split_leaf(ten, 5, 5);
+        assert_eq!(t.to_string(), "[0,[5,5]]");
+        assert_eq!(t.parent(t.left(ten).unwrap()), Some(ten));
+        // The two freed leaves were reused for the split.
+        assert_eq!(t.len(), 5);
+        assert_eq!(t.nodes.slots(), 5);
+        // Clones are independent.
+        assert_eq!(copy.to_string(), "[[1,2],10]");
+
+        let sum = Tree::join(&copy, &t);
+        assert_eq!(sum.to_string(), "[[[1,2],10],[0,[5,5]]]");
+        assert_eq!(sum.len(), 11);
+        sum.validate()?;
+
+        // A pair with a single child is invalid: it's reported, and shown with a `?`.
+        let mut bad = Tree::from_ascii(b"[1,2]")?;
+        let root = bad.root();
+        bad.set_right(root, None)?;
+        assert_eq!(
+            bad.validate().unwrap_err().to_string(),
+            "Internal node with one child at depth 0"
+        );
+        assert_eq!(bad.to_string(), "[1,?]");
+        // So is a leaf that was given a child.
+        let one = bad.left(root).unwrap();
+        let two = bad.add_node(Some(2));
+        bad.set_left(one, Some(two))?;
+        assert_eq!(bad.to_string(), "[?,?]");
+        Ok(())
+    }
+
+    #[test]
+    fn reattaching() -> AocResult<()> {
+        // Setting a child to itself keeps it rather than freeing it.
+        let mut t = Tree::from_ascii(b"[[1,2],3]")?;
+        let root = t.root();
+        let pair = t.left(root).unwrap();
+        t.set_left(root, Some(pair))?;
+        assert_eq!(t.to_string(), "[[1,2],3]");
+        assert_eq!(t.len(), 5);
+
+        // Moving a node detaches it from its old parent.
+        let three = t.right(root).unwrap();
+        let one = t.left(pair).unwrap();
+        t.set_left(pair, Some(three))?;
+        assert_eq!(t.right(root), None);
+        assert_eq!(t.parent(three), Some(pair));
+        // The node it replaced was freed, and its slot can't be reached any more.
+        assert_eq!(t.len(), 4);
+        assert!(t.nodes.get(one.0).is_none());
+        let four = t.add_node(Some(4));
+        t.set_right(root, Some(four))?;
+        assert_eq!(t.to_string(), "[[3,2],4]");
+        t.validate()?;
+
+        // A node can't go below itself or its descendants.
+        assert!(t.set_left(pair, Some(pair)).is_err());
+        assert!(t.set_left(pair, Some(root)).is_err());
+        assert_eq!(t.to_string(), "[[3,2],4]");
+        Ok(())
+    }
+}
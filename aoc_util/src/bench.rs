@@ -0,0 +1,163 @@
+use std::env;
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Summary statistics for a benchmark's timed iterations.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchStats {
+    pub name: String,
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl BenchStats {
+    /// Returns `None` if there are no samples.
+    pub fn from_samples(name: &str, samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let min = *sorted.first()?;
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+        let mean = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / n as f64;
+        let variance = sorted
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        Some(BenchStats {
+            name: name.to_string(),
+            iterations: n,
+            min,
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+impl fmt::Display for BenchStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:<32} median {:>10}  mean {:>10}  stddev {:>10}  min {:>10}  ({} runs)",
+            self.name,
+            format!("{:.1?}", self.median),
+            format!("{:.1?}", self.mean),
+            format!("{:.1?}", self.stddev),
+            format!("{:.1?}", self.min),
+            self.iterations
+        )
+    }
+}
+
+/// A minimal benchmark runner for `harness = false` bench targets: each benchmark is
+/// run a few times untimed to warm up, then timed over a fixed number of iterations.
+#[derive(Clone, Debug)]
+pub struct Bencher {
+    warmup: usize,
+    iterations: usize,
+    filter: Option<String>,
+}
+
+impl Default for Bencher {
+    fn default() -> Self {
+        Bencher {
+            warmup: 3,
+            iterations: 20,
+            filter: None,
+        }
+    }
+}
+
+impl Bencher {
+    pub fn new(warmup: usize, iterations: usize) -> Self {
+        Bencher {
+            warmup,
+            iterations,
+            filter: None,
+        }
+    }
+
+    /// A default `Bencher` that only runs benchmarks whose names contain the first
+    /// non-flag command line argument, as in `cargo bench -- dijkstra`.
+    pub fn from_args() -> Self {
+        Bencher {
+            filter: env::args().skip(1).find(|arg| !arg.starts_with('-')),
+            ..Self::default()
+        }
+    }
+
+    /// Times `f`, prints its stats and returns them. Returns `None` without running
+    /// anything if `name` doesn't match the filter.
+    pub fn bench<T, F>(&self, name: &str, mut f: F) -> Option<BenchStats>
+    where
+        F: FnMut() -> T,
+    {
+        if self
+            .filter
+            .as_ref()
+            .is_some_and(|filter| !name.contains(filter))
+        {
+            return None;
+        }
+        for _ in 0..self.warmup {
+            black_box(f());
+        }
+        let samples: Vec<Duration> = (0..self.iterations)
+            .map(|_| {
+                let start = Instant::now();
+                black_box(f());
+                start.elapsed()
+            })
+            .collect();
+        let stats = BenchStats::from_samples(name, &samples)?;
+        println!("{stats}");
+        Some(stats)
+    }
+}
+
+#[cfg(test)]
+mod bench_tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let ms = Duration::from_millis;
+        let stats = BenchStats::from_samples("x", &[ms(4), ms(1), ms(3), ms(2)]).unwrap();
+        assert_eq!(stats.iterations, 4);
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean.as_micros(), 2500);
+        // sqrt(1.25) ms
+        assert_eq!(stats.stddev.as_micros(), 1118);
+
+        let stats = BenchStats::from_samples("x", &[ms(5), ms(1), ms(3)]).unwrap();
+        assert_eq!(stats.median, ms(3));
+        assert!(BenchStats::from_samples("x", &[]).is_none());
+    }
+
+    #[test]
+    fn bencher() {
+        let mut calls = 0;
+        let stats = Bencher::new(2, 5).bench("count", || calls += 1).unwrap();
+        assert_eq!(calls, 7);
+        assert_eq!(stats.name, "count");
+        assert_eq!(stats.iterations, 5);
+
+        let filtered = Bencher {
+            filter: Some("dijkstra".to_string()),
+            ..Bencher::new(0, 1)
+        };
+        assert!(filtered.bench("bitvec", || calls += 1).is_none());
+        assert_eq!(calls, 7);
+        assert!(filtered.bench("grid dijkstra", || ()).is_some());
+    }
+}
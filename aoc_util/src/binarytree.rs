@@ -1,20 +1,25 @@
-use crate::errors::{failure, AocResult};
+use crate::errors::AocResult;
+use crate::graph::dot_quote;
+use crate::{ensure, failure};
 use std::cell::RefCell;
-use std::fmt;
+use std::collections::VecDeque;
+use std::fmt::{self, Write};
 use std::rc::{Rc, Weak};
 
-pub type NodeLink = Rc<RefCell<Node>>;
+pub type NodeLink<T = i64> = Rc<RefCell<Node<T>>>;
 
+/// A binary tree node. The payload type defaults to `i64`, which is what the day code
+/// (snailfish numbers) uses, but may be anything.
 #[derive(Clone, Debug)]
-pub struct Node {
-    data: Option<i64>,
-    left: Option<NodeLink>,
-    right: Option<NodeLink>,
-    parent: Option<Weak<RefCell<Node>>>,
+pub struct Node<T = i64> {
+    data: Option<T>,
+    left: Option<NodeLink<T>>,
+    right: Option<NodeLink<T>>,
+    parent: Option<Weak<RefCell<Node<T>>>>,
 }
 
-impl Node {
-    pub fn new(data: Option<i64>) -> NodeLink {
+impl<T> Node<T> {
+    pub fn new(data: Option<T>) -> NodeLink<T> {
         Rc::new(RefCell::new(Node {
             data,
             left: None,
@@ -23,7 +28,7 @@ impl Node {
         }))
     }
 
-    pub fn new_with_parent(data: Option<i64>, parent: &NodeLink) -> NodeLink {
+    pub fn new_with_parent(data: Option<T>, parent: &NodeLink<T>) -> NodeLink<T> {
         Rc::new(RefCell::new(Node {
             data,
             left: None,
@@ -33,16 +38,30 @@ impl Node {
     }
 }
 
-#[derive(Clone, Debug)]
-pub struct NodeWrapper(NodeLink);
+/// Which child of its parent a node is.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+#[derive(Debug)]
+pub struct NodeWrapper<T = i64>(NodeLink<T>);
+
+// Not derived, since cloning a wrapper only clones the `Rc` and shouldn't need `T: Clone`.
+impl<T> Clone for NodeWrapper<T> {
+    fn clone(&self) -> Self {
+        NodeWrapper(self.0.clone())
+    }
+}
 
-impl From<NodeLink> for NodeWrapper {
-    fn from(n: NodeLink) -> NodeWrapper {
+impl<T> From<NodeLink<T>> for NodeWrapper<T> {
+    fn from(n: NodeLink<T>) -> NodeWrapper<T> {
         NodeWrapper(n)
     }
 }
 
-impl fmt::Display for NodeWrapper {
+impl<T: fmt::Display> fmt::Display for NodeWrapper<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // TODO currently only supports trees with (required) data at leaves.
         if self.is_leaf() && !self.has_data() {
@@ -51,7 +70,7 @@ impl fmt::Display for NodeWrapper {
         if !self.is_leaf() && self.has_data() {
             panic!("Invalid tree: non-leaf with data");
         }
-        if let Some(data) = self.get_data() {
+        if let Some(data) = &self.0.borrow().data {
             write!(f, "{}", data)
         } else {
             let left_string = self.get_left().unwrap().to_string();
@@ -65,11 +84,12 @@ impl fmt::Display for NodeWrapper {
     }
 }
 
-impl NodeWrapper {
-    pub fn new() -> NodeWrapper {
+impl<T> NodeWrapper<T> {
+    pub fn new() -> NodeWrapper<T> {
         Self(Node::new(None))
     }
-    pub fn get_left(&self) -> Option<NodeWrapper> {
+
+    pub fn get_left(&self) -> Option<NodeWrapper<T>> {
         self.0
             .borrow()
             .left
@@ -77,7 +97,7 @@ impl NodeWrapper {
             .map(|left| left.clone().into())
     }
 
-    pub fn get_right(&self) -> Option<NodeWrapper> {
+    pub fn get_right(&self) -> Option<NodeWrapper<T>> {
         self.0
             .borrow()
             .right
@@ -85,11 +105,7 @@ impl NodeWrapper {
             .map(|right| right.clone().into())
     }
 
-    pub fn get_data(&self) -> Option<i64> {
-        self.0.borrow().data
-    }
-
-    pub fn get_parent(&self) -> Option<NodeWrapper> {
+    pub fn get_parent(&self) -> Option<NodeWrapper<T>> {
         self.0
             .borrow()
             .parent
@@ -97,7 +113,7 @@ impl NodeWrapper {
             .map(|parent| parent.upgrade().unwrap().into())
     }
 
-    pub fn set_left(&self, child: Option<&NodeWrapper>) {
+    pub fn set_left(&self, child: Option<&NodeWrapper<T>>) {
         if let Some(child) = child {
             self.0.borrow_mut().left = Some(child.0.clone());
             child.0.borrow_mut().parent = Some(Rc::downgrade(&self.0));
@@ -106,7 +122,7 @@ impl NodeWrapper {
         }
     }
 
-    pub fn set_right(&self, child: Option<&NodeWrapper>) {
+    pub fn set_right(&self, child: Option<&NodeWrapper<T>>) {
         if let Some(child) = child {
             self.0.borrow_mut().right = Some(child.0.clone());
             child.0.borrow_mut().parent = Some(Rc::downgrade(&self.0));
@@ -115,7 +131,7 @@ impl NodeWrapper {
         }
     }
 
-    pub fn set_data(&self, data: Option<i64>) {
+    pub fn set_data(&self, data: Option<T>) {
         self.0.borrow_mut().data = data;
     }
 
@@ -124,37 +140,217 @@ impl NodeWrapper {
     }
 
     pub fn has_data(&self) -> bool {
-        self.get_data().is_some()
+        self.0.borrow().data.is_some()
     }
 
-    pub fn depth_first_iter(&self) -> DepthFirstIterator {
+    /// Preorder traversal yielding `(node, depth)`, with this node at depth 0.
+    pub fn depth_first_iter(&self) -> DepthFirstIterator<T> {
         DepthFirstIterator::new(&self.0)
     }
 
-    pub fn from_ascii(ascii: &[u8]) -> AocResult<NodeWrapper> {
-        Ok(NodeWrapper::_from_ascii(ascii)?.0)
+    /// In-order (left, node, right) traversal yielding `(node, depth)`.
+    pub fn in_order_iter(&self) -> InOrderIterator<T> {
+        InOrderIterator::new(&self.0)
     }
 
-    pub fn inner(&self) -> NodeLink {
+    /// Post-order (left, right, node) traversal yielding `(node, depth)`.
+    pub fn post_order_iter(&self) -> PostOrderIterator<T> {
+        PostOrderIterator::new(&self.0)
+    }
+
+    /// Level-order traversal yielding `(node, depth)`.
+    pub fn breadth_first_iter(&self) -> BreadthFirstIterator<T> {
+        BreadthFirstIterator::new(&self.0)
+    }
+
+    /// The leaves of the tree from left to right, as `(node, depth)`.
+    pub fn leaves(&self) -> impl Iterator<Item = (NodeWrapper<T>, usize)> {
+        self.depth_first_iter().filter(|(node, _)| node.is_leaf())
+    }
+
+    pub fn inner(&self) -> NodeLink<T> {
         self.0.clone()
     }
 
+    /// Number of nodes in the tree rooted at this node.
+    pub fn size(&self) -> usize {
+        self.depth_first_iter().count()
+    }
+
+    /// Depth of the deepest node below this one, with this node at depth 0 (as in
+    /// `depth_first_iter`). A lone leaf has depth 0.
+    pub fn depth(&self) -> usize {
+        self.depth_first_iter()
+            .map(|(_, depth)| depth)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Checks the invariants the rest of this module relies on, for the tree rooted at
+    /// this node: leaves have data, internal nodes have no data and exactly two
+    /// children, and every child's parent link points back at its parent.
+    pub fn validate(&self) -> AocResult<()> {
+        for (node, depth) in self.depth_first_iter() {
+            match (node.is_leaf(), node.has_data()) {
+                (true, false) => {
+                    failure!("Leaf with no data at depth {depth}")
+                }
+                (false, true) => {
+                    failure!("Internal node with data at depth {depth}")
+                }
+                _ => (),
+            }
+            if !node.is_leaf() && (node.get_left().is_none() || node.get_right().is_none()) {
+                failure!("Internal node with one child at depth {depth}");
+            }
+            for child in [node.get_left(), node.get_right()].into_iter().flatten() {
+                ensure!(
+                    child.get_parent().is_some_and(|p| p.ptr_eq(&node)),
+                    "Broken parent link at depth {}",
+                    depth + 1
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// True if `self` and `other` are the same node (not merely equal trees).
+    pub fn ptr_eq(&self, other: &NodeWrapper<T>) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+
+    pub fn get_child(&self, side: Side) -> Option<NodeWrapper<T>> {
+        match side {
+            Side::Left => self.get_left(),
+            Side::Right => self.get_right(),
+        }
+    }
+
+    /// Which side of its parent this node hangs from, or None for a root.
+    pub fn side(&self) -> Option<Side> {
+        let parent = self.get_parent()?;
+        if parent.get_left().is_some_and(|l| l.ptr_eq(self)) {
+            Some(Side::Left)
+        } else {
+            Some(Side::Right)
+        }
+    }
+
+    /// The other child of this node's parent, if any.
+    pub fn sibling(&self) -> Option<NodeWrapper<T>> {
+        let parent = self.get_parent()?;
+        match self.side()? {
+            Side::Left => parent.get_right(),
+            Side::Right => parent.get_left(),
+        }
+    }
+
+    /// Removes this node (and its subtree) from its parent, making it a root. Does
+    /// nothing if it is already a root.
+    pub fn detach(&self) {
+        if let (Some(parent), Some(side)) = (self.get_parent(), self.side()) {
+            let mut p = parent.0.borrow_mut();
+            match side {
+                Side::Left => p.left = None,
+                Side::Right => p.right = None,
+            }
+        }
+        self.0.borrow_mut().parent = None;
+    }
+
+    /// Makes `subtree` this node's child on `side`, detaching it from wherever it was
+    /// first. The previous child on that side, if any, is detached and returned.
+    pub fn splice_child(
+        &self,
+        side: Side,
+        subtree: Option<&NodeWrapper<T>>,
+    ) -> Option<NodeWrapper<T>> {
+        let old = self.get_child(side);
+        if let Some(old) = &old {
+            old.detach();
+        }
+        if let Some(subtree) = subtree {
+            subtree.detach();
+        }
+        match side {
+            Side::Left => self.set_left(subtree),
+            Side::Right => self.set_right(subtree),
+        }
+        old
+    }
+
+    /// Puts `other` in this node's place under its parent, leaving this node detached.
+    /// Fails if this node is a root, since there's no place to put `other`.
+    pub fn replace_with(&self, other: &NodeWrapper<T>) -> AocResult<()> {
+        let parent = self.get_parent().ok_or("Can't replace a root node")?;
+        let side = self.side().ok_or("Can't replace a root node")?;
+        parent.splice_child(side, Some(other));
+        Ok(())
+    }
+}
+
+impl<T: Clone> NodeWrapper<T> {
+    pub fn get_data(&self) -> Option<T> {
+        self.0.borrow().data.clone()
+    }
+}
+
+impl<T: fmt::Display> NodeWrapper<T> {
+    /// Renders the tree rooted at this node in Graphviz DOT format. Nodes are labelled
+    /// with their data, if any; left children are drawn before right children.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph {\n");
+        self.write_dot(&mut out, &mut 0);
+        out.push_str("}\n");
+        out
+    }
+
+    /// Writes this subtree's nodes and edges, numbering nodes in preorder starting from
+    /// `next_id`. Returns this node's id.
+    fn write_dot(&self, out: &mut String, next_id: &mut usize) -> usize {
+        let id = *next_id;
+        *next_id += 1;
+        let label = self
+            .0
+            .borrow()
+            .data
+            .as_ref()
+            .map(|d| d.to_string())
+            .unwrap_or_default();
+        writeln!(out, "    n{id} [label={}];", dot_quote(&label)).unwrap();
+        for child in [self.get_left(), self.get_right()].into_iter().flatten() {
+            let child_id = child.write_dot(out, next_id);
+            writeln!(out, "    n{id} -> n{child_id};").unwrap();
+        }
+        id
+    }
+}
+
+impl NodeWrapper<i64> {
+    pub fn from_ascii(ascii: &[u8]) -> AocResult<NodeWrapper> {
+        let (node, consumed) = NodeWrapper::_from_ascii(ascii)?;
+        ensure!(
+            skip_whitespace(ascii, consumed) == ascii.len(),
+            "Trailing characters after tree"
+        );
+        Ok(node)
+    }
+
     /// Parses a NodeLink from a line of ASCII of the form:
     /// "[[1,2],[3,[4,5]]]" etc.
-    /// Current limitations: no whitespace, only single digit numbers supported.
+    /// Leaves may be any (possibly negative) integer, and whitespace is allowed between
+    /// tokens. Returns the node and the number of bytes consumed.
     fn _from_ascii(ascii: &[u8]) -> AocResult<(NodeWrapper, usize)> {
-        if ascii[0] != b'[' {
-            return failure("Invalid line start");
-        }
+        let mut consumed = skip_whitespace(ascii, 0);
+        ensure!(ascii.get(consumed) == Some(&b'['), "Invalid line start");
 
-        let mut consumed = 0;
         let mut seen_comma = false;
         let mut seen_opening_bracket = false;
         let mut pair = Vec::new();
 
         // Another implicit state machine :(.
         loop {
-            let c = ascii[consumed];
+            let c = *ascii.get(consumed).ok_or("Unexpected end of input")?;
             match c {
                 b'[' => {
                     if seen_opening_bracket {
@@ -166,58 +362,73 @@ impl NodeWrapper {
                         consumed += 1;
                     }
                 }
-                b'0'..=b'9' => {
-                    if (!seen_comma && !pair.is_empty()) || (seen_comma && pair.is_empty()) {
-                        return failure("Invalid digit location");
+                b'-' | b'0'..=b'9' => {
+                    if (!seen_comma && !pair.is_empty()) || (seen_comma && pair.len() != 1) {
+                        failure!("Invalid digit location");
                     }
-                    pair.push(Node::new(Some((c - 48) as i64)).into());
-                    consumed += 1;
+                    let len = ascii[consumed + 1..]
+                        .iter()
+                        .take_while(|b| b.is_ascii_digit())
+                        .count()
+                        + 1;
+                    let literal = std::str::from_utf8(&ascii[consumed..consumed + len])?;
+                    let data = literal
+                        .parse::<i64>()
+                        .map_err(|e| format!("Invalid literal {literal:?}: {e}"))?;
+                    pair.push(Node::new(Some(data)).into());
+                    consumed += len;
                 }
                 b',' => {
                     if seen_comma {
-                        return failure("Two commas in a node");
+                        failure!("Two commas in a node");
                     }
                     seen_comma = true;
                     consumed += 1;
                 }
                 b']' => {
-                    if !seen_comma {
-                        return failure("No comma in a node");
-                    }
-                    if pair.len() != 2 {
-                        return failure(format!("Invalid 'pair': {:?}", pair));
-                    }
+                    ensure!(seen_comma, "No comma in a node");
+                    ensure!(pair.len() == 2, "Invalid 'pair': {:?}", pair);
                     consumed += 1;
                     let node = NodeWrapper::from(Node::new(None));
                     node.set_left(Some(&pair.remove(0)));
                     node.set_right(Some(&pair.remove(0)));
                     return Ok((node, consumed));
                 }
-                _ => return failure("Invalid character"),
+                c if c.is_ascii_whitespace() => consumed = skip_whitespace(ascii, consumed),
+                _ => failure!("Invalid character"),
             }
         }
     }
 }
 
-impl Default for NodeWrapper {
+/// Returns the index of the first non-whitespace byte at or after `start`.
+fn skip_whitespace(ascii: &[u8], start: usize) -> usize {
+    start
+        + ascii[start..]
+            .iter()
+            .take_while(|b| b.is_ascii_whitespace())
+            .count()
+}
+
+impl<T> Default for NodeWrapper<T> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-pub struct DepthFirstIterator {
-    stack: Vec<(NodeLink, usize)>,
+pub struct DepthFirstIterator<T = i64> {
+    stack: Vec<(NodeLink<T>, usize)>,
 }
 
-impl DepthFirstIterator {
-    pub fn new(node: &NodeLink) -> Self {
+impl<T> DepthFirstIterator<T> {
+    pub fn new(node: &NodeLink<T>) -> Self {
         let stack = vec![(node.clone(), 0)];
         DepthFirstIterator { stack }
     }
 }
 
-impl Iterator for DepthFirstIterator {
-    type Item = (NodeWrapper, usize);
+impl<T> Iterator for DepthFirstIterator<T> {
+    type Item = (NodeWrapper<T>, usize);
 
     fn next(&mut self) -> Option<Self::Item> {
         if !self.stack.is_empty() {
@@ -236,6 +447,100 @@ impl Iterator for DepthFirstIterator {
     }
 }
 
+pub struct InOrderIterator<T = i64> {
+    stack: Vec<(NodeLink<T>, usize)>,
+    current: Option<(NodeLink<T>, usize)>,
+}
+
+impl<T> InOrderIterator<T> {
+    pub fn new(node: &NodeLink<T>) -> Self {
+        InOrderIterator {
+            stack: Vec::new(),
+            current: Some((node.clone(), 0)),
+        }
+    }
+}
+
+impl<T> Iterator for InOrderIterator<T> {
+    type Item = (NodeWrapper<T>, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Walk as far left as possible, then visit the deepest pending node.
+        while let Some((node, depth)) = self.current.take() {
+            self.current = node.borrow().left.clone().map(|left| (left, depth + 1));
+            self.stack.push((node, depth));
+        }
+        let (node, depth) = self.stack.pop()?;
+        self.current = node.borrow().right.clone().map(|right| (right, depth + 1));
+        Some((node.into(), depth))
+    }
+}
+
+pub struct PostOrderIterator<T = i64> {
+    /// `(node, depth, children_pushed)`
+    stack: Vec<(NodeLink<T>, usize, bool)>,
+}
+
+impl<T> PostOrderIterator<T> {
+    pub fn new(node: &NodeLink<T>) -> Self {
+        PostOrderIterator {
+            stack: vec![(node.clone(), 0, false)],
+        }
+    }
+}
+
+impl<T> Iterator for PostOrderIterator<T> {
+    type Item = (NodeWrapper<T>, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, depth, children_pushed)) = self.stack.pop() {
+            if children_pushed {
+                return Some((node.into(), depth));
+            }
+            let (left, right) = {
+                let n = node.borrow();
+                (n.left.clone(), n.right.clone())
+            };
+            self.stack.push((node, depth, true));
+            // Push right first so that we pop left first.
+            if let Some(right) = right {
+                self.stack.push((right, depth + 1, false));
+            }
+            if let Some(left) = left {
+                self.stack.push((left, depth + 1, false));
+            }
+        }
+        None
+    }
+}
+
+pub struct BreadthFirstIterator<T = i64> {
+    queue: VecDeque<(NodeLink<T>, usize)>,
+}
+
+impl<T> BreadthFirstIterator<T> {
+    pub fn new(node: &NodeLink<T>) -> Self {
+        BreadthFirstIterator {
+            queue: VecDeque::from([(node.clone(), 0)]),
+        }
+    }
+}
+
+impl<T> Iterator for BreadthFirstIterator<T> {
+    type Item = (NodeWrapper<T>, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, depth) = self.queue.pop_front()?;
+        if let Some(left) = node.borrow().left.clone() {
+            self.queue.push_back((left, depth + 1));
+        }
+        if let Some(right) = node.borrow().right.clone() {
+            self.queue.push_back((right, depth + 1));
+        }
+        Some((node.into(), depth))
+    }
+}
+
 #[cfg(test)]
 mod nodewrapper_tests {
     use super::*;
@@ -255,6 +560,28 @@ mod nodewrapper_tests {
         Ok(())
     }
 
+    #[test]
+    fn nodewrapper_from_ascii_extended() -> AocResult<()> {
+        for (s, expected) in [
+            ("[10,-2]", "[10,-2]"),
+            (" [ [123 , 4],\t-56 ] \n", "[[123,4],-56]"),
+            ("[[-1,-2],[0,9999999999]]", "[[-1,-2],[0,9999999999]]"),
+        ] {
+            let t = NodeWrapper::from_ascii(s.as_bytes())?;
+            assert_eq!(t.to_string(), expected);
+            assert_eq!(
+                NodeWrapper::from_ascii(expected.as_bytes())?.to_string(),
+                expected
+            );
+        }
+        for s in [
+            "", "[1,2", "[1 2]", "[1,,2]", "[-,2]", "[1,2,3]", "[1,2]x", "[1,2]]", "1",
+        ] {
+            assert!(NodeWrapper::from_ascii(s.as_bytes()).is_err(), "{s}");
+        }
+        Ok(())
+    }
+
     #[test]
     fn nodewrapper_depth_first_traversal() -> AocResult<()> {
         for (s, v, d) in [
@@ -288,4 +615,183 @@ mod nodewrapper_tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn nodewrapper_to_dot() -> AocResult<()> {
+        let t = NodeWrapper::from_ascii(b"[[1,2],3]")?;
+        assert_eq!(
+            t.to_dot(),
+            "digraph {
+    n0 [label=\"\"];
+    n1 [label=\"\"];
+    n2 [label=\"1\"];
+    n1 -> n2;
+    n3 [label=\"2\"];
+    n1 -> n3;
+    n0 -> n1;
+    n4 [label=\"3\"];
+    n0 -> n4;
+}
+"
+        );
+
+        // Labels are escaped, so they can't end early or swallow the closing quote.
+        let t = NodeWrapper::new();
+        t.set_data(Some(r#"say "hi" \"#.to_string()));
+        assert_eq!(
+            t.to_dot(),
+            r#"digraph {
+    n0 [label="say \"hi\" \\"];
+}
+"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn nodewrapper_traversal_orders() -> AocResult<()> {
+        // Label every node: leaves keep their data, pairs become 100 + 10 * depth +
+        // preorder position, so that each traversal order is easy to read off.
+        let t = NodeWrapper::from_ascii(b"[[1,2],[3,[4,5]]]")?;
+        for (i, (node, depth)) in t.depth_first_iter().enumerate() {
+            if !node.is_leaf() {
+                node.set_data(Some(10 * depth as i64 + i as i64 + 100));
+            }
+        }
+        let order = |it: &mut dyn Iterator<Item = (NodeWrapper, usize)>| {
+            it.map(|(n, d)| (n.get_data().unwrap(), d))
+                .collect::<Vec<_>>()
+        };
+        // Pairs: root = 100, [1,2] = 111, [3,[4,5]] = 114, [4,5] = 126.
+        assert_eq!(
+            order(&mut t.in_order_iter()),
+            vec![
+                (1, 2),
+                (111, 1),
+                (2, 2),
+                (100, 0),
+                (3, 2),
+                (114, 1),
+                (4, 3),
+                (126, 2),
+                (5, 3)
+            ]
+        );
+        assert_eq!(
+            order(&mut t.post_order_iter()),
+            vec![
+                (1, 2),
+                (2, 2),
+                (111, 1),
+                (3, 2),
+                (4, 3),
+                (5, 3),
+                (126, 2),
+                (114, 1),
+                (100, 0)
+            ]
+        );
+        assert_eq!(
+            order(&mut t.breadth_first_iter()),
+            vec![
+                (100, 0),
+                (111, 1),
+                (114, 1),
+                (1, 2),
+                (2, 2),
+                (3, 2),
+                (126, 2),
+                (4, 3),
+                (5, 3)
+            ]
+        );
+        assert_eq!(
+            order(&mut t.leaves()),
+            vec![(1, 2), (2, 2), (3, 2), (4, 3), (5, 3)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn nodewrapper_generic_payload() {
+        let root: NodeWrapper<String> = NodeWrapper::new();
+        let left = NodeWrapper::from(Node::new(Some("ab".to_string())));
+        let right = NodeWrapper::from(Node::new(Some("cd".to_string())));
+        root.set_left(Some(&left));
+        root.set_right(Some(&right));
+        assert_eq!(root.to_string(), "[ab,cd]");
+        assert_eq!(
+            root.leaves()
+                .filter_map(|(n, _)| n.get_data())
+                .collect::<Vec<_>>(),
+            vec!["ab", "cd"]
+        );
+
+        let pairs: NodeWrapper<(u8, char)> = NodeWrapper::new();
+        pairs.set_data(Some((1, 'x')));
+        assert_eq!(pairs.get_data(), Some((1, 'x')));
+    }
+
+    #[test]
+    fn nodewrapper_editing() -> AocResult<()> {
+        let t = NodeWrapper::from_ascii(b"[[1,2],[3,4]]")?;
+        let l = t.get_left().unwrap();
+        let r = t.get_right().unwrap();
+        assert_eq!(l.side(), Some(Side::Left));
+        assert_eq!(r.side(), Some(Side::Right));
+        assert_eq!(t.side(), None);
+        assert!(l.sibling().unwrap().ptr_eq(&r));
+        assert!(t.sibling().is_none());
+
+        // Snailfish style explode: replace a pair with a regular number.
+        l.replace_with(&Node::new(Some(0)).into())?;
+        assert_eq!(t.to_string(), "[0,[3,4]]");
+        assert!(l.get_parent().is_none());
+        assert!(t.replace_with(&l).is_err());
+
+        // Move a subtree between trees.
+        let four = r.get_right().unwrap();
+        let old = l.splice_child(Side::Right, Some(&four)).unwrap();
+        assert_eq!(old.to_string(), "2");
+        assert!(old.get_parent().is_none());
+        assert!(r.get_right().is_none());
+        assert!(four.get_parent().unwrap().ptr_eq(&l));
+        assert_eq!(l.to_string(), "[1,4]");
+
+        l.get_left().unwrap().detach();
+        assert!(l.get_left().is_none());
+        assert_eq!(l.get_right().unwrap().side(), Some(Side::Right));
+        Ok(())
+    }
+
+    #[test]
+    fn nodewrapper_validate_and_metrics() -> AocResult<()> {
+        let t = NodeWrapper::from_ascii(b"[[1,2],[3,[4,5]]]")?;
+        t.validate()?;
+        assert_eq!(t.size(), 9);
+        assert_eq!(t.depth(), 3);
+        assert_eq!(t.get_left().unwrap().depth(), 1);
+        let leaf = t.get_left().unwrap().get_left().unwrap();
+        assert_eq!((leaf.size(), leaf.depth()), (1, 0));
+
+        leaf.set_data(None);
+        assert!(t.validate().is_err());
+        leaf.set_data(Some(1));
+
+        t.set_data(Some(7));
+        assert!(t.validate().is_err());
+        t.set_data(None);
+
+        t.get_right().unwrap().splice_child(Side::Left, None);
+        assert!(t.validate().is_err());
+
+        // A child whose parent link points elsewhere.
+        let other = NodeWrapper::from_ascii(b"[8,9]")?;
+        let eight = other.get_left().unwrap();
+        t.get_right().unwrap().set_left(Some(&eight));
+        t.validate()?;
+        other.set_left(Some(&eight));
+        assert!(t.validate().is_err());
+        Ok(())
+    }
 }
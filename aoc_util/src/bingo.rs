@@ -0,0 +1,159 @@
+//! Bingo against a squid, as in 2021 day 4, on square boards of any size.
+
+use crate::ensure;
+use crate::errors::{malformed, AocResult, LineContext};
+
+/// A square bingo board. A board wins once every square in some row or column is marked.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Board {
+    size: usize,
+    /// Row-major.
+    values: Vec<i64>,
+    marked: Vec<bool>,
+}
+
+impl Board {
+    /// A `size` by `size` board, with `values` in row-major order.
+    pub fn new(size: usize, values: Vec<i64>) -> AocResult<Self> {
+        ensure!(size > 0, "Empty board");
+        ensure!(
+            values.len() == size * size,
+            "{} values for a {size}x{size} board",
+            values.len()
+        );
+        Ok(Board {
+            size,
+            marked: vec![false; values.len()],
+            values,
+        })
+    }
+
+    /// Parses a board from rows of whitespace-separated numbers. There must be as many
+    /// numbers in each row as there are rows.
+    pub fn from_rows<S: AsRef<str>>(rows: &[S]) -> AocResult<Self> {
+        let size = rows.len();
+        let mut values = Vec::with_capacity(size * size);
+        for (i, row) in rows.iter().enumerate() {
+            let row: Vec<i64> = row
+                .as_ref()
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .at_line(i + 1)?;
+            if row.len() != size {
+                return malformed(
+                    i + 1,
+                    format!("expected {size} numbers in a row, got {}", row.len()),
+                );
+            }
+            values.extend(row);
+        }
+        Self::new(size, values)
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Marks every square holding `x`, returning whether there were any.
+    pub fn mark(&mut self, x: i64) -> bool {
+        let mut found = false;
+        for (v, m) in self.values.iter().zip(&mut self.marked) {
+            if *v == x {
+                *m = true;
+                found = true;
+            }
+        }
+        found
+    }
+
+    pub fn has_won(&self) -> bool {
+        let n = self.size;
+        (0..n).any(|i| {
+            (0..n).all(|j| self.marked[i * n + j]) || (0..n).all(|j| self.marked[j * n + i])
+        })
+    }
+
+    /// The sum of the unmarked squares.
+    pub fn unmarked_sum(&self) -> i64 {
+        self.values
+            .iter()
+            .zip(&self.marked)
+            .filter(|(_, &m)| !m)
+            .map(|(v, _)| v)
+            .sum()
+    }
+
+    /// The score of a board that has just won on `last_number`.
+    pub fn score(&self, last_number: i64) -> i64 {
+        self.unmarked_sum() * last_number
+    }
+}
+
+/// Draws `numbers` in turn, yielding `(board index, score)` for each board as it wins.
+/// Each board wins at most once; boards that win on the same number come in index order.
+/// The first win is `.next()`, and the last is `.last()`.
+pub fn play(
+    numbers: &[i64],
+    mut boards: Vec<Board>,
+) -> impl Iterator<Item = (usize, i64)> + '_ {
+    let mut won = vec![false; boards.len()];
+    numbers.iter().flat_map(move |&x| {
+        let mut wins = Vec::new();
+        for (i, board) in boards.iter_mut().enumerate() {
+            if !won[i] && board.mark(x) && board.has_won() {
+                won[i] = true;
+                wins.push((i, board.score(x)));
+            }
+        }
+        wins
+    })
+}
+
+#[cfg(test)]
+mod bingo_tests {
+    use super::*;
+
+    #[test]
+    fn boards() -> AocResult<()> {
+        let mut board = Board::from_rows(&["1 2 3", " 4  5 6", "7 8 9"])?;
+        assert_eq!(board.size(), 3);
+        assert!(board.mark(2));
+        assert!(!board.mark(10));
+        assert!(board.mark(5));
+        assert!(!board.has_won());
+        assert!(board.mark(8));
+        assert!(board.has_won());
+        assert_eq!(board.unmarked_sum(), 45 - 15);
+        assert_eq!(board.score(8), 240);
+
+        let err = Board::from_rows(&["1 2", "3"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 2: expected 2 numbers in a row, got 1"
+        );
+        assert!(Board::from_rows(&["1 x", "3 4"]).is_err());
+        assert!(Board::from_rows::<&str>(&[]).is_err());
+        assert!(Board::new(2, vec![1, 2, 3]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn games() -> AocResult<()> {
+        let boards = vec![
+            Board::new(2, vec![1, 2, 3, 4])?,
+            Board::new(2, vec![4, 3, 2, 1])?,
+            Board::new(2, vec![5, 6, 7, 8])?,
+        ];
+        // The first two boards both win on 3, each by a column; the third never does.
+        let wins: Vec<_> = play(&[1, 3, 9, 2], boards.clone()).collect();
+        assert_eq!(wins, [(0, 6 * 3), (1, 6 * 3)]);
+        assert_eq!(play(&[4, 2, 7], boards.clone()).next(), Some((0, 4 * 2)));
+        assert_eq!(
+            play(&[4, 2, 7, 8], boards.clone()).last(),
+            Some((2, 11 * 8))
+        );
+        assert_eq!(play(&[], boards).next(), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,385 @@
+use crate::errors::{malformed, malformed_at, AocResult};
+use crate::{ensure, failure};
+use std::fmt::Write;
+
+/// A growable, MSBit-first sequence of bits.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BitVec {
+    store: Vec<u8>,
+    /// Number of valid bits.
+    bit_len: usize,
+}
+
+impl BitVec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_hex_str(hex: &str) -> AocResult<Self> {
+        if let Some(i) = hex.find(|c: char| !c.is_ascii_hexdigit()) {
+            let c = hex[i..].chars().next().unwrap_or_default();
+            return malformed_at(
+                1,
+                hex[..i].chars().count() + 1,
+                format!("bad hex digit {c:?}"),
+            );
+        }
+        let mut out = Vec::with_capacity(hex.len() / 2);
+        for chunk in hex.as_bytes().chunks(2) {
+            let s = String::from_utf8(chunk.to_vec())?;
+            let mut b = u8::from_str_radix(&s, 16)?;
+            if s.len() == 1 {
+                b <<= 4;
+            }
+            out.push(b);
+        }
+        Ok(BitVec {
+            store: out,
+            bit_len: hex.len() * 4,
+        })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        BitVec {
+            store: bytes.to_vec(),
+            bit_len: bytes.len() * 8,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bit_len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bit_len == 0
+    }
+
+    pub fn get_bit(&self, idx: usize) -> AocResult<u64> {
+        ensure!(
+            idx < self.bit_len,
+            "get_bit: invalid bit index {} >= {}",
+            idx,
+            self.bit_len
+        );
+        let byte_idx = idx / 8_usize;
+        let byte = self.store[byte_idx];
+        let bit_index_in_byte = 8 - (idx % 8) - 1;
+        let bit = (byte >> bit_index_in_byte) & 1;
+        Ok(bit as u64)
+    }
+
+    // TODO stupidly slow, but simple. Optimize later.
+    /// Get a range of bits of length `bit_len` from the bitvec, starting from bit index `idx`.
+    /// Returns `Err` if `idx` is outside the bitvec or `bit_len` > 64 or `bit_len` == 0.
+    pub fn get_bits(&self, idx: usize, bit_len: usize) -> AocResult<u64> {
+        ensure!(
+            idx < self.bit_len,
+            "get_bits: invalid bit index {} >= {}",
+            idx,
+            self.bit_len
+        );
+        if bit_len > 64 || bit_len == 0 {
+            failure!("get_bits: invalid bit length {}", bit_len);
+        }
+        let mut out: u64 = 0;
+        for i in 0..bit_len {
+            let bit = self.get_bit(idx + i)?;
+            out |= bit << (bit_len - i - 1);
+        }
+        Ok(out)
+    }
+
+    pub fn push_bit(&mut self, bit: bool) {
+        if self.bit_len.is_multiple_of(8) {
+            self.store.push(0);
+        }
+        if bit {
+            let bit_index_in_byte = 8 - (self.bit_len % 8) - 1;
+            *self.store.last_mut().unwrap() |= 1 << bit_index_in_byte;
+        }
+        self.bit_len += 1;
+    }
+
+    /// Appends the low `bit_len` bits of `value`, MSBit first. Returns `Err` if
+    /// `bit_len` > 64 or `value` doesn't fit in `bit_len` bits.
+    pub fn push_bits(&mut self, value: u64, bit_len: usize) -> AocResult<()> {
+        ensure!(bit_len <= 64, "push_bits: invalid bit length {}", bit_len);
+        if bit_len < 64 && value >> bit_len != 0 {
+            failure!("push_bits: {value} doesn't fit in {bit_len} bits");
+        }
+        for i in (0..bit_len).rev() {
+            self.push_bit((value >> i) & 1 == 1);
+        }
+        Ok(())
+    }
+
+    /// Appends all of `other`'s bits.
+    pub fn append(&mut self, other: &BitVec) {
+        for i in 0..other.len() {
+            // Can't fail: i is in bounds.
+            self.push_bit(other.get_bit(i).unwrap() == 1);
+        }
+    }
+
+    /// Formats the bits as uppercase hex, padding the last nibble with zeros.
+    pub fn to_hex_string(&self) -> String {
+        let mut out = String::with_capacity(self.store.len() * 2);
+        for b in &self.store {
+            write!(out, "{b:02X}").unwrap();
+        }
+        out.truncate(self.bit_len.div_ceil(4));
+        out
+    }
+
+    /// Returns a cursor reading from the start of the bitvec.
+    pub fn reader(&self) -> BitReader<'_> {
+        BitReader::new(self)
+    }
+}
+
+/// A read cursor over a `BitVec`, so that parsers don't have to track bit offsets by
+/// hand.
+#[derive(Clone, Debug)]
+pub struct BitReader<'a> {
+    bits: &'a BitVec,
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(bits: &'a BitVec) -> Self {
+        BitReader { bits, pos: 0 }
+    }
+
+    /// Reads the next `n` bits as an MSBit-first integer and advances past them.
+    /// Returns `Err` (without advancing) if `n` is 0 or > 64, or fewer than `n` bits
+    /// remain.
+    pub fn take(&mut self, n: usize) -> AocResult<u64> {
+        ensure!(
+            n <= self.remaining(),
+            "take: {n} bits requested at position {}, but only {} remain",
+            self.pos,
+            self.remaining()
+        );
+        let value = self.bits.get_bits(self.pos, n)?;
+        self.pos += n;
+        Ok(value)
+    }
+
+    pub fn take_bit(&mut self) -> AocResult<bool> {
+        Ok(self.take(1)? == 1)
+    }
+
+    /// Advances past `n` bits without reading them.
+    pub fn skip(&mut self, n: usize) -> AocResult<()> {
+        ensure!(
+            n <= self.remaining(),
+            "skip: can't skip {n} bits at position {}",
+            self.pos
+        );
+        self.pos += n;
+        Ok(())
+    }
+
+    /// Number of bits read so far.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.bits.len() - self.pos
+    }
+
+    /// Advances to the next byte boundary, unless already on one. Never moves past the
+    /// end of the bitvec.
+    pub fn align(&mut self) {
+        self.pos = self.pos.next_multiple_of(8).min(self.bits.len());
+    }
+}
+
+/// Parses lines of '0's and '1's, all the same width of at most 64, as binary numbers.
+/// Returns the numbers and the width.
+pub fn parse_binary_lines<S: AsRef<str>>(lines: &[S]) -> AocResult<(Vec<u64>, usize)> {
+    let width = lines.first().ok_or("No lines")?.as_ref().len();
+    ensure!(
+        (1..=64).contains(&width),
+        "Lines must have 1 to 64 bits, not {width}"
+    );
+    let mut out = Vec::with_capacity(lines.len());
+    for (i, line) in lines.iter().enumerate() {
+        let line = line.as_ref();
+        let mut value = 0;
+        for (j, c) in line.chars().enumerate() {
+            let bit = match c {
+                '0' => 0,
+                '1' => 1,
+                _ => return malformed_at(i + 1, j + 1, format!("bad bit {c:?}")),
+            };
+            value = value << 1 | bit;
+        }
+        if line.len() != width {
+            return malformed(i + 1, format!("expected {width} bits, got {}", line.len()));
+        }
+        out.push(value);
+    }
+    Ok((out, width))
+}
+
+/// Narrows `lines` of binary numbers down to one, going from the most significant bit to
+/// the least. At each position, keeps the numbers with the bit that's most common among
+/// those left (1 on a tie) if `keep_most`, or else least common (0 on a tie). Fails if
+/// more than one number is left at the end.
+pub fn filter_by_bit_criteria<S: AsRef<str>>(
+    lines: &[S],
+    keep_most: bool,
+) -> AocResult<u64> {
+    let (mut values, width) = parse_binary_lines(lines)?;
+    for bit in (0..width).rev() {
+        if values.len() == 1 {
+            break;
+        }
+        let ones = values.iter().filter(|&&v| v >> bit & 1 == 1).count();
+        let ones_most_common = 2 * ones >= values.len();
+        let keep = u64::from(ones_most_common == keep_most);
+        values.retain(|&v| v >> bit & 1 == keep);
+    }
+    match values[..] {
+        [v] => Ok(v),
+        _ => failure!("{} numbers match every bit criterion", values.len()),
+    }
+}
+
+#[cfg(test)]
+mod bitvec_tests {
+    use super::*;
+
+    #[test]
+    fn bitvec_get_bit() -> AocResult<()> {
+        assert_eq!(
+            BitVec::from_hex_str("12G4").unwrap_err().to_string(),
+            "Line 1, column 3: bad hex digit 'G'"
+        );
+        let bv = BitVec::from_hex_str("123456789ABCDEF")?;
+        assert_eq!(bv.get_bit(0)?, 0);
+        assert_eq!(bv.get_bit(1)?, 0);
+        assert_eq!(bv.get_bit(2)?, 0);
+        assert_eq!(bv.get_bit(3)?, 1);
+        assert_eq!(bv.get_bit(4)?, 0);
+        assert_eq!(bv.get_bit(5)?, 0);
+        assert_eq!(bv.get_bit(6)?, 1);
+        assert_eq!(bv.get_bit(7)?, 0);
+
+        assert_eq!(bv.get_bit(31)?, 0);
+        assert_eq!(bv.get_bit(32)?, 1);
+        assert_eq!(bv.get_bit(33)?, 0);
+        assert_eq!(bv.get_bit(34)?, 0);
+        assert_eq!(bv.get_bit(35)?, 1);
+        assert_eq!(bv.get_bit(36)?, 1);
+        assert_eq!(bv.get_bit(37)?, 0);
+        assert_eq!(bv.get_bit(38)?, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn bitvec_get_bits() -> AocResult<()> {
+        let bv = BitVec::from_hex_str("123456789ABCDEF")?;
+        assert_eq!(bv.get_bits(0, 1)?, 0);
+        assert_eq!(bv.get_bits(1, 1)?, 0);
+        assert_eq!(bv.get_bits(2, 1)?, 0);
+        assert_eq!(bv.get_bits(3, 1)?, 1);
+        assert_eq!(bv.get_bits(4, 1)?, 0);
+        assert_eq!(bv.get_bits(5, 1)?, 0);
+        assert_eq!(bv.get_bits(6, 1)?, 1);
+        assert_eq!(bv.get_bits(7, 1)?, 0);
+
+        assert_eq!(bv.get_bits(0, 4)?, 1);
+        assert_eq!(bv.get_bits(0, 8)?, 0x12);
+        assert_eq!(bv.get_bits(0, 9)?, 36);
+        assert_eq!(bv.get_bits(1, 3)?, 1);
+        assert_eq!(bv.get_bits(8, 8)?, 0x34);
+        assert_eq!(bv.get_bits(8, 20)?, 0x34567);
+
+        Ok(())
+    }
+
+    #[test]
+    fn bitvec_writer() -> AocResult<()> {
+        let mut bv = BitVec::new();
+        assert!(bv.is_empty());
+        bv.push_bits(0b110, 3)?;
+        bv.push_bit(true);
+        bv.push_bits(0x2FE28, 20)?;
+        assert_eq!(bv.len(), 24);
+        assert_eq!(bv.get_bits(0, 4)?, 0xD);
+        assert_eq!(bv.get_bits(4, 20)?, 0x2FE28);
+        assert_eq!(bv.to_hex_string(), "D2FE28");
+        assert!(bv.push_bits(4, 2).is_err());
+        assert!(bv.push_bits(0, 65).is_err());
+        bv.push_bits(u64::MAX, 64)?;
+        assert_eq!(bv.get_bits(24, 64)?, u64::MAX);
+
+        for hex in ["123456789ABCDEF", "8A004A801A8002F478", "0"] {
+            assert_eq!(BitVec::from_hex_str(hex)?.to_hex_string(), hex);
+        }
+        let mut bv = BitVec::from_hex_str("A")?;
+        bv.push_bit(true);
+        bv.append(&BitVec::from_hex_str("3")?);
+        assert_eq!(bv.len(), 9);
+        assert_eq!(bv.to_hex_string(), "A98");
+
+        let bv = BitVec::from_bytes(&[0xAB, 0x01]);
+        assert_eq!(bv.len(), 16);
+        assert_eq!(bv, BitVec::from_hex_str("AB01")?);
+        Ok(())
+    }
+
+    #[test]
+    fn bitreader() -> AocResult<()> {
+        let bv = BitVec::from_hex_str("D2FE28")?;
+        let mut r = bv.reader();
+        assert_eq!(r.take(3)?, 6);
+        assert!(r.take_bit()?);
+        assert_eq!(r.position(), 4);
+        assert_eq!(r.remaining(), 20);
+        r.skip(2)?;
+        assert_eq!(r.take(6)?, 0b101111);
+        r.align();
+        assert_eq!(r.position(), 16);
+        r.align();
+        assert_eq!(r.position(), 16);
+        assert!(r.take(9).is_err());
+        assert_eq!(r.position(), 16);
+        assert!(r.take(0).is_err());
+        assert_eq!(r.take(8)?, 0x28);
+        assert_eq!(r.remaining(), 0);
+        assert!(r.take_bit().is_err());
+        assert!(r.skip(1).is_err());
+
+        let bv = BitVec::from_hex_str("F")?;
+        let mut r = bv.reader();
+        r.skip(1)?;
+        r.align();
+        assert_eq!(r.position(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn bit_criteria() -> AocResult<()> {
+        let lines = [
+            "00100", "11110", "10110", "10111", "10101", "01111", "00111", "11100", "10000",
+            "11001", "00010", "01010",
+        ];
+        assert_eq!(filter_by_bit_criteria(&lines, true)?, 23);
+        assert_eq!(filter_by_bit_criteria(&lines, false)?, 10);
+        assert_eq!(filter_by_bit_criteria(&["1"], false)?, 1);
+        assert!(filter_by_bit_criteria(&["01", "01"], true).is_err());
+        assert!(filter_by_bit_criteria::<&str>(&[], true).is_err());
+
+        assert_eq!(parse_binary_lines(&["101", "011"])?, (vec![5, 3], 3));
+        let err = parse_binary_lines(&["101", "0x1"]).unwrap_err();
+        assert_eq!(err.to_string(), "Line 2, column 2: bad bit 'x'");
+        let err = parse_binary_lines(&["101", "01"]).unwrap_err();
+        assert_eq!(err.to_string(), "Line 2: expected 3 bits, got 2");
+        assert!(parse_binary_lines(&["0".repeat(65)]).is_err());
+        Ok(())
+    }
+}
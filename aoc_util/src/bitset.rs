@@ -0,0 +1,109 @@
+//! Sets of small integer ids, for tracking visited nodes without allocating.
+
+use std::fmt;
+
+/// A set of ids in `0..64`, stored as the bits of a `u64`. It's `Copy`, so a recursive
+/// search can hand each call its own copy, and cheap to hash as a memo key.
+#[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct SmallSet64(u64);
+
+impl SmallSet64 {
+    /// The largest number of ids a set can hold: ids run from 0 to `CAPACITY - 1`.
+    pub const CAPACITY: usize = u64::BITS as usize;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `id`, returning whether it was newly added. Panics if `id` is 64 or more.
+    pub fn insert(&mut self, id: usize) -> bool {
+        let was_absent = !self.contains(id);
+        self.0 |= Self::bit(id);
+        was_absent
+    }
+
+    /// Removes `id`, returning whether it was present. Panics if `id` is 64 or more.
+    pub fn remove(&mut self, id: usize) -> bool {
+        let was_present = self.contains(id);
+        self.0 &= !Self::bit(id);
+        was_present
+    }
+
+    /// Panics if `id` is 64 or more.
+    pub fn contains(&self, id: usize) -> bool {
+        self.0 & Self::bit(id) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The ids in the set, smallest first.
+    pub fn iter(&self) -> impl Iterator<Item = usize> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let id = bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+            Some(id)
+        })
+    }
+
+    fn bit(id: usize) -> u64 {
+        assert!(id < Self::CAPACITY, "Id {id} doesn't fit in a SmallSet64");
+        1 << id
+    }
+}
+
+impl FromIterator<usize> for SmallSet64 {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut out = Self::new();
+        for id in iter {
+            out.insert(id);
+        }
+        out
+    }
+}
+
+impl fmt::Debug for SmallSet64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod bitset_tests {
+    use super::*;
+
+    #[test]
+    fn membership() {
+        let mut set = SmallSet64::new();
+        assert!(set.is_empty());
+        assert!(set.insert(3));
+        assert!(set.insert(63));
+        assert!(set.insert(0));
+        assert!(!set.insert(3));
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(63) && !set.contains(62));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0, 3, 63]);
+        assert_eq!(format!("{set:?}"), "{0, 3, 63}");
+
+        let copy = set;
+        assert!(set.remove(3));
+        assert!(!set.remove(3));
+        assert_eq!(set, [63, 0].into_iter().collect());
+        assert!(copy.contains(3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_big() {
+        SmallSet64::new().insert(64);
+    }
+}
@@ -0,0 +1,90 @@
+//! Lines of nested brackets, like the navigation subsystem of 2021 day 10.
+
+/// What `check` makes of a line.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LineStatus {
+    /// Every bracket is closed by its match.
+    Complete,
+    /// The closer `found` at `column` (0-based) doesn't match the innermost open
+    /// bracket, or closes a bracket that was never opened.
+    Corrupted { column: usize, found: char },
+    /// The line ends with brackets still open; `missing` closes them, innermost first.
+    Incomplete { missing: String },
+    /// `found`, at `column`, isn't a bracket at all.
+    Invalid { column: usize, found: char },
+}
+
+/// The closer for the opener `c`, or None if `c` isn't an opener.
+pub fn closer(c: char) -> Option<char> {
+    match c {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '{' => Some('}'),
+        '<' => Some('>'),
+        _ => None,
+    }
+}
+
+fn is_closer(c: char) -> bool {
+    matches!(c, ')' | ']' | '}' | '>')
+}
+
+/// Checks that the brackets `()`, `[]`, `{}` and `<>` in `line` nest properly, stopping
+/// at the first one that doesn't.
+pub fn check(line: &str) -> LineStatus {
+    let mut expected: Vec<char> = Vec::new();
+    for (column, c) in line.chars().enumerate() {
+        if let Some(close) = closer(c) {
+            expected.push(close);
+        } else if !is_closer(c) {
+            return LineStatus::Invalid { column, found: c };
+        } else if expected.pop() != Some(c) {
+            return LineStatus::Corrupted { column, found: c };
+        }
+    }
+    if expected.is_empty() {
+        LineStatus::Complete
+    } else {
+        LineStatus::Incomplete {
+            missing: expected.into_iter().rev().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod brackets_tests {
+    use super::*;
+
+    #[test]
+    fn statuses() {
+        assert_eq!(check(""), LineStatus::Complete);
+        assert_eq!(check("([]{<>})"), LineStatus::Complete);
+        assert_eq!(
+            check("{([(<{}[<>[]}>{[]{[(<()>"),
+            LineStatus::Corrupted {
+                column: 12,
+                found: '}'
+            }
+        );
+        assert_eq!(
+            check("())"),
+            LineStatus::Corrupted {
+                column: 2,
+                found: ')'
+            }
+        );
+        assert_eq!(
+            check("[({(<(())[]>[[{[]{<()<>>"),
+            LineStatus::Incomplete {
+                missing: "}}]])})]".to_string()
+            }
+        );
+        assert_eq!(
+            check("(a)"),
+            LineStatus::Invalid {
+                column: 1,
+                found: 'a'
+            }
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! A priority queue for small integer priorities, as used by Dial's version of
+//! Dijkstra's algorithm.
+
+/// A min-priority queue for searches whose edge weights are small integers. Priorities
+/// must never go below that of the last item popped, nor more than `max_step` above it,
+/// which holds for Dijkstra's algorithm when no edge weighs more than `max_step`. Pushes
+/// and pops then take O(1) time (amortized over the scan for the next non-empty
+/// bucket), rather than a binary heap's O(log n). Weights of 0 are fine, so this also
+/// serves for 0-1 BFS.
+#[derive(Clone, Debug)]
+pub struct BucketQueue<T> {
+    /// A ring of buckets: `buckets[cursor]` holds the items with priority `floor`, the
+    /// next bucket those with priority `floor + 1`, and so on.
+    buckets: Vec<Vec<T>>,
+    cursor: usize,
+    floor: u64,
+    len: usize,
+}
+
+impl<T> BucketQueue<T> {
+    pub fn new(max_step: usize) -> Self {
+        Self {
+            buckets: (0..=max_step).map(|_| Vec::new()).collect(),
+            cursor: 0,
+            floor: 0,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Panics if `priority` is below the last priority popped (or 0, before any pops), or
+    /// more than `max_step` above it.
+    pub fn push(&mut self, priority: u64, item: T) {
+        let offset = priority
+            .checked_sub(self.floor)
+            .and_then(|o| usize::try_from(o).ok())
+            .filter(|&o| o < self.buckets.len());
+        let Some(offset) = offset else {
+            panic!(
+                "Priority {priority} is outside {}..={}",
+                self.floor,
+                self.floor + self.buckets.len() as u64 - 1
+            );
+        };
+        let index = (self.cursor + offset) % self.buckets.len();
+        self.buckets[index].push(item);
+        self.len += 1;
+    }
+
+    /// An item with the smallest priority, and that priority. Items with equal
+    /// priorities come out in no particular order.
+    pub fn pop(&mut self) -> Option<(u64, T)> {
+        if self.len == 0 {
+            return None;
+        }
+        loop {
+            if let Some(item) = self.buckets[self.cursor].pop() {
+                self.len -= 1;
+                return Some((self.floor, item));
+            }
+            self.cursor = (self.cursor + 1) % self.buckets.len();
+            self.floor += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod bucket_tests {
+    use super::*;
+
+    #[test]
+    fn ordering() {
+        let mut q = BucketQueue::new(3);
+        assert!(q.is_empty());
+        assert_eq!(q.pop(), None);
+        q.push(2, 'c');
+        q.push(0, 'a');
+        q.push(3, 'd');
+        assert_eq!(q.len(), 3);
+        assert_eq!(q.pop(), Some((0, 'a')));
+        assert_eq!(q.pop(), Some((2, 'c')));
+        // The window has moved on: 5 is now within reach, and the ring wraps.
+        q.push(5, 'e');
+        q.push(2, 'b');
+        assert_eq!(q.pop(), Some((2, 'b')));
+        assert_eq!(q.pop(), Some((3, 'd')));
+        assert_eq!(q.pop(), Some((5, 'e')));
+        assert_eq!(q.pop(), None);
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "outside")]
+    fn too_far_ahead() {
+        let mut q = BucketQueue::new(3);
+        q.push(4, ());
+    }
+
+    #[test]
+    #[should_panic(expected = "outside")]
+    fn behind() {
+        let mut q = BucketQueue::new(3);
+        q.push(2, ());
+        q.pop();
+        q.push(1, ());
+    }
+}
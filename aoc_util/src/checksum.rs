@@ -0,0 +1,134 @@
+//! SHA-256 manifests for cached puzzle inputs, so that a truncated or accidentally
+//! edited input is noticed instead of producing a wrong answer.
+
+use crate::errors::AocResult;
+use crate::failure;
+use crate::sha256::sha256_hex;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The manifest's file name. It lives next to the inputs it covers and uses the same
+/// format as `sha256sum`, so `sha256sum -c inputs.sha256` works too.
+pub const MANIFEST: &str = "inputs.sha256";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ChecksumStatus {
+    /// The manifest has no entry for the input.
+    Unrecorded,
+    Matches,
+    Mismatch {
+        expected: String,
+        actual: String,
+    },
+}
+
+/// The manifest covering `input`.
+pub fn manifest_path(input: &Path) -> PathBuf {
+    input.with_file_name(MANIFEST)
+}
+
+/// The name `input` is recorded under: its file name, minus any ".gz", since the
+/// checksum is of the uncompressed contents.
+fn entry_name(input: &Path) -> AocResult<String> {
+    let name = input
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or(format!("No file name in {input:?}"))?;
+    Ok(name.strip_suffix(".gz").unwrap_or(name).to_string())
+}
+
+fn read_manifest(path: &Path) -> AocResult<BTreeMap<String, String>> {
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let mut entries = BTreeMap::new();
+    for (i, line) in fs::read_to_string(path)?.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (hash, name) = line.split_once("  ").ok_or(format!(
+            "{}:{}: expected \"HASH  NAME\"",
+            path.display(),
+            i + 1
+        ))?;
+        if hash.len() != 64 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+            failure!("{}:{}: bad hash {hash:?}", path.display(), i + 1);
+        }
+        entries.insert(name.to_string(), hash.to_ascii_lowercase());
+    }
+    Ok(entries)
+}
+
+/// Records the checksum of `contents` as that of `input`, replacing any previous entry.
+pub fn record_checksum(input: &Path, contents: &str) -> AocResult<()> {
+    let path = manifest_path(input);
+    let mut entries = read_manifest(&path)?;
+    entries.insert(entry_name(input)?, sha256_hex(contents.as_bytes()));
+    let manifest: String = entries
+        .iter()
+        .map(|(name, hash)| format!("{hash}  {name}\n"))
+        .collect();
+    fs::write(path, manifest)?;
+    Ok(())
+}
+
+/// Checks `contents`, read from `input`, against the manifest.
+pub fn check_checksum(input: &Path, contents: &str) -> AocResult<ChecksumStatus> {
+    let entries = read_manifest(&manifest_path(input))?;
+    let Some(expected) = entries.get(&entry_name(input)?) else {
+        return Ok(ChecksumStatus::Unrecorded);
+    };
+    let actual = sha256_hex(contents.as_bytes());
+    Ok(if *expected == actual {
+        ChecksumStatus::Matches
+    } else {
+        ChecksumStatus::Mismatch {
+            expected: expected.clone(),
+            actual,
+        }
+    })
+}
+
+#[cfg(test)]
+mod checksum_tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn manifest() -> AocResult<()> {
+        let dir = env::temp_dir().join(format!("aoc_checksum_tests_{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let input = dir.join("06_input.txt");
+        let result = (|| -> AocResult<()> {
+            assert_eq!(check_checksum(&input, "1")?, ChecksumStatus::Unrecorded);
+            record_checksum(&input, "3,4,3,1,2\n")?;
+            record_checksum(&dir.join("01_input.txt.gz"), "1\n")?;
+            assert_eq!(
+                check_checksum(&input, "3,4,3,1,2\n")?,
+                ChecksumStatus::Matches
+            );
+            assert_eq!(
+                check_checksum(&dir.join("06_input.txt.gz"), "3,4,3,1,2\n")?,
+                ChecksumStatus::Matches
+            );
+            assert!(matches!(
+                check_checksum(&input, "3,4,3")?,
+                ChecksumStatus::Mismatch { .. }
+            ));
+            let manifest = fs::read_to_string(dir.join(MANIFEST))?;
+            assert_eq!(manifest.lines().count(), 2);
+            assert!(manifest.lines().next().unwrap().ends_with("  01_input.txt"));
+
+            // Re-recording replaces the entry.
+            record_checksum(&input, "3,4,3")?;
+            assert_eq!(check_checksum(&input, "3,4,3")?, ChecksumStatus::Matches);
+
+            fs::write(dir.join(MANIFEST), "nonsense\n")?;
+            assert!(check_checksum(&input, "").is_err());
+            Ok(())
+        })();
+        fs::remove_dir_all(&dir)?;
+        result
+    }
+}
@@ -0,0 +1,199 @@
+use crate::errors::AocResult;
+use crate::io::get_day_file;
+use crate::log::{self, Level};
+use crate::timing;
+use crate::{ensure, failure};
+use std::collections::BTreeMap;
+use std::env;
+
+/// Parsed command line arguments. Every binary understands
+///
+/// - `--input PATH` (or a lone positional PATH): the input file, "-" for stdin
+/// - `--test`: use the day's example input from `data/` instead of the real one
+/// - `--part N`: only solve part N
+/// - `--verbose`: print diagnostics to stderr, as if `AOC_VERBOSE` were set (see
+///   `aoc_util::log`)
+/// - `--timings`: print how long each part and its phases took, as if `AOC_TIMINGS` were
+///   set (see `aoc_util::timing`)
+///
+/// plus whatever binary-specific flags and options it asks for. Options may be given
+/// as `--name VALUE` or `--name=VALUE`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Args {
+    pub positional: Vec<String>,
+    pub input: Option<String>,
+    pub test: bool,
+    pub part: Option<u32>,
+    pub verbose: bool,
+    pub timings: bool,
+    flags: Vec<String>,
+    options: BTreeMap<String, String>,
+}
+
+impl Args {
+    /// Parses the process's arguments, accepting the extra boolean `flags` and
+    /// valued `options` (both spelled with their leading "--"). `--verbose` and
+    /// `--timings` take effect immediately.
+    pub fn from_env(flags: &[&str], options: &[&str]) -> AocResult<Self> {
+        let args = Self::parse(env::args().skip(1), flags, options)?;
+        if args.verbose {
+            log::set_level(Level::Info);
+        }
+        if args.timings {
+            timing::set_enabled();
+        }
+        Ok(args)
+    }
+
+    pub fn parse<I>(args: I, flags: &[&str], options: &[&str]) -> AocResult<Self>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg == "-" || !arg.starts_with("--") {
+                parsed.positional.push(arg);
+                continue;
+            }
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (arg.as_str(), None),
+            };
+            let takes_value =
+                matches!(name, "--input" | "--part") || options.contains(&name);
+            let is_flag = matches!(name, "--test" | "--verbose" | "--timings")
+                || flags.contains(&name);
+            if is_flag {
+                ensure!(inline_value.is_none(), "{name} doesn't take a value");
+                match name {
+                    "--test" => parsed.test = true,
+                    "--verbose" => parsed.verbose = true,
+                    "--timings" => parsed.timings = true,
+                    _ => parsed.flags.push(name.to_string()),
+                }
+                continue;
+            }
+            if !takes_value {
+                let known: Vec<&str> =
+                    ["--input", "--test", "--part", "--verbose", "--timings"]
+                        .into_iter()
+                        .chain(flags.iter().chain(options).copied())
+                        .collect();
+                failure!(
+                    "Unknown option {name}; expected one of {}",
+                    known.join(", ")
+                );
+            }
+            let value = match inline_value {
+                Some(value) => value,
+                None => args.next().ok_or(format!("{name} needs a value"))?,
+            };
+            match name {
+                "--input" => parsed.input = Some(value),
+                "--part" => {
+                    let part = value
+                        .parse()
+                        .ok()
+                        .filter(|p| (1..=2).contains(p))
+                        .ok_or(format!("Invalid part {value:?}: expected 1 or 2"))?;
+                    parsed.part = Some(part);
+                }
+                _ => {
+                    parsed.options.insert(name.to_string(), value);
+                }
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// Whether the binary-specific flag `name` was given.
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|f| f == name)
+    }
+
+    /// The value of the binary-specific option `name`, if given.
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    /// Whether `part` should be solved, given `--part`.
+    pub fn wants_part(&self, part: u32) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+
+    /// The input path given by `--input` or the positional arguments, if any. At most
+    /// one may be given, and not together with `--test`.
+    pub fn explicit_input(&self) -> AocResult<Option<&str>> {
+        let path = match (self.input.as_deref(), self.positional.as_slice()) {
+            (input, []) => input,
+            (None, [path]) => Some(path.as_str()),
+            _ => failure!("Expected one input, got {:?}", self.positional),
+        };
+        if path.is_some() && self.test {
+            failure!("--test can't be combined with an input file");
+        }
+        Ok(path)
+    }
+
+    /// The input file for `day`: the explicit input if there is one, else the day's
+    /// example or real input under `data/`.
+    pub fn input_path(&self, day: u32) -> AocResult<String> {
+        Ok(match self.explicit_input()? {
+            Some(path) => path.to_string(),
+            None => get_day_file(day, if self.test { "test" } else { "input" }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod cli_tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> AocResult<Args> {
+        Args::parse(
+            args.iter().map(|s| s.to_string()),
+            &["--dump"],
+            &["--output"],
+        )
+    }
+
+    #[test]
+    fn parsing() -> AocResult<()> {
+        let args = parse(&["in.txt"])?;
+        assert_eq!(args.positional, ["in.txt"]);
+        assert_eq!(args.input_path(3)?, "in.txt");
+        assert!(args.wants_part(1) && args.wants_part(2));
+
+        let args = parse(&["--test", "--part", "2", "--verbose", "--timings", "--dump"])?;
+        assert!(args.test && args.verbose && args.timings && args.flag("--dump"));
+        assert!(!args.flag("--other"));
+        assert_eq!(args.part, Some(2));
+        assert!(!args.wants_part(1) && args.wants_part(2));
+        assert_eq!(args.input_path(3)?, get_day_file(3, "test"));
+
+        let args = parse(&["--input=-", "--output", "json", "--part=1"])?;
+        assert_eq!(args.input_path(3)?, "-");
+        assert_eq!(args.option("--output"), Some("json"));
+        assert_eq!(args.option("--nope"), None);
+        assert_eq!(args.part, Some(1));
+
+        assert_eq!(parse(&[])?.input_path(25)?, get_day_file(25, "input"));
+        assert_eq!(parse(&["-"])?.input_path(1)?, "-");
+
+        for bad in [
+            &["--nope"][..],
+            &["--part", "3"],
+            &["--part", "x"],
+            &["--part"],
+            &["--output"],
+            &["--test=1"],
+        ] {
+            assert!(parse(bad).is_err(), "{bad:?}");
+        }
+        for bad in [&["a", "b"][..], &["--input", "a", "b"], &["--test", "a"]] {
+            assert!(parse(bad)?.input_path(1).is_err(), "{bad:?}");
+        }
+        Ok(())
+    }
+}
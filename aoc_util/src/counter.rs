@@ -0,0 +1,165 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::{self, HashMap};
+use std::hash::Hash;
+use std::ops::{AddAssign, Sub, SubAssign};
+
+/// A multiset: how many times each item has been added. Items with a count of zero
+/// aren't stored.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Counter<T: Hash + Eq> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T: Hash + Eq> Default for Counter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Hash + Eq> Counter<T> {
+    pub fn new() -> Self {
+        Self {
+            counts: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, item: T) {
+        self.add_n(item, 1);
+    }
+
+    pub fn add_n(&mut self, item: T, n: usize) {
+        if n > 0 {
+            *self.counts.entry(item).or_insert(0) += n;
+        }
+    }
+
+    pub fn get(&self, item: &T) -> usize {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    /// The number of distinct items.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// The sum of all the counts.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Each distinct item and its count, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.counts.iter().map(|(k, &v)| (k, v))
+    }
+
+    /// Items from most to least common. The order of equally common items is arbitrary.
+    pub fn most_common(&self) -> Vec<(&T, usize)> {
+        let mut out: Vec<_> = self.iter().collect();
+        out.sort_by_key(|&(_, v)| Reverse(v));
+        out
+    }
+
+    /// A most common item, or None if `self` is empty.
+    pub fn max_by_count(&self) -> Option<(&T, usize)> {
+        self.iter().max_by_key(|&(_, v)| v)
+    }
+
+    /// A least common item, or None if `self` is empty.
+    pub fn min_by_count(&self) -> Option<(&T, usize)> {
+        self.iter().min_by_key(|&(_, v)| v)
+    }
+}
+
+impl<T: Hash + Eq> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut out = Self::new();
+        out.extend(iter);
+        out
+    }
+}
+
+impl<T: Hash + Eq> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.add(item);
+        }
+    }
+}
+
+impl<T: Hash + Eq> IntoIterator for Counter<T> {
+    type Item = (T, usize);
+    type IntoIter = hash_map::IntoIter<T, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+impl<T: Hash + Eq> AddAssign for Counter<T> {
+    fn add_assign(&mut self, other: Self) {
+        for (item, n) in other {
+            self.add_n(item, n);
+        }
+    }
+}
+
+/// Subtracts counts, dropping items whose count would go to zero or below.
+impl<T: Hash + Eq> SubAssign for Counter<T> {
+    fn sub_assign(&mut self, other: Self) {
+        for (item, n) in other {
+            if let hash_map::Entry::Occupied(mut e) = self.counts.entry(item) {
+                if *e.get() > n {
+                    *e.get_mut() -= n;
+                } else {
+                    e.remove();
+                }
+            }
+        }
+    }
+}
+
+impl<T: Hash + Eq> Sub for Counter<T> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self {
+        self -= other;
+        self
+    }
+}
+
+#[cfg(test)]
+mod counter_tests {
+    use super::*;
+
+    #[test]
+    fn counts() {
+        let mut c: Counter<char> = "abracadabra".chars().collect();
+        assert_eq!(c.get(&'a'), 5);
+        assert_eq!(c.get(&'z'), 0);
+        assert_eq!(c.len(), 5);
+        assert_eq!(c.total(), 11);
+        assert_eq!(c.max_by_count(), Some((&'a', 5)));
+        assert_eq!(c.min_by_count().map(|(_, n)| n), Some(1));
+        assert_eq!(c.most_common()[..1], [(&'a', 5)]);
+        c.add_n('z', 0);
+        assert_eq!(c.len(), 5);
+        c.add('z');
+        assert_eq!(c.get(&'z'), 1);
+        assert!(Counter::<u8>::new().max_by_count().is_none());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a: Counter<u8> = [1, 1, 2].into_iter().collect();
+        let b: Counter<u8> = [1, 3].into_iter().collect();
+        let mut sum = a.clone();
+        sum += b.clone();
+        assert_eq!((sum.get(&1), sum.get(&2), sum.get(&3)), (3, 1, 1));
+        let diff = a - b;
+        assert_eq!(diff, [1, 2].into_iter().collect());
+    }
+}
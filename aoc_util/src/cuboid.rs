@@ -1,13 +1,21 @@
-use crate::errors::{failure, AocResult};
+use crate::errors::AocResult;
+use crate::fasthash::FastSet;
+use crate::parse::ints;
+use crate::{ensure, failure};
 
 use std::cmp::{max, min};
-use std::collections::HashSet;
 use std::error;
 use std::fmt;
-use std::num::ParseIntError;
 use std::slice::Iter;
 use std::str::FromStr;
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
 #[derive(Debug, Clone, Eq, Ord, PartialOrd, PartialEq)]
 pub struct Cuboid {
     x0: i64,
@@ -23,32 +31,9 @@ impl FromStr for Cuboid {
     type Err = Box<dyn error::Error>;
 
     fn from_str(s: &str) -> AocResult<Self> {
-        let (mut x0, mut x1, mut y0, mut y1, mut z0, mut z1) = (0, 0, 0, 0, 0, 0);
-
-        for (prefix, c0, c1, has_suffix) in [
-            ("x=", &mut x0, &mut x1, true),
-            ("y=", &mut y0, &mut y1, true),
-            ("z=", &mut z0, &mut z1, false),
-        ] {
-            let start =
-                s.find(prefix).ok_or(format!("No prefix \"{}\"?", prefix))? + prefix.len();
-            let end = if has_suffix {
-                start + s[start..].find(',').ok_or("No suffix \",\"?")?
-            } else {
-                s.len()
-            };
-            let slice = &s[start..end];
-            let c0_c1: Vec<i64> = slice
-                .split("..")
-                .map(|s| s.parse::<i64>())
-                .collect::<Result<_, ParseIntError>>()?;
-            if c0_c1.len() != 2 {
-                return failure("Bad pair length");
-            }
-            *c0 = c0_c1[0];
-            *c1 = c0_c1[1];
-        }
-
+        let [x0, x1, y0, y1, z0, z1] = ints(s)[..] else {
+            failure!("Expected x, y and z ranges, got {s:?}");
+        };
         Cuboid::new(x0, x1, y0, y1, z0, z1)
     }
 }
@@ -66,7 +51,7 @@ impl fmt::Display for Cuboid {
 impl Cuboid {
     pub fn new(x0: i64, x1: i64, y0: i64, y1: i64, z0: i64, z1: i64) -> AocResult<Self> {
         if x0 > x1 || y0 > y1 || z0 > z1 {
-            return failure("Invalid cuboid: require coord0 <= coord1");
+            failure!("Invalid cuboid: require coord0 <= coord1");
         }
         Ok(Self {
             x0,
@@ -77,6 +62,13 @@ impl Cuboid {
             z1,
         })
     }
+
+    pub fn contains_point(&self, x: i64, y: i64, z: i64) -> bool {
+        (self.x0..=self.x1).contains(&x)
+            && (self.y0..=self.y1).contains(&y)
+            && (self.z0..=self.z1).contains(&z)
+    }
+
     pub fn contains(&self, other: &Cuboid) -> bool {
         self.x0 <= other.x0
             && self.x1 >= other.x1
@@ -100,28 +92,29 @@ impl Cuboid {
         }
     }
 
-    pub fn get_coord(&self, i: i64) -> i64 {
-        match i {
-            0 => self.x0,
-            1 => self.x1,
-            2 => self.y0,
-            3 => self.y1,
-            4 => self.z0,
-            5 => self.z1,
-            _ => panic!("Invalid coordinate {i}"),
+    /// The inclusive `(min, max)` extent of `self` along `axis`.
+    pub fn bounds(&self, axis: Axis) -> (i64, i64) {
+        match axis {
+            Axis::X => (self.x0, self.x1),
+            Axis::Y => (self.y0, self.y1),
+            Axis::Z => (self.z0, self.z1),
         }
     }
 
-    pub fn set_coord(&mut self, i: i64, value: i64) {
-        match i {
-            0 => self.x0 = value,
-            1 => self.x1 = value,
-            2 => self.y0 = value,
-            3 => self.y1 = value,
-            4 => self.z0 = value,
-            5 => self.z1 = value,
-            _ => panic!("Bad coordinate index {i}"),
-        }
+    /// Sets the extent of `self` along `axis`, leaving `self` unchanged if `lo > hi`.
+    pub fn set_bounds(&mut self, axis: Axis, lo: i64, hi: i64) -> AocResult<()> {
+        ensure!(
+            lo <= hi,
+            "Invalid {axis:?} bounds {lo}..{hi}: require lo <= hi"
+        );
+        let (c0, c1) = match axis {
+            Axis::X => (&mut self.x0, &mut self.x1),
+            Axis::Y => (&mut self.y0, &mut self.y1),
+            Axis::Z => (&mut self.z0, &mut self.z1),
+        };
+        *c0 = lo;
+        *c1 = hi;
+        Ok(())
     }
 
     /// Extend `self` to `other` in at most 26 different ways. Extensions
@@ -169,7 +162,15 @@ impl Cuboid {
                 || co.4 > other.z1
                 || co.5 < other.z0)
             {
-                out.push(Cuboid::new(co.0, co.1, co.2, co.3, co.4, co.5).unwrap());
+                // The check above rules out empty ranges, so `co` is a valid cuboid.
+                out.push(Cuboid {
+                    x0: co.0,
+                    x1: co.1,
+                    y0: co.2,
+                    y1: co.3,
+                    z0: co.4,
+                    z1: co.5,
+                });
             }
         }
         debug_assert!(out.iter().all(|c| !c.intersects(self)));
@@ -237,7 +238,14 @@ impl Cuboid {
             (max(left.z0, right.z0), min(left.z1, right.z1))
         };
 
-        Some(Cuboid::new(x_seg.0, x_seg.1, y_seg.0, y_seg.1, z_seg.0, z_seg.1).unwrap())
+        Some(Cuboid {
+            x0: x_seg.0,
+            x1: x_seg.1,
+            y0: y_seg.0,
+            y1: y_seg.1,
+            z0: z_seg.0,
+            z1: z_seg.1,
+        })
     }
 
     pub fn intersects(&self, other: &Cuboid) -> bool {
@@ -273,7 +281,7 @@ impl Cuboid {
 
     pub fn split(&self) -> AocResult<[Cuboid; 8]> {
         if self.x0 == self.x1 || self.y0 == self.y1 || self.z0 == self.z1 {
-            return failure(format!("Cuboid {:?} is too small to split!", self));
+            failure!("Cuboid {:?} is too small to split!", self);
         }
         let xlen = self.x1 - self.x0;
         let ylen = self.y1 - self.y0;
@@ -367,6 +375,18 @@ mod cuboid_tests {
         Ok(())
     }
 
+    #[test]
+    fn cuboid_bounds() -> AocResult<()> {
+        let mut c = Cuboid::new(-23, 22, -17, 33, -1, 44)?;
+        assert_eq!(c.bounds(Axis::X), (-23, 22));
+        assert_eq!(c.bounds(Axis::Z), (-1, 44));
+        c.set_bounds(Axis::Y, 0, 5)?;
+        assert_eq!(c, Cuboid::new(-23, 22, 0, 5, -1, 44)?);
+        assert!(c.set_bounds(Axis::Y, 5, 0).is_err());
+        assert_eq!(c.bounds(Axis::Y), (0, 5));
+        Ok(())
+    }
+
     #[test]
     fn cuboid_split() -> AocResult<()> {
         {
@@ -550,6 +570,33 @@ impl PolyCuboid {
         }
         self.cuboids = post_delete;
     }
+
+    /// Returns the smallest cuboid containing every cuboid in `self`, or None if `self`
+    /// is empty.
+    pub fn bounding_box(&self) -> Option<Cuboid> {
+        let mut it = self.iter();
+        let mut bb = it.next()?.clone();
+        for c in it {
+            bb.x0 = min(bb.x0, c.x0);
+            bb.x1 = max(bb.x1, c.x1);
+            bb.y0 = min(bb.y0, c.y0);
+            bb.y1 = max(bb.y1, c.y1);
+            bb.z0 = min(bb.z0, c.z0);
+            bb.z1 = max(bb.z1, c.z1);
+        }
+        Some(bb)
+    }
+
+    pub fn contains_point(&self, x: i64, y: i64, z: i64) -> bool {
+        self.iter().any(|c| c.contains_point(x, y, z))
+    }
+
+    /// Returns the part of `self` that lies within `clip`.
+    pub fn clipped(&self, clip: &Cuboid) -> PolyCuboid {
+        PolyCuboid {
+            cuboids: self.iter().filter_map(|c| c.intersection(clip)).collect(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -642,22 +689,61 @@ mod polycuboid_tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn polycuboid_bounding_box() -> AocResult<()> {
+        let mut p = PolyCuboid::new();
+        assert_eq!(p.bounding_box(), None);
+        p.insert(&Cuboid::new(0, 1, -3, 1, 2, 2)?);
+        assert_eq!(p.bounding_box(), Some(Cuboid::new(0, 1, -3, 1, 2, 2)?));
+        p.insert(&Cuboid::new(-1, 0, 0, 4, 0, 1)?);
+        assert_eq!(p.bounding_box(), Some(Cuboid::new(-1, 1, -3, 4, 0, 2)?));
+        Ok(())
+    }
+
+    #[test]
+    fn polycuboid_contains_point() -> AocResult<()> {
+        let mut p = PolyCuboid::new();
+        p.insert(&Cuboid::new(0, 2, 0, 2, 0, 2)?);
+        p.delete(&Cuboid::new(1, 1, 1, 1, 1, 1)?);
+        assert!(p.contains_point(0, 0, 0));
+        assert!(p.contains_point(2, 2, 2));
+        assert!(p.contains_point(1, 1, 0));
+        assert!(!p.contains_point(1, 1, 1));
+        assert!(!p.contains_point(3, 0, 0));
+        Ok(())
+    }
+
+    #[test]
+    fn polycuboid_clipped() -> AocResult<()> {
+        let mut p = PolyCuboid::new();
+        p.insert(&Cuboid::new(0, 3, 0, 3, 0, 3)?);
+        p.insert(&Cuboid::new(10, 11, 10, 11, 10, 11)?);
+        let clip = Cuboid::new(2, 10, 2, 10, 2, 10)?;
+        let c = p.clipped(&clip);
+        assert_eq!(c.volume(), 8 + 1);
+        assert_eq!(c.bounding_box(), Some(Cuboid::new(2, 10, 2, 10, 2, 10)?));
+        assert!(c.contains_point(10, 10, 10));
+        assert!(!c.contains_point(11, 11, 11));
+        assert_eq!(p.volume(), 64 + 8);
+        Ok(())
+    }
 }
 
 #[derive(Default, Debug)]
 pub struct PolyHashCuboid {
-    voxels: HashSet<(i64, i64, i64)>,
+    voxels: FastSet<(i64, i64, i64)>,
 }
 
 impl PolyHashCuboid {
     pub fn new() -> Self {
         Self {
-            voxels: HashSet::new(),
+            voxels: FastSet::default(),
         }
     }
 
     pub fn volume(&self) -> i64 {
-        self.voxels.len().try_into().unwrap()
+        self.voxels.len() as i64
     }
 
     pub fn insert(&mut self, other: &Cuboid) {
@@ -680,3 +766,85 @@ impl PolyHashCuboid {
         }
     }
 }
+
+/// The volume that's on after applying `ops` in order, where `(true, c)` turns every
+/// point of `c` on and `(false, c)` turns it off. Rather than keeping track of the shape,
+/// this sweeps along each axis in turn, between consecutive faces of the cuboids: inside
+/// such a slab, every point is covered by the same cuboids, so the last of them decides
+/// whether the whole slab is on. That's an independent check on `PolyCuboid`.
+pub fn sweep_volume(ops: &[(bool, Cuboid)]) -> i64 {
+    let ops: Vec<(bool, &Cuboid)> = ops.iter().map(|(on, c)| (*on, c)).collect();
+    sweep(&ops, &[Axis::X, Axis::Y, Axis::Z])
+}
+
+fn sweep(ops: &[(bool, &Cuboid)], axes: &[Axis]) -> i64 {
+    let Some((&axis, rest)) = axes.split_first() else {
+        return i64::from(ops.last().is_some_and(|&(on, _)| on));
+    };
+    let mut cuts: Vec<i64> = ops
+        .iter()
+        .flat_map(|(_, c)| {
+            let (lo, hi) = c.bounds(axis);
+            [lo, hi + 1]
+        })
+        .collect();
+    cuts.sort_unstable();
+    cuts.dedup();
+
+    let mut total = 0;
+    for slab in cuts.windows(2) {
+        let (lo, hi) = (slab[0], slab[1] - 1);
+        let covering: Vec<(bool, &Cuboid)> = ops
+            .iter()
+            .filter(|(_, c)| {
+                let (c0, c1) = c.bounds(axis);
+                c0 <= lo && hi <= c1
+            })
+            .copied()
+            .collect();
+        // A slab that nothing turns on stays off.
+        if covering.iter().any(|&(on, _)| on) {
+            total += (hi - lo + 1) * sweep(&covering, rest);
+        }
+    }
+    total
+}
+
+#[cfg(test)]
+mod sweep_tests {
+    use super::*;
+
+    #[test]
+    fn sweep() -> AocResult<()> {
+        let on = |c: &Cuboid| (true, c.clone());
+        let off = |c: &Cuboid| (false, c.clone());
+        let a = Cuboid::new(10, 12, 10, 12, 10, 12)?;
+        let b = Cuboid::new(11, 13, 11, 13, 11, 13)?;
+        let c = Cuboid::new(9, 11, 9, 11, 9, 11)?;
+        let d = Cuboid::new(10, 10, 10, 10, 10, 10)?;
+        assert_eq!(sweep_volume(&[]), 0);
+        assert_eq!(sweep_volume(&[on(&a)]), 27);
+        assert_eq!(sweep_volume(&[on(&a), on(&b)]), 27 + 19);
+        assert_eq!(sweep_volume(&[on(&a), on(&b), off(&c)]), 38);
+        assert_eq!(sweep_volume(&[on(&a), on(&b), off(&c), on(&d)]), 39);
+        assert_eq!(sweep_volume(&[off(&a), on(&d)]), 1);
+        assert_eq!(sweep_volume(&[on(&d), off(&a)]), 0);
+
+        // Agrees with the other representations.
+        let ops = [on(&a), off(&d), on(&c), off(&b)];
+        let mut poly = PolyCuboid::new();
+        let mut voxels = PolyHashCuboid::new();
+        for (is_on, c) in &ops {
+            if *is_on {
+                poly.insert(c);
+                voxels.insert(c);
+            } else {
+                poly.delete(c);
+                voxels.delete(c);
+            }
+        }
+        assert_eq!(sweep_volume(&ops), poly.volume());
+        assert_eq!(sweep_volume(&ops), voxels.volume());
+        Ok(())
+    }
+}
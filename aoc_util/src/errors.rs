@@ -1,29 +1,240 @@
 use std::error;
 use std::fmt;
+use std::io;
+use std::num::{ParseIntError, TryFromIntError};
 
-#[derive(Debug, Clone)]
-pub struct AocError {
-    err: String,
+#[derive(Debug)]
+pub enum AocError {
+    Io(io::Error),
+    ParseInt(ParseIntError),
+    /// The input doesn't look the way the puzzle says it should. `line` and `column`
+    /// are 1-based, and `column` counts characters.
+    Malformed {
+        line: usize,
+        column: Option<usize>,
+        msg: String,
+    },
+    /// Valid input that the solution can't handle, e.g. a case the puzzle never exercises.
+    Unsupported(String),
+    Other(String),
 }
 
 impl AocError {
+    /// An `Other` error, as returned by `failure`.
     pub fn new<S: AsRef<str>>(err: S) -> Self {
-        AocError {
-            err: err.as_ref().to_string(),
+        AocError::Other(err.as_ref().to_string())
+    }
+
+    pub fn malformed<S: AsRef<str>>(line: usize, msg: S) -> Self {
+        AocError::Malformed {
+            line,
+            column: None,
+            msg: msg.as_ref().to_string(),
+        }
+    }
+
+    pub fn malformed_at<S: AsRef<str>>(line: usize, column: usize, msg: S) -> Self {
+        AocError::Malformed {
+            line,
+            column: Some(column),
+            msg: msg.as_ref().to_string(),
+        }
+    }
+
+    /// Recovers the structured error from an `AocResult`'s boxed one. Errors that aren't
+    /// an `AocError` or one of the types it wraps become `Other`.
+    pub fn from_boxed(err: Box<dyn error::Error>) -> Self {
+        let err = match err.downcast::<AocError>() {
+            Ok(err) => return *err,
+            Err(err) => err,
+        };
+        let err = match err.downcast::<io::Error>() {
+            Ok(err) => return AocError::Io(*err),
+            Err(err) => err,
+        };
+        match err.downcast::<ParseIntError>() {
+            Ok(err) => AocError::ParseInt(*err),
+            Err(err) => AocError::Other(err.to_string()),
         }
     }
 }
 
 impl fmt::Display for AocError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.err)
+        match self {
+            AocError::Io(e) => write!(f, "{e}"),
+            AocError::ParseInt(e) => write!(f, "{e}"),
+            AocError::Malformed {
+                line,
+                column: Some(column),
+                msg,
+            } => write!(f, "Line {line}, column {column}: {msg}"),
+            AocError::Malformed { line, msg, .. } => write!(f, "Line {line}: {msg}"),
+            AocError::Unsupported(msg) => write!(f, "Unsupported: {msg}"),
+            AocError::Other(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl error::Error for AocError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            AocError::Io(e) => Some(e),
+            AocError::ParseInt(e) => Some(e),
+            _ => None,
+        }
     }
 }
 
-impl error::Error for AocError {}
+impl From<io::Error> for AocError {
+    fn from(err: io::Error) -> Self {
+        AocError::Io(err)
+    }
+}
+
+impl From<ParseIntError> for AocError {
+    fn from(err: ParseIntError) -> Self {
+        AocError::ParseInt(err)
+    }
+}
+
+impl From<TryFromIntError> for AocError {
+    fn from(err: TryFromIntError) -> Self {
+        AocError::Other(err.to_string())
+    }
+}
 
 pub type AocResult<T> = std::result::Result<T, Box<dyn error::Error>>;
 
 pub fn failure<T, S: AsRef<str>>(err: S) -> AocResult<T> {
     Err(Box::new(AocError::new(err.as_ref())))
 }
+
+/// Returns early with a `failure`, formatting its arguments like `format!`.
+#[macro_export]
+macro_rules! failure {
+    ($($arg:tt)*) => {
+        return $crate::errors::failure(format!($($arg)*))
+    };
+}
+
+/// Returns early with a `failure!` unless `cond` holds.
+#[macro_export]
+macro_rules! ensure {
+    ($cond:expr, $($arg:tt)*) => {
+        if !$cond {
+            $crate::failure!($($arg)*);
+        }
+    };
+}
+
+/// Fails with `AocError::Malformed`.
+pub fn malformed<T, S: AsRef<str>>(line: usize, msg: S) -> AocResult<T> {
+    Err(Box::new(AocError::malformed(line, msg)))
+}
+
+/// Fails with `AocError::Malformed`, at a known column.
+pub fn malformed_at<T, S: AsRef<str>>(line: usize, column: usize, msg: S) -> AocResult<T> {
+    Err(Box::new(AocError::malformed_at(line, column, msg)))
+}
+
+/// Fails with `AocError::Unsupported`.
+pub fn unsupported<T, S: AsRef<str>>(msg: S) -> AocResult<T> {
+    Err(Box::new(AocError::Unsupported(msg.as_ref().to_string())))
+}
+
+/// Locates errors from parsing part of an input. Parsers of a single string (a line, or
+/// a block of lines) report positions relative to that string; `at_line` shifts them to
+/// where the string starts in the whole input, and gives any other error that line.
+pub trait LineContext<T> {
+    fn at_line(self, line: usize) -> AocResult<T>;
+}
+
+impl<T, E: Into<Box<dyn error::Error>>> LineContext<T> for Result<T, E> {
+    fn at_line(self, line: usize) -> AocResult<T> {
+        self.map_err(|e| {
+            let err = match AocError::from_boxed(e.into()) {
+                AocError::Malformed {
+                    line: inner,
+                    column,
+                    msg,
+                } => AocError::Malformed {
+                    line: line + inner - 1,
+                    column,
+                    msg,
+                },
+                err => AocError::malformed(line, err.to_string()),
+            };
+            err.into()
+        })
+    }
+}
+
+#[cfg(test)]
+mod errors_tests {
+    use super::*;
+
+    #[test]
+    fn kinds() {
+        let err = AocError::from_boxed(failure::<(), _>("oops").unwrap_err());
+        assert!(matches!(&err, AocError::Other(msg) if msg == "oops"));
+        assert_eq!(err.to_string(), "oops");
+
+        let err = AocError::from_boxed(malformed::<(), _>(3, "bad digit").unwrap_err());
+        assert!(matches!(err, AocError::Malformed { line: 3, .. }));
+        assert_eq!(err.to_string(), "Line 3: bad digit");
+
+        let err = AocError::from_boxed(unsupported::<(), _>("packet type 9").unwrap_err());
+        assert_eq!(err.to_string(), "Unsupported: packet type 9");
+
+        let parse = || -> AocResult<i32> { Ok("x".parse::<i32>()?) };
+        let err = AocError::from_boxed(parse().unwrap_err());
+        assert!(matches!(err, AocError::ParseInt(_)));
+        assert!(error::Error::source(&err).is_some());
+
+        let read = || -> AocResult<String> { Ok(std::fs::read_to_string("/nonexistent")?) };
+        assert!(matches!(
+            AocError::from_boxed(read().unwrap_err()),
+            AocError::Io(_)
+        ));
+
+        assert!(matches!(
+            AocError::from(u8::try_from(300).unwrap_err()),
+            AocError::Other(_)
+        ));
+        assert!(matches!(
+            AocError::from_boxed("plain string".into()),
+            AocError::Other(msg) if msg == "plain string"
+        ));
+    }
+
+    #[test]
+    fn macros() {
+        fn check(x: i32) -> AocResult<i32> {
+            ensure!(x >= 0, "{x} is negative");
+            if x > 9 {
+                failure!("{x} has {} digits", x.to_string().len());
+            }
+            Ok(x)
+        }
+        assert_eq!(check(3).unwrap(), 3);
+        assert_eq!(check(-1).unwrap_err().to_string(), "-1 is negative");
+        assert_eq!(check(123).unwrap_err().to_string(), "123 has 3 digits");
+    }
+
+    #[test]
+    fn locations() {
+        let err = malformed_at::<(), _>(2, 5, "bad digit 'x'").unwrap_err();
+        assert_eq!(err.to_string(), "Line 2, column 5: bad digit 'x'");
+
+        // Positions within a block are shifted to where it starts.
+        let err = malformed_at::<(), _>(2, 5, "bad digit 'x'").at_line(10);
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "Line 11, column 5: bad digit 'x'"
+        );
+        let err = "12a".parse::<u32>().at_line(7).unwrap_err();
+        assert_eq!(err.to_string(), "Line 7: invalid digit found in string");
+        assert_eq!(Ok::<_, AocError>(3).at_line(1).unwrap(), 3);
+    }
+}
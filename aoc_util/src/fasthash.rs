@@ -0,0 +1,108 @@
+//! Hash maps and sets for hot loops. With the `fast-hash` feature, `FastMap` and
+//! `FastSet` use `FxHasher`, the multiply-and-rotate hash from Firefox and rustc, which
+//! is much quicker than std's SipHash on small keys like integers and coordinates.
+//! Without it, they're std's maps and sets with the default hasher.
+//!
+//! `FxHasher` makes no attempt to resist collision attacks; that's fine for puzzle
+//! inputs. Build the maps with `default()` (or `FastMap::from_iter`), since `new()` only
+//! exists for the default hasher.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasherDefault, Hasher};
+
+#[cfg(feature = "fast-hash")]
+pub type FastBuildHasher = BuildHasherDefault<FxHasher>;
+#[cfg(not(feature = "fast-hash"))]
+pub type FastBuildHasher = std::collections::hash_map::RandomState;
+
+pub type FastMap<K, V> = HashMap<K, V, FastBuildHasher>;
+pub type FastSet<T> = HashSet<T, FastBuildHasher>;
+
+/// The `BuildHasher` for `FxHasher`, whatever the feature says.
+pub type FxBuildHasher = BuildHasherDefault<FxHasher>;
+
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+/// Hashes a word at a time: each word is folded into the state with a rotate, xor and
+/// multiply.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+impl FxHasher {
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            let mut word = [0; 8];
+            word.copy_from_slice(chunk);
+            self.add_to_hash(u64::from_le_bytes(word));
+        }
+        for &b in chunks.remainder() {
+            self.add_to_hash(u64::from(b));
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add_to_hash(u64::from(i));
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.add_to_hash(u64::from(i));
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.add_to_hash(u64::from(i));
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.add_to_hash(i);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+#[cfg(test)]
+mod fasthash_tests {
+    use super::*;
+    use std::hash::{BuildHasher, Hash};
+
+    fn fx_hash<T: Hash + ?Sized>(x: &T) -> u64 {
+        FxBuildHasher::default().hash_one(x)
+    }
+
+    #[test]
+    fn hashing() {
+        assert_eq!(fx_hash(&0u64), 0);
+        assert_eq!(fx_hash(&1u64), SEED);
+        assert_eq!(fx_hash(&1u8), fx_hash(&1u64));
+        assert_ne!(fx_hash(&(1i64, 2i64)), fx_hash(&(2i64, 1i64)));
+        assert_ne!(fx_hash("abcdefghi"), fx_hash("abcdefghj"));
+        assert_eq!(fx_hash(&[1u8, 2, 3]), fx_hash(&[1u8, 2, 3]));
+    }
+
+    #[test]
+    fn collections() {
+        let mut map: FastMap<(i64, i64), u32> = FastMap::default();
+        for i in 0..1000 {
+            *map.entry((i % 10, i / 100)).or_default() += 1;
+        }
+        assert_eq!(map.len(), 100);
+        assert_eq!(map[&(3, 4)], 10);
+
+        let set: FastSet<&str> = ["a", "b", "a"].into_iter().collect();
+        assert_eq!(set.len(), 2);
+    }
+}
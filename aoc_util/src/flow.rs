@@ -0,0 +1,297 @@
+use crate::ensure;
+use crate::errors::AocResult;
+use crate::intern::{Interner, NodeId};
+use std::cmp::min;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Dinic's algorithm over nodes `0..num_nodes`. Every edge is stored next to its
+/// residual (reverse) edge, so edge `e`'s partner is `e ^ 1`.
+#[derive(Clone, Debug, Default)]
+struct Dinic {
+    to: Vec<usize>,
+    cap: Vec<u64>,
+    adj: Vec<Vec<usize>>,
+    level: Vec<Option<usize>>,
+    next_edge: Vec<usize>,
+}
+
+impl Dinic {
+    fn add_node(&mut self) -> usize {
+        self.adj.push(Vec::new());
+        self.adj.len() - 1
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, capacity: u64) -> usize {
+        let e = self.to.len();
+        self.to.push(to);
+        self.cap.push(capacity);
+        self.adj[from].push(e);
+        self.to.push(from);
+        self.cap.push(0);
+        self.adj[to].push(e + 1);
+        e
+    }
+
+    /// Flow currently pushed through edge `e`, which must be a forward edge.
+    fn flow(&self, e: usize) -> u64 {
+        self.cap[e ^ 1]
+    }
+
+    /// Levels every node reachable from `source` in the residual graph. Returns whether
+    /// `sink` was reached.
+    fn bfs(&mut self, source: usize, sink: usize) -> bool {
+        self.level = vec![None; self.adj.len()];
+        self.level[source] = Some(0);
+        let mut q = VecDeque::from([source]);
+        while let Some(u) = q.pop_front() {
+            let next_level = self.level[u].map(|l| l + 1);
+            for &e in &self.adj[u] {
+                let v = self.to[e];
+                if self.cap[e] > 0 && self.level[v].is_none() {
+                    self.level[v] = next_level;
+                    q.push_back(v);
+                }
+            }
+        }
+        self.level[sink].is_some()
+    }
+
+    /// Pushes up to `limit` units of flow from `u` to `sink` along the level graph.
+    fn dfs(&mut self, u: usize, sink: usize, limit: u64) -> u64 {
+        if u == sink {
+            return limit;
+        }
+        while self.next_edge[u] < self.adj[u].len() {
+            let e = self.adj[u][self.next_edge[u]];
+            let v = self.to[e];
+            if self.cap[e] > 0 && self.level[v] == self.level[u].map(|l| l + 1) {
+                let pushed = self.dfs(v, sink, min(limit, self.cap[e]));
+                if pushed > 0 {
+                    self.cap[e] -= pushed;
+                    self.cap[e ^ 1] += pushed;
+                    return pushed;
+                }
+            }
+            self.next_edge[u] += 1;
+        }
+        0
+    }
+
+    fn max_flow(&mut self, source: usize, sink: usize) -> u64 {
+        let mut total = 0;
+        while self.bfs(source, sink) {
+            self.next_edge = vec![0; self.adj.len()];
+            loop {
+                let pushed = self.dfs(source, sink, u64::MAX);
+                if pushed == 0 {
+                    break;
+                }
+                total += pushed;
+            }
+        }
+        total
+    }
+}
+
+/// A directed flow network with named nodes.
+#[derive(Clone, Debug, Default)]
+pub struct FlowNetwork {
+    dinic: Dinic,
+    interner: Interner,
+}
+
+impl FlowNetwork {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.interner.len()
+    }
+
+    fn node_or_insert(&mut self, name: &str) -> usize {
+        let node = self.interner.intern(name).index();
+        if node == self.dinic.adj.len() {
+            self.dinic.add_node();
+        }
+        node
+    }
+
+    fn node(&self, name: &str) -> AocResult<usize> {
+        Ok(self
+            .interner
+            .get(name)
+            .ok_or(format!("No node with name {name}"))?
+            .index())
+    }
+
+    fn name_at(&self, node: usize) -> &str {
+        self.interner.name(NodeId::from_index(node))
+    }
+
+    /// Adds an edge from `a` to `b` with the given capacity, creating the nodes if
+    /// necessary. Parallel edges are allowed; their capacities add up.
+    pub fn add_edge(&mut self, a: &str, b: &str, capacity: u64) {
+        let a = self.node_or_insert(a);
+        let b = self.node_or_insert(b);
+        self.dinic.add_edge(a, b, capacity);
+    }
+
+    /// Pushes as much flow as possible from `source` to `sink` and returns the amount
+    /// pushed. The flow stays in the network, so calling this again with the same
+    /// endpoints returns 0.
+    pub fn max_flow(&mut self, source: &str, sink: &str) -> AocResult<u64> {
+        let source = self.node(source)?;
+        let sink = self.node(sink)?;
+        ensure!(source != sink, "Source and sink must differ");
+        Ok(self.dinic.max_flow(source, sink))
+    }
+
+    /// Returns `(from, to, flow)` for every edge carrying a nonzero flow.
+    pub fn flows(&self) -> Vec<(&str, &str, u64)> {
+        (0..self.dinic.to.len())
+            .step_by(2)
+            .filter(|&e| self.dinic.flow(e) > 0)
+            .map(|e| {
+                (
+                    self.name_at(self.dinic.to[e ^ 1]),
+                    self.name_at(self.dinic.to[e]),
+                    self.dinic.flow(e),
+                )
+            })
+            .collect()
+    }
+
+    /// After `max_flow`, returns the edges of a minimum cut separating `source` from the
+    /// sink: every edge leading from a node still reachable from `source` in the residual
+    /// network to one that isn't.
+    pub fn min_cut(&mut self, source: &str) -> AocResult<Vec<(&str, &str)>> {
+        let source = self.node(source)?;
+        // The sink is only used as an early exit, so pass the source to search the
+        // whole residual network.
+        self.dinic.bfs(source, source);
+        let level = &self.dinic.level;
+        Ok((0..self.dinic.to.len())
+            .step_by(2)
+            .filter_map(|e| {
+                let (u, v) = (self.dinic.to[e ^ 1], self.dinic.to[e]);
+                (level[u].is_some() && level[v].is_none())
+                    .then(|| (self.name_at(u), self.name_at(v)))
+            })
+            .collect())
+    }
+}
+
+/// Returns a maximum matching of the bipartite graph whose edges are given as
+/// `(left, right)` pairs. Left and right nodes live in separate namespaces, so the same
+/// value may appear on both sides.
+pub fn bipartite_matching<L, R>(edges: &[(L, R)]) -> Vec<(L, R)>
+where
+    L: Clone + Eq + Hash,
+    R: Clone + Eq + Hash,
+{
+    let mut dinic = Dinic::default();
+    let source = dinic.add_node();
+    let sink = dinic.add_node();
+    let mut lefts = HashMap::new();
+    let mut rights = HashMap::new();
+    let mut edge_ids = Vec::with_capacity(edges.len());
+
+    for (l, r) in edges {
+        let l_node = *lefts.entry(l).or_insert_with(|| {
+            let node = dinic.add_node();
+            dinic.add_edge(source, node, 1);
+            node
+        });
+        let r_node = *rights.entry(r).or_insert_with(|| {
+            let node = dinic.add_node();
+            dinic.add_edge(node, sink, 1);
+            node
+        });
+        edge_ids.push(dinic.add_edge(l_node, r_node, 1));
+    }
+
+    dinic.max_flow(source, sink);
+    edges
+        .iter()
+        .zip(edge_ids)
+        .filter(|&(_, e)| dinic.flow(e) > 0)
+        .map(|(edge, _)| edge.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod flow_tests {
+    use super::*;
+
+    fn clrs_network() -> FlowNetwork {
+        let mut net = FlowNetwork::new();
+        for (a, b, c) in [
+            ("s", "v1", 16),
+            ("s", "v2", 13),
+            ("v1", "v3", 12),
+            ("v2", "v1", 4),
+            ("v2", "v4", 14),
+            ("v3", "v2", 9),
+            ("v3", "t", 20),
+            ("v4", "v3", 7),
+            ("v4", "t", 4),
+        ] {
+            net.add_edge(a, b, c);
+        }
+        net
+    }
+
+    #[test]
+    fn max_flow_min_cut() -> AocResult<()> {
+        let mut net = clrs_network();
+        assert_eq!(net.max_flow("s", "t")?, 23);
+        assert_eq!(net.max_flow("s", "t")?, 0);
+
+        let into_sink: u64 = net
+            .flows()
+            .iter()
+            .filter(|(_, to, _)| *to == "t")
+            .map(|(_, _, f)| f)
+            .sum();
+        assert_eq!(into_sink, 23);
+
+        let mut cut = net.min_cut("s")?;
+        cut.sort();
+        assert_eq!(cut, vec![("v1", "v3"), ("v4", "t"), ("v4", "v3")]);
+
+        assert!(net.max_flow("s", "nope").is_err());
+        assert!(net.max_flow("s", "s").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn matching() {
+        // Each allergen is in exactly one ingredient; find the assignment.
+        let edges = [
+            ("dairy", "mxmxvkd"),
+            ("dairy", "kfcds"),
+            ("fish", "mxmxvkd"),
+            ("fish", "sqjhc"),
+            ("soy", "sqjhc"),
+            ("soy", "fvjkl"),
+            ("nuts", "fvjkl"),
+        ];
+        let mut matching = bipartite_matching(&edges);
+        matching.sort();
+        assert_eq!(
+            matching,
+            vec![
+                ("dairy", "kfcds"),
+                ("fish", "mxmxvkd"),
+                ("nuts", "fvjkl"),
+                ("soy", "sqjhc")
+            ]
+        );
+
+        // The same value on both sides.
+        assert_eq!(bipartite_matching(&[(1, 1), (1, 2), (2, 1)]).len(), 2);
+        assert!(bipartite_matching::<u8, u8>(&[]).is_empty());
+    }
+}
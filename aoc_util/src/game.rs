@@ -0,0 +1,126 @@
+//! Counting the ways a game can end, for games whose moves branch into many universes, as
+//! with the Dirac dice of 2021 day 21.
+
+use crate::errors::AocResult;
+use crate::failure;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The number of ways to reach each terminal state from `start`. `transitions` gives the
+/// states a non-terminal state can move to, each with the number of ways it can happen.
+/// The states reachable from `start` must form a DAG; a cycle is an error, since it would
+/// give infinitely many ways. Each state's transitions are only generated once, and the
+/// counts are pushed through the states in topological order, so this takes time linear
+/// in the number of reachable states and transitions.
+pub fn count_outcomes<S, T, I, P>(
+    start: S,
+    transitions: T,
+    is_terminal: P,
+) -> AocResult<HashMap<S, u64>>
+where
+    S: Clone + Eq + Hash,
+    T: Fn(&S) -> I,
+    I: IntoIterator<Item = (S, u64)>,
+    P: Fn(&S) -> bool,
+{
+    // Number the reachable states and record their transitions, finishing each state
+    // after everything it leads to (a depth-first postorder).
+    let mut ids: HashMap<S, usize> = HashMap::new();
+    let mut states: Vec<S> = Vec::new();
+    let mut edges: Vec<Vec<(usize, u64)>> = Vec::new();
+    let mut finished: Vec<bool> = Vec::new();
+    let mut postorder: Vec<usize> = Vec::new();
+
+    ids.insert(start.clone(), 0);
+    states.push(start);
+    edges.push(Vec::new());
+    finished.push(false);
+    // (State, index of the next edge to follow.)
+    let mut stack: Vec<(usize, usize)> = vec![(0, 0)];
+    let mut expanded = vec![false];
+    while let Some(&mut (u, ref mut next)) = stack.last_mut() {
+        if !expanded[u] {
+            expanded[u] = true;
+            if !is_terminal(&states[u]) {
+                let mut out = Vec::new();
+                for (s, ways) in transitions(&states[u]) {
+                    let id = *ids.entry(s.clone()).or_insert_with(|| {
+                        states.push(s);
+                        edges.push(Vec::new());
+                        finished.push(false);
+                        expanded.push(false);
+                        states.len() - 1
+                    });
+                    out.push((id, ways));
+                }
+                edges[u] = out;
+            }
+        }
+        if let Some(&(v, _)) = edges[u].get(*next) {
+            *next += 1;
+            if !expanded[v] {
+                stack.push((v, 0));
+            } else if !finished[v] {
+                failure!("The game can loop, so it has infinitely many outcomes");
+            }
+        } else {
+            finished[u] = true;
+            postorder.push(u);
+            stack.pop();
+        }
+    }
+
+    let mut ways = vec![0u64; states.len()];
+    ways[0] = 1;
+    for &u in postorder.iter().rev() {
+        for &(v, n) in &edges[u] {
+            match ways[u].checked_mul(n).and_then(|w| w.checked_add(ways[v])) {
+                Some(w) => ways[v] = w,
+                None => failure!("Too many ways to reach a state to count in a u64"),
+            }
+        }
+    }
+    Ok(states
+        .into_iter()
+        .zip(ways)
+        .filter(|(s, _)| is_terminal(s))
+        .collect())
+}
+
+#[cfg(test)]
+mod game_tests {
+    use super::*;
+
+    #[test]
+    fn outcomes() -> AocResult<()> {
+        // Climbing to step 10 with strides of 1 or 2: the Fibonacci numbers.
+        let climbs = count_outcomes(
+            0,
+            |&n: &u32| {
+                [(n + 1, 1), (n + 2, 1)]
+                    .into_iter()
+                    .filter(|&(m, _)| m <= 10)
+            },
+            |&n| n == 10,
+        )?;
+        assert_eq!(climbs, HashMap::from([(10, 89)]));
+
+        // Three coin flips, counting heads, with two ways to flip heads.
+        let flips = count_outcomes(
+            (0, 0),
+            |&(flips, heads): &(u32, u32)| {
+                [((flips + 1, heads + 1), 2), ((flips + 1, heads), 1)]
+            },
+            |&(flips, _)| flips == 3,
+        )?;
+        let by_heads: Vec<u64> = (0..=3).map(|h| flips[&(3, h)]).collect();
+        assert_eq!(by_heads, [1, 6, 12, 8]);
+
+        let start_is_terminal = count_outcomes(5, |&n: &u32| [(n + 1, 1)], |_| true)?;
+        assert_eq!(start_is_terminal, HashMap::from([(5, 1)]));
+
+        assert!(count_outcomes(0, |&n: &u32| [((n + 1) % 3, 1)], |_| false).is_err());
+        assert!(count_outcomes(0, |&n: &u32| [(n + 1, 1 << 40)], |&n| n == 2).is_err());
+        Ok(())
+    }
+}
@@ -1,5 +1,9 @@
-use crate::errors::{failure, AocResult};
-use std::collections::{HashMap, HashSet};
+use crate::errors::{malformed, AocError, AocResult};
+use crate::intern::{Interner, NodeId};
+use crate::{ensure, failure};
+use std::cmp::{min, Reverse};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::fmt::Write;
 use std::fs::File;
 use std::io::{self, BufRead};
 
@@ -7,8 +11,7 @@ use std::io::{self, BufRead};
 #[derive(Debug)]
 pub struct UnweightedUndirectedGraph {
     edges: Vec<Vec<usize>>,
-    names: Vec<String>,
-    name2node: HashMap<String, usize>,
+    interner: Interner,
 }
 
 impl UnweightedUndirectedGraph {
@@ -23,64 +26,725 @@ impl UnweightedUndirectedGraph {
     /// Node names may be any alphabetic ASCII string. Edges are represented by '-'.
     /// Edges may appear more than once, though they will only be counted once.
     ///
-    /// Note that in this format, nodes with no edges are unrepresentable. Something
-    /// to fix once an AoC problem requires it.
+    /// Note that in this format, nodes with no edges are unrepresentable. Use `add_node`
+    /// to add them after parsing.
     pub fn from_file(filename: &str) -> AocResult<Self> {
         Self::from_bufreader(&mut io::BufReader::new(File::open(filename)?))
     }
 
     pub fn from_bufreader<R: BufRead>(bufreader: R) -> AocResult<Self> {
-        let mut edgesets: Vec<HashSet<usize>> = Vec::new();
-        let mut names = Vec::new();
-        let mut name2node = HashMap::new();
+        let mut graph = Self::new();
 
-        for line in bufreader.lines() {
-            let edge_strings = line?.split('-').map(String::from).collect::<Vec<String>>();
+        for (i, line) in bufreader.lines().enumerate() {
+            let line = line?;
+            let edge_strings = line.split('-').map(String::from).collect::<Vec<String>>();
             if edge_strings.len() != 2
                 || !edge_strings
                     .iter()
                     .all(|v| !v.is_empty() && v.chars().all(|c| c.is_ascii_alphabetic()))
             {
-                return failure(format!("Malformed edge {:?} in input", edge_strings));
+                return malformed(i + 1, format!("malformed edge {line:?}"));
             }
+            graph.add_edge(&edge_strings[0], &edge_strings[1]);
+        }
+        Ok(graph)
+    }
+
+    /// Parses one edge per line, with the endpoints separated by `delimiter`. Unlike
+    /// `from_bufreader`, node names may be any non-empty token without whitespace (e.g.
+    /// "42" or "ab12"). Blank lines are skipped. Weights aren't allowed; see
+    /// `WeightedGraph::from_lines`.
+    pub fn from_lines<S, I>(lines: I, delimiter: &str) -> AocResult<Self>
+    where
+        S: AsRef<str>,
+        I: IntoIterator<Item = S>,
+    {
+        let mut graph = Self::new();
+        for (i, line) in lines.into_iter().enumerate() {
+            match parse_edge_line(line.as_ref(), delimiter, i + 1)? {
+                Some((a, b, None)) => graph.add_edge(a, b),
+                Some((_, _, Some(_))) => return malformed(i + 1, "unexpected edge weight"),
+                None => (),
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Builds a graph from `(a, b)` edge pairs. As with `add_edge`, duplicate edges are
+    /// only counted once.
+    pub fn from_edges<S, I>(edges: I) -> Self
+    where
+        S: AsRef<str>,
+        I: IntoIterator<Item = (S, S)>,
+    {
+        let mut graph = Self::new();
+        for (a, b) in edges {
+            graph.add_edge(a.as_ref(), b.as_ref());
+        }
+        graph
+    }
 
-            let mut edge_ids = [0, 0];
+    pub fn new() -> Self {
+        UnweightedUndirectedGraph {
+            edges: Vec::new(),
+            interner: Interner::new(),
+        }
+    }
+
+    /// Adds a node with no edges, if a node called `name` doesn't already exist.
+    pub fn add_node(&mut self, name: &str) {
+        self.node_or_insert(name);
+    }
+
+    /// Adds an edge between `a` and `b`, creating the nodes if necessary. Adding an edge
+    /// that already exists has no effect.
+    pub fn add_edge(&mut self, a: &str, b: &str) {
+        let a = self.node_or_insert(a);
+        let b = self.node_or_insert(b);
+        if !self.edges[a].contains(&b) {
+            self.edges[a].push(b);
+        }
+        if !self.edges[b].contains(&a) {
+            self.edges[b].push(a);
+        }
+    }
+
+    fn node_or_insert(&mut self, name: &str) -> usize {
+        let node = self.interner.intern(name).index();
+        if node == self.edges.len() {
+            self.edges.push(Vec::new());
+        }
+        node
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.interner.len()
+    }
+
+    pub fn neighbour_names(&self, node_name: &str) -> AocResult<Vec<&str>> {
+        Ok(self.neighbours(node_name)?.collect())
+    }
+
+    /// Like `neighbour_names`, but iterates over the node's adjacency list directly
+    /// instead of collecting it.
+    pub fn neighbours(&self, node_name: &str) -> AocResult<impl Iterator<Item = &str> + '_> {
+        Ok(self.edges[self.node(node_name)?]
+            .iter()
+            .map(|v| self.name_at(*v)))
+    }
+
+    /// Like `neighbours`, but works purely on ids, avoiding any string hashing. Useful
+    /// in hot loops. Panics if `id` isn't from this graph.
+    pub fn neighbours_by_id(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges[id.index()]
+            .iter()
+            .map(|&v| NodeId::from_index(v))
+    }
+
+    fn node(&self, name: &str) -> AocResult<usize> {
+        Ok(self.id(name)?.index())
+    }
+
+    /// Returns the id of the node called `name`. Ids are dense, starting from 0, and
+    /// are assigned in the order nodes were added.
+    pub fn id(&self, name: &str) -> AocResult<NodeId> {
+        Ok(self
+            .interner
+            .get(name)
+            .ok_or(format!("No node with name {name}"))?)
+    }
+
+    /// Panics if `id` isn't from this graph.
+    pub fn name(&self, id: NodeId) -> &str {
+        self.interner.name(id)
+    }
+
+    /// Iterates over all node ids, in increasing order.
+    pub fn node_ids(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.num_nodes()).map(NodeId::from_index)
+    }
+
+    fn name_at(&self, node: usize) -> &str {
+        self.interner.name(NodeId::from_index(node))
+    }
+
+    /// Iterates over the nodes reachable from `start` in breadth-first order, yielding
+    /// `(node name, distance from start)`.
+    pub fn bfs(&self, start: &str) -> AocResult<impl Iterator<Item = (&str, usize)> + '_> {
+        Ok(Bfs::new(&self.edges, &self.interner, self.node(start)?))
+    }
+
+    /// Iterates over the nodes reachable from `start` in depth-first preorder, yielding
+    /// `(node name, depth in the DFS tree)`. Neighbours are visited in insertion order.
+    pub fn dfs(&self, start: &str) -> AocResult<impl Iterator<Item = (&str, usize)> + '_> {
+        Ok(Dfs::new(&self.edges, &self.interner, self.node(start)?))
+    }
+
+    /// Renders the graph in Graphviz DOT format, e.g. for `dot -Tsvg`.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("graph {\n");
+        for u in 0..self.num_nodes() {
+            let name = self.name_at(u);
+            writeln!(out, "    {};", dot_quote(name)).unwrap();
+            for &v in self.edges[u].iter().filter(|&&v| u <= v) {
+                writeln!(
+                    out,
+                    "    {} -- {};",
+                    dot_quote(name),
+                    dot_quote(self.name_at(v))
+                )
+                .unwrap();
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Returns every edge whose removal would disconnect its endpoints.
+    pub fn bridges(&self) -> Vec<(&str, &str)> {
+        let (bridges, _) = bridges_and_articulation_points(&self.edges);
+        bridges
+            .into_iter()
+            .map(|(u, v)| (self.name_at(u), self.name_at(v)))
+            .collect()
+    }
+
+    /// Returns every node whose removal would increase the number of connected
+    /// components.
+    pub fn articulation_points(&self) -> Vec<&str> {
+        let (_, points) = bridges_and_articulation_points(&self.edges);
+        points.into_iter().map(|u| self.name_at(u)).collect()
+    }
+}
+
+impl Default for UnweightedUndirectedGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Directedness {
+    Directed,
+    Undirected,
+}
+
+/// A weighted graph in adjacency list form. Edge weights are non-negative.
+#[derive(Clone, Debug)]
+pub struct WeightedGraph {
+    directedness: Directedness,
+    /// `edges[u]` holds `(v, weight)` for every edge u -> v.
+    edges: Vec<Vec<(usize, u64)>>,
+    interner: Interner,
+}
+
+impl WeightedGraph {
+    pub fn new(directedness: Directedness) -> Self {
+        WeightedGraph {
+            directedness,
+            edges: Vec::new(),
+            interner: Interner::new(),
+        }
+    }
 
-            for (i, name) in edge_strings.into_iter().enumerate() {
-                if let Some(node) = name2node.get(&name) {
-                    edge_ids[i] = *node;
-                } else {
-                    let node_id = name2node.len();
-                    edge_ids[i] = node_id;
-                    edgesets.push(HashSet::new());
-                    names.push(name.to_owned());
-                    name2node.insert(name.to_owned(), node_id);
+    /// Parses one edge per line, in the form `a<delimiter>b` or `a<delimiter>b:weight`.
+    /// Edges without a weight get weight 1. Node names may be any non-empty token
+    /// without whitespace, and blank lines are skipped. Errors report the offending
+    /// (1-based) line number.
+    pub fn from_lines<S, I>(
+        lines: I,
+        delimiter: &str,
+        directedness: Directedness,
+    ) -> AocResult<Self>
+    where
+        S: AsRef<str>,
+        I: IntoIterator<Item = S>,
+    {
+        let mut graph = Self::new(directedness);
+        for (i, line) in lines.into_iter().enumerate() {
+            if let Some((a, b, weight)) = parse_edge_line(line.as_ref(), delimiter, i + 1)? {
+                graph.add_edge(a, b, weight.unwrap_or(1));
+            }
+        }
+        Ok(graph)
+    }
+
+    pub fn directedness(&self) -> Directedness {
+        self.directedness
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.interner.len()
+    }
+
+    fn node_or_insert(&mut self, name: &str) -> usize {
+        let node = self.interner.intern(name).index();
+        if node == self.edges.len() {
+            self.edges.push(Vec::new());
+        }
+        node
+    }
+
+    fn node(&self, name: &str) -> AocResult<usize> {
+        Ok(self.id(name)?.index())
+    }
+
+    /// Returns the id of the node called `name`. Ids are dense, starting from 0, and
+    /// are assigned in the order nodes were added.
+    pub fn id(&self, name: &str) -> AocResult<NodeId> {
+        Ok(self
+            .interner
+            .get(name)
+            .ok_or(format!("No node with name {name}"))?)
+    }
+
+    /// Panics if `id` isn't from this graph.
+    pub fn name(&self, id: NodeId) -> &str {
+        self.interner.name(id)
+    }
+
+    /// Iterates over all node ids, in increasing order.
+    pub fn node_ids(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.num_nodes()).map(NodeId::from_index)
+    }
+
+    fn name_at(&self, node: usize) -> &str {
+        self.interner.name(NodeId::from_index(node))
+    }
+
+    /// Adds an edge from `a` to `b` (and from `b` to `a` if the graph is undirected),
+    /// creating the nodes if necessary. Adding an edge that already exists replaces its
+    /// weight.
+    pub fn add_edge(&mut self, a: &str, b: &str, weight: u64) {
+        let a = self.node_or_insert(a);
+        let b = self.node_or_insert(b);
+        self.insert_half_edge(a, b, weight);
+        if self.directedness == Directedness::Undirected {
+            self.insert_half_edge(b, a, weight);
+        }
+    }
+
+    fn insert_half_edge(&mut self, from: usize, to: usize, weight: u64) {
+        if let Some(edge) = self.edges[from].iter_mut().find(|(v, _)| *v == to) {
+            edge.1 = weight;
+        } else {
+            self.edges[from].push((to, weight));
+        }
+    }
+
+    /// Returns `(neighbour name, edge weight)` for every outgoing edge of `node_name`.
+    pub fn neighbours(&self, node_name: &str) -> AocResult<Vec<(&str, u64)>> {
+        Ok(self.edges[self.node(node_name)?]
+            .iter()
+            .map(|&(v, w)| (self.name_at(v), w))
+            .collect())
+    }
+
+    /// Returns `(neighbour id, edge weight)` for every outgoing edge of `id`. Panics if
+    /// `id` isn't from this graph.
+    pub fn neighbours_by_id(&self, id: NodeId) -> impl Iterator<Item = (NodeId, u64)> + '_ {
+        self.edges[id.index()]
+            .iter()
+            .map(|&(v, w)| (NodeId::from_index(v), w))
+    }
+
+    /// Returns the shortest distance from `from` to every node reachable from it
+    /// (including `from` itself).
+    pub fn dijkstra(&self, from: &str) -> AocResult<HashMap<&str, u64>> {
+        let start = self.node(from)?;
+        let mut dist: Vec<Option<u64>> = vec![None; self.num_nodes()];
+        let mut q = BinaryHeap::new();
+        dist[start] = Some(0);
+        q.push(Reverse((0, start)));
+
+        while let Some(Reverse((d, u))) = q.pop() {
+            if dist[u].is_some_and(|best| d > best) {
+                // Stale queue entry.
+                continue;
+            }
+            for &(v, w) in &self.edges[u] {
+                let alt = d + w;
+                if dist[v].is_none_or(|best| alt < best) {
+                    dist[v] = Some(alt);
+                    q.push(Reverse((alt, v)));
                 }
             }
-            edgesets[edge_ids[0]].insert(edge_ids[1]);
-            edgesets[edge_ids[1]].insert(edge_ids[0]);
         }
-        let edges = edgesets
+
+        Ok(dist
             .into_iter()
-            .map(|s| Vec::from_iter(s.into_iter()))
-            .collect();
-        Ok(UnweightedUndirectedGraph {
-            edges,
-            name2node,
-            names,
-        })
+            .enumerate()
+            .filter_map(|(v, d)| d.map(|d| (self.name_at(v), d)))
+            .collect())
     }
 
-    pub fn neighbour_names(&self, node_name: &str) -> AocResult<Vec<&str>> {
-        let node = self
-            .name2node
-            .get(node_name)
-            .ok_or("No node with name {node_name}")?;
-        Ok(self.edges[*node]
+    /// Returns the edges `(a, b, weight)` of a minimum spanning tree, computed with
+    /// Prim's algorithm. If the graph is disconnected, a minimum spanning forest is
+    /// returned instead. Only defined for undirected graphs.
+    pub fn minimum_spanning_tree(&self) -> AocResult<Vec<(&str, &str, u64)>> {
+        ensure!(
+            self.directedness == Directedness::Undirected,
+            "Minimum spanning trees require an undirected graph"
+        );
+        let mut in_tree = vec![false; self.num_nodes()];
+        let mut out = Vec::new();
+
+        for root in 0..self.num_nodes() {
+            if in_tree[root] {
+                continue;
+            }
+            in_tree[root] = true;
+            let mut q: BinaryHeap<Reverse<(u64, usize, usize)>> = self.edges[root]
+                .iter()
+                .map(|&(v, w)| Reverse((w, root, v)))
+                .collect();
+            while let Some(Reverse((w, u, v))) = q.pop() {
+                if in_tree[v] {
+                    continue;
+                }
+                in_tree[v] = true;
+                out.push((self.name_at(u), self.name_at(v), w));
+                q.extend(
+                    self.edges[v]
+                        .iter()
+                        .filter(|(x, _)| !in_tree[*x])
+                        .map(|&(x, wx)| Reverse((wx, v, x))),
+                );
+            }
+        }
+        Ok(out)
+    }
+
+    /// See `UnweightedUndirectedGraph::bfs`. Edge weights are ignored.
+    pub fn bfs(&self, start: &str) -> AocResult<impl Iterator<Item = (&str, usize)> + '_> {
+        Ok(Bfs::new(&self.edges, &self.interner, self.node(start)?))
+    }
+
+    /// See `UnweightedUndirectedGraph::dfs`. Edge weights are ignored.
+    pub fn dfs(&self, start: &str) -> AocResult<impl Iterator<Item = (&str, usize)> + '_> {
+        Ok(Dfs::new(&self.edges, &self.interner, self.node(start)?))
+    }
+
+    /// Renders the graph in Graphviz DOT format, with edge weights as labels.
+    pub fn to_dot(&self) -> String {
+        let (header, arrow) = match self.directedness {
+            Directedness::Directed => ("digraph", "->"),
+            Directedness::Undirected => ("graph", "--"),
+        };
+        let mut out = format!("{header} {{\n");
+        for u in 0..self.num_nodes() {
+            let name = self.name_at(u);
+            writeln!(out, "    {};", dot_quote(name)).unwrap();
+            for &(v, w) in &self.edges[u] {
+                if self.directedness == Directedness::Undirected && v < u {
+                    continue;
+                }
+                writeln!(
+                    out,
+                    "    {} {arrow} {} [label=\"{w}\"];",
+                    dot_quote(name),
+                    dot_quote(self.name_at(v))
+                )
+                .unwrap();
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    fn unweighted_edges(&self) -> Vec<Vec<usize>> {
+        self.edges
             .iter()
-            .map(|v| self.names[*v].as_str())
+            .map(|e| e.iter().map(|(v, _)| *v).collect())
+            .collect()
+    }
+
+    /// Returns the strongly connected components of the graph, computed with Tarjan's
+    /// algorithm. Components are returned in reverse topological order. For undirected
+    /// graphs these are just the connected components.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<&str>> {
+        tarjan_scc(&self.unweighted_edges())
+            .into_iter()
+            .map(|c| c.into_iter().map(|u| self.name_at(u)).collect())
+            .collect()
+    }
+
+    /// See `UnweightedUndirectedGraph::bridges`. Only defined for undirected graphs.
+    pub fn bridges(&self) -> AocResult<Vec<(&str, &str)>> {
+        ensure!(
+            self.directedness == Directedness::Undirected,
+            "Bridges require an undirected graph"
+        );
+        let (bridges, _) = bridges_and_articulation_points(&self.unweighted_edges());
+        Ok(bridges
+            .into_iter()
+            .map(|(u, v)| (self.name_at(u), self.name_at(v)))
             .collect())
     }
+
+    /// See `UnweightedUndirectedGraph::articulation_points`. Only defined for undirected
+    /// graphs.
+    pub fn articulation_points(&self) -> AocResult<Vec<&str>> {
+        ensure!(
+            self.directedness == Directedness::Undirected,
+            "Articulation points require an undirected graph"
+        );
+        let (_, points) = bridges_and_articulation_points(&self.unweighted_edges());
+        Ok(points.into_iter().map(|u| self.name_at(u)).collect())
+    }
+}
+
+/// Parses `a<delimiter>b[:weight]` into `(a, b, weight)`, or None for a blank line.
+/// `line_num` is only used for error messages.
+fn parse_edge_line<'a>(
+    line: &'a str,
+    delimiter: &str,
+    line_num: usize,
+) -> AocResult<Option<(&'a str, &'a str, Option<u64>)>> {
+    if delimiter.is_empty() || delimiter.contains(':') {
+        failure!("Invalid edge delimiter {delimiter:?}");
+    }
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+    let (edge, weight) = match line.rsplit_once(':') {
+        Some((edge, weight)) => {
+            let weight = weight.trim().parse::<u64>().map_err(|e| {
+                AocError::malformed(line_num, format!("invalid weight {weight:?}: {e}"))
+            })?;
+            (edge, Some(weight))
+        }
+        None => (line, None),
+    };
+    let nodes = edge.split(delimiter).map(str::trim).collect::<Vec<_>>();
+    if nodes.len() != 2
+        || nodes
+            .iter()
+            .any(|n| n.is_empty() || n.contains(char::is_whitespace))
+    {
+        return malformed(line_num, format!("malformed edge {line:?}"));
+    }
+    Ok(Some((nodes[0], nodes[1], weight)))
+}
+
+/// Quotes a node name for use as a DOT identifier, or any text as a DOT label.
+pub(crate) fn dot_quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Lets the traversal iterators walk both weighted and unweighted adjacency lists.
+trait EdgeTarget {
+    fn target(&self) -> usize;
+}
+
+impl EdgeTarget for usize {
+    fn target(&self) -> usize {
+        *self
+    }
+}
+
+impl EdgeTarget for (usize, u64) {
+    fn target(&self) -> usize {
+        self.0
+    }
+}
+
+struct Bfs<'a, E> {
+    edges: &'a [Vec<E>],
+    interner: &'a Interner,
+    visited: Vec<bool>,
+    queue: VecDeque<(usize, usize)>,
+}
+
+impl<'a, E: EdgeTarget> Bfs<'a, E> {
+    fn new(edges: &'a [Vec<E>], interner: &'a Interner, start: usize) -> Self {
+        let mut visited = vec![false; edges.len()];
+        visited[start] = true;
+        Bfs {
+            edges,
+            interner,
+            visited,
+            queue: VecDeque::from([(start, 0)]),
+        }
+    }
+}
+
+impl<'a, E: EdgeTarget> Iterator for Bfs<'a, E> {
+    type Item = (&'a str, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (u, depth) = self.queue.pop_front()?;
+        for v in self.edges[u].iter().map(EdgeTarget::target) {
+            if !self.visited[v] {
+                self.visited[v] = true;
+                self.queue.push_back((v, depth + 1));
+            }
+        }
+        Some((self.interner.name(NodeId::from_index(u)), depth))
+    }
+}
+
+struct Dfs<'a, E> {
+    edges: &'a [Vec<E>],
+    interner: &'a Interner,
+    visited: Vec<bool>,
+    stack: Vec<(usize, usize)>,
+}
+
+impl<'a, E: EdgeTarget> Dfs<'a, E> {
+    fn new(edges: &'a [Vec<E>], interner: &'a Interner, start: usize) -> Self {
+        Dfs {
+            edges,
+            interner,
+            visited: vec![false; edges.len()],
+            stack: vec![(start, 0)],
+        }
+    }
+}
+
+impl<'a, E: EdgeTarget> Iterator for Dfs<'a, E> {
+    type Item = (&'a str, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((u, depth)) = self.stack.pop() {
+            if self.visited[u] {
+                continue;
+            }
+            self.visited[u] = true;
+            // Push in reverse so that the first neighbour is visited first.
+            for v in self.edges[u].iter().rev().map(EdgeTarget::target) {
+                if !self.visited[v] {
+                    self.stack.push((v, depth + 1));
+                }
+            }
+            return Some((self.interner.name(NodeId::from_index(u)), depth));
+        }
+        None
+    }
+}
+
+const UNVISITED: usize = usize::MAX;
+
+/// Tarjan's strongly connected components algorithm over an adjacency list. Iterative,
+/// so that large graphs can't overflow the stack.
+fn tarjan_scc(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let n = edges.len();
+    let mut index = vec![UNVISITED; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut next_index = 0;
+    let mut out = Vec::new();
+
+    for root in 0..n {
+        if index[root] != UNVISITED {
+            continue;
+        }
+        // (node, index of the next edge to explore)
+        let mut call_stack = vec![(root, 0)];
+        index[root] = next_index;
+        low[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some((u, next_edge)) = call_stack.last_mut() {
+            let u = *u;
+            if let Some(&v) = edges[u].get(*next_edge) {
+                *next_edge += 1;
+                if index[v] == UNVISITED {
+                    index[v] = next_index;
+                    low[v] = next_index;
+                    next_index += 1;
+                    stack.push(v);
+                    on_stack[v] = true;
+                    call_stack.push((v, 0));
+                } else if on_stack[v] {
+                    low[u] = min(low[u], index[v]);
+                }
+                continue;
+            }
+
+            call_stack.pop();
+            if let Some((parent, _)) = call_stack.last() {
+                low[*parent] = min(low[*parent], low[u]);
+            }
+            if low[u] == index[u] {
+                let mut component = Vec::new();
+                while let Some(v) = stack.pop() {
+                    on_stack[v] = false;
+                    component.push(v);
+                    if v == u {
+                        break;
+                    }
+                }
+                out.push(component);
+            }
+        }
+    }
+    out
+}
+
+/// Returns (bridges, articulation points) of an undirected graph without parallel edges,
+/// using the low-link DFS.
+fn bridges_and_articulation_points(
+    edges: &[Vec<usize>],
+) -> (Vec<(usize, usize)>, Vec<usize>) {
+    let n = edges.len();
+    let mut disc = vec![UNVISITED; n];
+    let mut low = vec![0; n];
+    let mut is_articulation = vec![false; n];
+    let mut bridges = Vec::new();
+    let mut time = 0;
+
+    for root in 0..n {
+        if disc[root] != UNVISITED {
+            continue;
+        }
+        disc[root] = time;
+        low[root] = time;
+        time += 1;
+        let mut root_children = 0;
+        // (node, parent, index of the next edge to explore)
+        let mut call_stack = vec![(root, UNVISITED, 0)];
+
+        while let Some((u, parent, next_edge)) = call_stack.last_mut() {
+            let (u, parent) = (*u, *parent);
+            if let Some(&v) = edges[u].get(*next_edge) {
+                *next_edge += 1;
+                if disc[v] == UNVISITED {
+                    disc[v] = time;
+                    low[v] = time;
+                    time += 1;
+                    if u == root {
+                        root_children += 1;
+                    }
+                    call_stack.push((v, u, 0));
+                } else if v != parent {
+                    low[u] = min(low[u], disc[v]);
+                }
+                continue;
+            }
+
+            call_stack.pop();
+            if parent != UNVISITED {
+                low[parent] = min(low[parent], low[u]);
+                if low[u] > disc[parent] {
+                    bridges.push((parent, u));
+                }
+                if parent != root && low[u] >= disc[parent] {
+                    is_articulation[parent] = true;
+                }
+            }
+        }
+        if root_children > 1 {
+            is_articulation[root] = true;
+        }
+    }
+
+    let points = (0..n).filter(|&u| is_articulation[u]).collect();
+    (bridges, points)
 }
 
 #[cfg(test)]
@@ -132,4 +796,252 @@ b-
         }
         Ok(())
     }
+
+    #[test]
+    fn graph_builder() -> AocResult<()> {
+        let mut g = UnweightedUndirectedGraph::new();
+        g.add_node("lonely");
+        g.add_edge("start", "A");
+        g.add_edge("A", "start");
+        g.add_edge("A", "42");
+        assert_eq!(g.num_nodes(), 4);
+        assert!(g.neighbour_names("lonely")?.is_empty());
+        assert_eq!(g.neighbour_names("start")?, vec!["A"]);
+        assert_eq!(g.neighbour_names("A")?, vec!["start", "42"]);
+
+        let g2 = UnweightedUndirectedGraph::from_edges([("x", "y"), ("y", "z"), ("y", "x")]);
+        assert_eq!(g2.num_nodes(), 3);
+        assert_eq!(g2.neighbour_names("y")?, vec!["x", "z"]);
+        assert!(g2.neighbour_names("w").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn graph_traversals() -> AocResult<()> {
+        //   a - b - d - f
+        //    \     /
+        //      c - e
+        let g = UnweightedUndirectedGraph::from_edges([
+            ("a", "b"),
+            ("a", "c"),
+            ("b", "d"),
+            ("c", "e"),
+            ("e", "d"),
+            ("d", "f"),
+        ]);
+        assert_eq!(
+            g.bfs("a")?.collect::<Vec<_>>(),
+            vec![("a", 0), ("b", 1), ("c", 1), ("d", 2), ("e", 2), ("f", 3)]
+        );
+        assert_eq!(
+            g.dfs("a")?.collect::<Vec<_>>(),
+            vec![("a", 0), ("b", 1), ("d", 2), ("e", 3), ("c", 4), ("f", 3)]
+        );
+        assert_eq!(
+            g.bfs("a")?
+                .take_while(|&(_, d)| d <= 1)
+                .map(|(n, _)| n)
+                .collect::<Vec<_>>(),
+            vec!["a", "b", "c"]
+        );
+        assert!(g.bfs("z").is_err());
+
+        let wg = weighted_graph();
+        assert_eq!(wg.bfs("e")?.collect::<Vec<_>>(), vec![("e", 0), ("f", 1)]);
+        assert_eq!(wg.dfs("a")?.count(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn graph_ids() -> AocResult<()> {
+        let g = UnweightedUndirectedGraph::from_edges([("a", "b"), ("a", "c")]);
+        let a = g.id("a")?;
+        assert_eq!(g.name(a), "a");
+        assert_eq!(
+            g.node_ids().map(|v| g.name(v)).collect::<Vec<_>>(),
+            vec!["a", "b", "c"]
+        );
+        assert_eq!(
+            g.neighbours_by_id(a).map(|v| g.name(v)).collect::<Vec<_>>(),
+            vec!["b", "c"]
+        );
+        assert!(g.id("d").is_err());
+
+        let wg = weighted_graph();
+        let e = wg.id("e")?;
+        assert_eq!(
+            wg.neighbours_by_id(e).collect::<Vec<_>>(),
+            vec![(wg.id("f")?, 3)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn graph_from_lines() -> AocResult<()> {
+        let g = UnweightedUndirectedGraph::from_lines(["1-2", "", "2 - ab12"], "-")?;
+        assert_eq!(g.neighbour_names("2")?, vec!["1", "ab12"]);
+
+        let wg = WeightedGraph::from_lines(
+            ["AA -> BB:3", "BB -> CC", "CC -> AA:10"],
+            "->",
+            Directedness::Directed,
+        )?;
+        assert_eq!(wg.neighbours("AA")?, vec![("BB", 3)]);
+        assert_eq!(wg.neighbours("BB")?, vec![("CC", 1)]);
+        assert_eq!(wg.dijkstra("CC")?["BB"], 13);
+
+        for (lines, err) in [
+            (vec!["a-b", "a-b-c"], "Line 2: malformed edge \"a-b-c\""),
+            (vec!["a-b:x"], "Line 1: invalid weight \"x\""),
+            (vec!["", "", "a-"], "Line 3: malformed edge \"a-\""),
+            (vec!["a b-c"], "Line 1: malformed edge \"a b-c\""),
+        ] {
+            let e = WeightedGraph::from_lines(lines, "-", Directedness::Undirected)
+                .unwrap_err()
+                .to_string();
+            assert!(e.starts_with(err), "{e}");
+        }
+        assert!(UnweightedUndirectedGraph::from_lines(["a-b:3"], "-").is_err());
+        assert!(UnweightedUndirectedGraph::from_lines(["a:b"], ":").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn graph_to_dot() {
+        let mut g = UnweightedUndirectedGraph::from_edges([("a", "b"), ("b", "c")]);
+        g.add_node("q\"uote");
+        assert_eq!(
+            g.to_dot(),
+            "graph {\n    \"a\";\n    \"a\" -- \"b\";\n    \"b\";\n    \"b\" -- \"c\";\n    \
+             \"c\";\n    \"q\\\"uote\";\n}\n"
+        );
+
+        let mut wg = WeightedGraph::new(Directedness::Directed);
+        wg.add_edge("x", "y", 3);
+        wg.add_edge("y", "x", 4);
+        assert_eq!(
+            wg.to_dot(),
+            "digraph {\n    \"x\";\n    \"x\" -> \"y\" [label=\"3\"];\n    \"y\";\n    \
+             \"y\" -> \"x\" [label=\"4\"];\n}\n"
+        );
+        assert_eq!(weighted_graph().to_dot().matches(" -- ").count(), 6);
+    }
+
+    fn weighted_graph() -> WeightedGraph {
+        let mut g = WeightedGraph::new(Directedness::Undirected);
+        g.add_edge("a", "b", 4);
+        g.add_edge("a", "c", 1);
+        g.add_edge("c", "b", 2);
+        g.add_edge("b", "d", 5);
+        g.add_edge("c", "d", 8);
+        g.add_edge("e", "f", 3);
+        g
+    }
+
+    #[test]
+    fn weighted_graph_dijkstra() -> AocResult<()> {
+        let g = weighted_graph();
+        let dist = g.dijkstra("a")?;
+        assert_eq!(dist.len(), 4);
+        assert_eq!(dist["a"], 0);
+        assert_eq!(dist["b"], 3);
+        assert_eq!(dist["c"], 1);
+        assert_eq!(dist["d"], 8);
+        assert!(g.dijkstra("z").is_err());
+
+        let mut dg = WeightedGraph::new(Directedness::Directed);
+        dg.add_edge("a", "b", 1);
+        dg.add_edge("b", "c", 1);
+        dg.add_edge("c", "a", 1);
+        dg.add_edge("a", "c", 5);
+        assert_eq!(dg.dijkstra("a")?["c"], 2);
+        assert_eq!(dg.dijkstra("c")?["b"], 2);
+        Ok(())
+    }
+
+    #[test]
+    fn weighted_graph_mst() -> AocResult<()> {
+        let g = weighted_graph();
+        let mut mst = g
+            .minimum_spanning_tree()?
+            .into_iter()
+            .map(|(a, b, w)| if a < b { (a, b, w) } else { (b, a, w) })
+            .collect::<Vec<_>>();
+        mst.sort();
+        assert_eq!(
+            mst,
+            vec![("a", "c", 1), ("b", "c", 2), ("b", "d", 5), ("e", "f", 3)]
+        );
+
+        let mut dg = WeightedGraph::new(Directedness::Directed);
+        dg.add_edge("a", "b", 1);
+        assert!(dg.minimum_spanning_tree().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn strongly_connected_components() -> AocResult<()> {
+        let mut g = WeightedGraph::new(Directedness::Directed);
+        for (a, b) in [
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("c", "d"),
+            ("d", "e"),
+            ("e", "d"),
+            ("f", "e"),
+        ] {
+            g.add_edge(a, b, 1);
+        }
+        let mut sccs = g
+            .strongly_connected_components()
+            .into_iter()
+            .map(|mut c| {
+                c.sort();
+                c
+            })
+            .collect::<Vec<_>>();
+        // Reverse topological order: {d, e} has no outgoing edges, so it comes first.
+        assert_eq!(sccs[0], vec!["d", "e"]);
+        sccs.sort();
+        assert_eq!(sccs, vec![vec!["a", "b", "c"], vec!["d", "e"], vec!["f"]]);
+        Ok(())
+    }
+
+    #[test]
+    fn bridges_and_articulation_points() -> AocResult<()> {
+        // Two triangles joined by the bridge c-d, plus a pendant node g.
+        let g = UnweightedUndirectedGraph::from_edges([
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("c", "d"),
+            ("d", "e"),
+            ("e", "f"),
+            ("f", "d"),
+            ("f", "g"),
+        ]);
+        let mut bridges = g
+            .bridges()
+            .into_iter()
+            .map(|(a, b)| if a < b { (a, b) } else { (b, a) })
+            .collect::<Vec<_>>();
+        bridges.sort();
+        assert_eq!(bridges, vec![("c", "d"), ("f", "g")]);
+        let mut points = g.articulation_points();
+        points.sort();
+        assert_eq!(points, vec!["c", "d", "f"]);
+
+        let cycle =
+            UnweightedUndirectedGraph::from_edges([("a", "b"), ("b", "c"), ("c", "a")]);
+        assert!(cycle.bridges().is_empty());
+        assert!(cycle.articulation_points().is_empty());
+
+        let mut wg = WeightedGraph::new(Directedness::Undirected);
+        wg.add_edge("a", "b", 7);
+        wg.add_edge("b", "c", 7);
+        assert_eq!(wg.articulation_points()?, vec!["b"]);
+        assert_eq!(wg.bridges()?.len(), 2);
+        Ok(())
+    }
 }
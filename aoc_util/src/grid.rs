@@ -1,5 +1,8 @@
-use crate::errors::{failure, AocError, AocResult};
+use crate::bucket::BucketQueue;
+use crate::errors::{malformed, AocError, AocResult};
 use crate::point::Point;
+use crate::search::SearchStats;
+use crate::{ensure, failure};
 
 use std::cmp::{Ordering, Reverse};
 use std::collections::{BinaryHeap, VecDeque};
@@ -38,6 +41,15 @@ pub enum NeighbourPattern {
     Compass8,
 }
 
+/// A side of the grid: North is row 0, and West is column 0.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
 /// Indexed by (row, col) like:
 /// 0,0  0,1  0,2 ...
 /// 1,0  1,1  1,2 ...
@@ -45,23 +57,31 @@ pub enum NeighbourPattern {
 ///  .    .    .
 ///  .    .    .
 impl Grid {
-    // TODO: update to use a an iterable of AsRef<str> instead of `filename`.
     pub fn from_digit_matrix_file(filename: &str) -> AocResult<Self> {
         let file = File::open(filename)?;
         let lines: Vec<String> = io::BufReader::new(file)
             .lines()
             .collect::<io::Result<_>>()?;
-        let num_rows = lines.len();
-        let num_cols = lines.get(0).ok_or("First row empty?")?.len();
-        if !lines.iter().all(|l| l.len() == num_cols) {
-            return failure("Not all rows have the same number of columns.");
-        }
+        Self::from_digit_matrix(lines)
+    }
+
+    /// Parses rows of decimal digits, one row per line.
+    pub fn from_digit_matrix<S, I>(lines: I) -> AocResult<Self>
+    where
+        S: AsRef<str>,
+        I: IntoIterator<Item = S>,
+    {
+        let lines: Vec<S> = lines.into_iter().collect();
+        let (num_rows, num_cols) = matrix_shape(&lines)?;
         let cells: Vec<u8> = lines
             .iter()
-            .flat_map(|s| {
-                s.chars().map(|c| {
-                    u8::try_from(c.to_digit(10).ok_or("Bad char").map_err(AocError::new)?)
-                        .map_err(|e| AocError::new(e.to_string()))
+            .enumerate()
+            .flat_map(|(row, s)| {
+                s.as_ref().chars().enumerate().map(move |(col, c)| {
+                    let digit = c.to_digit(10).ok_or_else(|| {
+                        AocError::malformed_at(row + 1, col + 1, format!("bad digit {c:?}"))
+                    })?;
+                    Ok::<_, AocError>(u8::try_from(digit)?)
                 })
             })
             .collect::<Result<Vec<_>, _>>()?;
@@ -78,16 +98,17 @@ impl Grid {
     where
         F: Fn(char) -> Option<u8>,
     {
-        let num_rows = lines.len();
-        let num_cols = lines.get(0).ok_or("First row empty?")?.len();
-        if !lines.iter().all(|l| l.len() == num_cols) {
-            return failure("Not all rows have the same number of columns.");
-        }
+        let (num_rows, num_cols) = matrix_shape(lines)?;
         let cells: Vec<u8> = lines
             .iter()
-            .flat_map(|s| {
-                s.chars()
-                    .map(|c| map_func(c).ok_or(format!("Bad char {c}")))
+            .enumerate()
+            .flat_map(|(row, s)| {
+                let map_func = &map_func;
+                s.chars().enumerate().map(move |(col, c)| {
+                    map_func(c).ok_or_else(|| {
+                        AocError::malformed_at(row + 1, col + 1, format!("bad char {c:?}"))
+                    })
+                })
             })
             .collect::<Result<Vec<_>, _>>()?;
         Ok(Grid {
@@ -99,14 +120,13 @@ impl Grid {
     }
 
     pub fn from_slice(slice: &[u8], num_rows: usize, num_cols: usize) -> AocResult<Self> {
-        if slice.len() != num_rows * num_cols {
-            return failure(format!(
-                "Vec len {} doesn't equal num_rows={} * num_cols={}",
-                slice.len(),
-                num_rows,
-                num_cols
-            ));
-        }
+        ensure!(
+            slice.len() == num_rows * num_cols,
+            "Vec len {} doesn't equal num_rows={} * num_cols={}",
+            slice.len(),
+            num_rows,
+            num_cols
+        );
         Ok(Grid {
             cells: slice.to_vec(),
             num_rows,
@@ -139,19 +159,26 @@ impl Grid {
         self.num_cols
     }
 
+    /// The value at `p`, or None if `p` is off the grid.
+    pub fn get(&self, p: Point) -> Option<u8> {
+        if self.cells.is_empty()
+            || !self.is_toroidal && (p.i >= self.num_rows || p.j >= self.num_cols)
+        {
+            return None;
+        }
+        Some(self.cells[(p.i % self.num_rows) * self.num_cols + (p.j % self.num_cols)])
+    }
+
     pub fn at(&self, p: Point) -> AocResult<u8> {
-        if !self.is_toroidal && (p.i >= self.num_rows || p.j >= self.num_cols) {
-            return failure(format!("Invalid coordinates {}", p));
+        match self.get(p) {
+            Some(v) => Ok(v),
+            None => failure!("Invalid coordinates {}", p),
         }
-        Ok(self.cells[(p.i % self.num_rows) * self.num_cols + (p.j % self.num_cols)])
     }
 
     pub fn set(&mut self, point: Point, value: u8) -> AocResult<()> {
-        if !self.is_toroidal && (point.i >= self.num_rows || point.j >= self.num_cols) {
-            return failure(format!("Invalid coordinates {}", point));
-        }
-        self.cells[(point.i % self.num_rows) * self.num_cols + (point.j % self.num_cols)] =
-            value;
+        let index = self.index_from_point(point)?;
+        self.cells[index] = value;
         Ok(())
     }
 
@@ -166,8 +193,10 @@ impl Grid {
         point: Point,
         neighbour_pattern: NeighbourPattern,
     ) -> AocResult<Vec<Option<(Point, u8)>>> {
-        if !self.is_toroidal && (point.i >= self.num_rows || point.j >= self.num_cols) {
-            return failure(format!("Invalid coordinates {}", point));
+        if self.cells.is_empty()
+            || !self.is_toroidal && (point.i >= self.num_rows || point.j >= self.num_cols)
+        {
+            failure!("Invalid coordinates {}", point);
         }
         let mut out: Vec<Option<(Point, u8)>> = Vec::new();
 
@@ -221,15 +250,18 @@ impl Grid {
     }
 
     fn point_from_index(&self, index: usize) -> AocResult<Point> {
-        if index >= self.num_rows * self.num_cols {
-            return failure(format!("Invalid index {index}"));
-        }
-        Ok(Point::new(index / self.num_rows, index % self.num_cols))
+        ensure!(
+            index < self.num_rows * self.num_cols,
+            "Invalid index {index}"
+        );
+        Ok(Point::new(index / self.num_cols, index % self.num_cols))
     }
 
     fn index_from_point(&self, point: Point) -> AocResult<usize> {
-        if !self.is_toroidal && (point.i >= self.num_rows || point.j >= self.num_cols) {
-            return failure(format!("Invalid coordinates {}", point));
+        if self.cells.is_empty()
+            || !self.is_toroidal && (point.i >= self.num_rows || point.j >= self.num_cols)
+        {
+            failure!("Invalid coordinates {}", point);
         }
         Ok(self.num_cols * (point.i % self.num_rows) + (point.j % self.num_cols))
     }
@@ -239,6 +271,17 @@ impl Grid {
         start: Point,
         finish: Point,
         neighbour_pattern: NeighbourPattern,
+    ) -> AocResult<(Vec<Point>, Option<u64>)> {
+        self.dijkstra_with_stats(start, finish, neighbour_pattern, &mut SearchStats::new())
+    }
+
+    /// Like `dijkstra`, but records the work done in `stats`.
+    pub fn dijkstra_with_stats(
+        &self,
+        start: Point,
+        finish: Point,
+        neighbour_pattern: NeighbourPattern,
+        stats: &mut SearchStats,
     ) -> AocResult<(Vec<Point>, Option<u64>)> {
         let mut dist: Vec<Option<u64>> = vec![None; self.num_rows * self.num_cols];
         let mut prev: Vec<Option<usize>> = vec![None; self.num_rows * self.num_cols];
@@ -248,12 +291,16 @@ impl Grid {
 
         dist[start_index] = Some(0);
         q.push(Reverse(DistIdx {
-            dist: dist[start_index].unwrap(),
+            dist: 0,
             idx: start_index,
         }));
 
-        while !q.is_empty() {
-            let u_index = q.pop().unwrap().0.idx;
+        loop {
+            stats.record_frontier(q.len());
+            let Some(Reverse(DistIdx { idx: u_index, .. })) = q.pop() else {
+                break;
+            };
+            stats.record_expansion();
             let u_point = self.point_from_index(u_index)?;
             for v in self
                 .neighbourhood(u_point, neighbour_pattern)?
@@ -282,41 +329,316 @@ impl Grid {
             }
         }
 
-        // Construct the shortest path Vec
+        let path = self.path_to(&prev, start_index, finish_index)?;
+        Ok((path, dist[finish_index]))
+    }
+
+    /// Like `dijkstra`, but with a `BucketQueue` in place of a binary heap, which is
+    /// quicker for a grid's small costs.
+    pub fn dial_shortest_path(
+        &self,
+        start: Point,
+        finish: Point,
+        neighbour_pattern: NeighbourPattern,
+    ) -> AocResult<(Vec<Point>, Option<u64>)> {
+        self.dial_shortest_path_with_stats(
+            start,
+            finish,
+            neighbour_pattern,
+            &mut SearchStats::new(),
+        )
+    }
+
+    /// Like `dial_shortest_path`, but records the work done in `stats`.
+    pub fn dial_shortest_path_with_stats(
+        &self,
+        start: Point,
+        finish: Point,
+        neighbour_pattern: NeighbourPattern,
+        stats: &mut SearchStats,
+    ) -> AocResult<(Vec<Point>, Option<u64>)> {
+        let mut dist: Vec<Option<u64>> = vec![None; self.cells.len()];
+        let mut prev: Vec<Option<usize>> = vec![None; self.cells.len()];
+        let mut q = BucketQueue::new(usize::from(u8::MAX));
+        let start_index = self.index_from_point(start)?;
+        let finish_index = self.index_from_point(finish)?;
+
+        dist[start_index] = Some(0);
+        q.push(0, start_index);
+        loop {
+            stats.record_frontier(q.len());
+            let Some((d, u_index)) = q.pop() else {
+                break;
+            };
+            // Points are queued again when a shorter path to them turns up, rather
+            // than moved; skip the stale entries.
+            if dist[u_index].is_some_and(|best| d > best) {
+                continue;
+            }
+            stats.record_expansion();
+            if u_index == finish_index {
+                break;
+            }
+            let u_point = self.point_from_index(u_index)?;
+            for (v_point, cost) in self
+                .neighbourhood(u_point, neighbour_pattern)?
+                .into_iter()
+                .flatten()
+            {
+                let v_index = self.index_from_point(v_point)?;
+                let alt = d + u64::from(cost);
+                if dist[v_index].is_none_or(|best| alt < best) {
+                    dist[v_index] = Some(alt);
+                    prev[v_index] = Some(u_index);
+                    q.push(alt, v_index);
+                }
+            }
+        }
+
+        let path = self.path_to(&prev, start_index, finish_index)?;
+        Ok((path, dist[finish_index]))
+    }
+
+    /// The path from `start_index` to `finish_index` through the predecessors in
+    /// `prev`, or an empty path if there isn't one.
+    fn path_to(
+        &self,
+        prev: &[Option<usize>],
+        start_index: usize,
+        finish_index: usize,
+    ) -> AocResult<Vec<Point>> {
         let mut out: VecDeque<Point> = VecDeque::new();
-        let mut u_index = Some(finish_index);
-        if prev[u_index.unwrap()].is_some() || u_index.unwrap() == start_index {
-            while u_index.is_some() {
-                out.push_front(self.point_from_index(u_index.unwrap())?);
-                u_index = prev[u_index.unwrap()];
+        if prev[finish_index].is_some() || finish_index == start_index {
+            let mut u_index = Some(finish_index);
+            while let Some(u) = u_index {
+                out.push_front(self.point_from_index(u)?);
+                u_index = prev[u];
             }
         }
+        Ok(out.into())
+    }
 
-        Ok((out.drain(..).collect(), dist[finish_index]))
+    /// The points connected to `start` through neighbours (per `neighbour_pattern`)
+    /// whose values satisfy `passable`, in breadth-first order from `start`. `start` is
+    /// always included, whatever its value.
+    pub fn flood_fill<F>(
+        &self,
+        start: Point,
+        neighbour_pattern: NeighbourPattern,
+        passable: F,
+    ) -> AocResult<Vec<Point>>
+    where
+        F: Fn(u8) -> bool,
+    {
+        let mut seen = vec![false; self.cells.len()];
+        seen[self.index_from_point(start)?] = true;
+        let mut out = vec![self.point_from_index(self.index_from_point(start)?)?];
+        let mut next = 0;
+        while let Some(&p) = out.get(next) {
+            next += 1;
+            for (q, v) in self
+                .neighbourhood(p, neighbour_pattern)?
+                .into_iter()
+                .flatten()
+            {
+                let index = self.index_from_point(q)?;
+                if !seen[index] && passable(v) {
+                    seen[index] = true;
+                    out.push(q);
+                }
+            }
+        }
+        Ok(out)
     }
 
     pub fn add_border(&mut self, border_size: usize, border_fill: u8) {
         if border_size == 0 {
             return;
         }
-        let new_len = (self.num_rows + border_size * 2) * (self.num_cols + border_size * 2);
-        let mut new_cells = Vec::with_capacity(new_len);
-        new_cells.resize(new_len, border_fill);
-        let mut new_grid = Grid::from_slice(
-            new_cells.as_slice(),
-            self.num_rows + border_size * 2,
-            self.num_cols + border_size * 2,
-        )
-        .unwrap();
-        new_grid.is_toroidal = self.is_toroidal;
-        for i in 0..self.num_rows() {
-            for j in 0..self.num_cols() {
-                let p_old = Point::new(i, j);
-                let p_new = Point::new(border_size + i, border_size + j);
-                new_grid.set(p_new, self.at(p_old).unwrap()).unwrap();
+        let num_rows = self.num_rows + border_size * 2;
+        let num_cols = self.num_cols + border_size * 2;
+        let mut cells = vec![border_fill; num_rows * num_cols];
+        for i in 0..self.num_rows {
+            let row = &self.cells[i * self.num_cols..(i + 1) * self.num_cols];
+            let start = (border_size + i) * num_cols + border_size;
+            cells[start..start + self.num_cols].copy_from_slice(row);
+        }
+        self.cells = cells;
+        self.num_rows = num_rows;
+        self.num_cols = num_cols;
+    }
+
+    /// Rotates row `i` right by `by` cells, or left if `by` is negative. Cells pushed off
+    /// one end come back on at the other.
+    pub fn shift_row(&mut self, i: usize, by: isize) -> AocResult<()> {
+        ensure!(i < self.num_rows, "Invalid row {i}");
+        let row = &mut self.cells[i * self.num_cols..(i + 1) * self.num_cols];
+        row.rotate_right(wrap(by, self.num_cols));
+        Ok(())
+    }
+
+    /// Rotates column `j` down by `by` cells, or up if `by` is negative. Cells pushed off
+    /// one end come back on at the other.
+    pub fn shift_col(&mut self, j: usize, by: isize) -> AocResult<()> {
+        ensure!(j < self.num_cols, "Invalid column {j}");
+        let mut col: Vec<u8> = self
+            .cells
+            .iter()
+            .skip(j)
+            .step_by(self.num_cols)
+            .copied()
+            .collect();
+        col.rotate_right(wrap(by, self.num_rows));
+        for (cell, v) in self
+            .cells
+            .iter_mut()
+            .skip(j)
+            .step_by(self.num_cols)
+            .zip(col)
+        {
+            *cell = v;
+        }
+        Ok(())
+    }
+
+    /// Moves every cell `di` rows down and `dj` columns right, wrapping around the edges,
+    /// as if the grid were toroidal.
+    pub fn shift(&mut self, di: isize, dj: isize) {
+        if self.cells.is_empty() {
+            return;
+        }
+        let dj = wrap(dj, self.num_cols);
+        if dj != 0 {
+            for row in self.cells.chunks_mut(self.num_cols) {
+                row.rotate_right(dj);
+            }
+        }
+        // Rotating the whole row-major vector by whole rows moves the rows.
+        self.cells
+            .rotate_right(wrap(di, self.num_rows) * self.num_cols);
+    }
+
+    /// The points on the edge of the grid, each once, in row-major order.
+    pub fn border_points(&self) -> impl Iterator<Item = Point> + '_ {
+        let cols = self.num_cols;
+        // A grid with no columns has no cells, however many rows it has.
+        let rows = if cols == 0 { 0 } else { self.num_rows };
+        (0..rows).flat_map(move |i| {
+            let step = if i == 0 || i == rows - 1 {
+                1
+            } else {
+                // Just the first and last columns; `max` keeps a 1-column grid's only
+                // column from being visited twice.
+                (cols - 1).max(1)
+            };
+            (0..cols).step_by(step).map(move |j| Point::new(i, j))
+        })
+    }
+
+    /// The points and values along the `side` edge of the grid. North and South edges
+    /// run left to right, and East and West edges top to bottom, so that opposite edges
+    /// of neighbouring tiles line up.
+    pub fn edge(&self, side: Direction) -> impl Iterator<Item = (Point, u8)> + '_ {
+        let (rows, cols) = (self.num_rows, self.num_cols);
+        let points: Box<dyn Iterator<Item = Point>> = match side {
+            _ if self.cells.is_empty() => Box::new(std::iter::empty()),
+            Direction::North => Box::new((0..cols).map(|j| Point::new(0, j))),
+            Direction::South => Box::new((0..cols).map(move |j| Point::new(rows - 1, j))),
+            Direction::West => Box::new((0..rows).map(|i| Point::new(i, 0))),
+            Direction::East => Box::new((0..rows).map(move |i| Point::new(i, cols - 1))),
+        };
+        points.map(|p| (p, self.cells[p.i * self.num_cols + p.j]))
+    }
+
+    /// The corner points of the grid, each once, in row-major order: four of them, unless
+    /// the grid is a single row or column.
+    pub fn corners(&self) -> impl Iterator<Item = Point> {
+        let mut corners = Vec::with_capacity(4);
+        if !self.cells.is_empty() {
+            for i in [0, self.num_rows - 1] {
+                for j in [0, self.num_cols - 1] {
+                    let p = Point::new(i, j);
+                    if !corners.contains(&p) {
+                        corners.push(p);
+                    }
+                }
+            }
+        }
+        corners.into_iter()
+    }
+
+    /// Applies `f` to every 3x3 window of the grid, padded with a one-cell border of
+    /// `outside`, and returns the results. The window is in row-major order, so its
+    /// centre is `window[4]`. Cells beyond the grid read as `outside` too, which lets a
+    /// grid stand for a finite picture on an infinite background; the result is one cell
+    /// bigger on every side, since the windows that overlap the border can differ from
+    /// the background.
+    pub fn convolve_3x3<F>(&self, outside: u8, f: F) -> Self
+    where
+        F: Fn(&[u8; 9]) -> u8,
+    {
+        let num_rows = self.num_rows + 2;
+        let num_cols = self.num_cols + 2;
+        // Reads the padded grid, in which this grid starts at (2, 2).
+        let padded = |i: usize, j: usize| {
+            if (2..self.num_rows + 2).contains(&i) && (2..self.num_cols + 2).contains(&j) {
+                self.cells[(i - 2) * self.num_cols + j - 2]
+            } else {
+                outside
             }
+        };
+        let mut cells = Vec::with_capacity(num_rows * num_cols);
+        let mut window = [0; 9];
+        for i in 0..num_rows {
+            for j in 0..num_cols {
+                for (k, w) in window.iter_mut().enumerate() {
+                    *w = padded(i + k / 3, j + k % 3);
+                }
+                cells.push(f(&window));
+            }
+        }
+        Grid {
+            cells,
+            num_rows,
+            num_cols,
+            is_toroidal: false,
         }
-        *self = new_grid;
+    }
+
+    /// A grid made of `tile_rows` by `tile_cols` copies of this one. The cell that came
+    /// from `value` in the copy at tile row `ti` and tile column `tj` holds
+    /// `f(value, ti, tj)`.
+    pub fn tiled<F>(&self, tile_rows: usize, tile_cols: usize, f: F) -> AocResult<Self>
+    where
+        F: Fn(u8, usize, usize) -> u8,
+    {
+        let num_rows = self.num_rows.checked_mul(tile_rows);
+        let num_cols = self.num_cols.checked_mul(tile_cols);
+        let (Some(num_rows), Some(num_cols)) = (num_rows, num_cols) else {
+            failure!("Tiling is too big");
+        };
+        let mut cells = Vec::with_capacity(num_rows * num_cols);
+        for ti in 0..tile_rows {
+            for row in self.cells.chunks(self.num_cols.max(1)) {
+                for tj in 0..tile_cols {
+                    cells.extend(row.iter().map(|&v| f(v, ti, tj)));
+                }
+            }
+        }
+        Ok(Grid {
+            cells,
+            num_rows,
+            num_cols,
+            is_toroidal: self.is_toroidal,
+        })
+    }
+}
+
+/// `by` reduced to a rotation in `0..len`, or 0 if `len` is 0.
+fn wrap(by: isize, len: usize) -> usize {
+    match isize::try_from(len) {
+        Ok(len) if len > 0 => by.rem_euclid(len) as usize,
+        _ => 0,
     }
 }
 
@@ -344,10 +666,59 @@ impl PartialEq for DistIdx {
     }
 }
 
+/// The number of rows and columns in `lines`, which must all be the same length.
+fn matrix_shape<S: AsRef<str>>(lines: &[S]) -> AocResult<(usize, usize)> {
+    let num_cols = lines.first().ok_or("No rows")?.as_ref().chars().count();
+    for (row, line) in lines.iter().enumerate() {
+        let len = line.as_ref().chars().count();
+        if len != num_cols {
+            return malformed(row + 1, format!("expected {num_cols} columns, got {len}"));
+        }
+    }
+    Ok((lines.len(), num_cols))
+}
+
 #[cfg(test)]
 mod grid_tests {
     use super::*;
 
+    #[test]
+    fn digit_matrix() -> AocResult<()> {
+        let grid = Grid::from_digit_matrix(["12", "34"])?;
+        assert_eq!(grid.vec(), &[1, 2, 3, 4]);
+        let err = AocError::from_boxed(Grid::from_digit_matrix(["12", "3x"]).unwrap_err());
+        assert_eq!(err.to_string(), "Line 2, column 2: bad digit 'x'");
+        let err = Grid::from_digit_matrix(["12", "345"]).unwrap_err();
+        assert_eq!(err.to_string(), "Line 2: expected 2 columns, got 3");
+        Ok(())
+    }
+
+    #[test]
+    fn out_of_bounds() -> AocResult<()> {
+        let mut grid = Grid::from_slice(&[1, 2, 3, 4, 5, 6], 2, 3)?;
+        assert_eq!(grid.get(Point::new(1, 2)), Some(6));
+        assert_eq!(grid.get(Point::new(2, 0)), None);
+        assert!(grid.at(Point::new(0, 3)).is_err());
+        assert!(grid.set(Point::new(0, 3), 0).is_err());
+
+        let empty = Grid::from_slice(&[], 0, 0)?;
+        assert_eq!(empty.get(Point::new(0, 0)), None);
+        assert!(empty
+            .neighbourhood(Point::new(0, 0), NeighbourPattern::Compass4)
+            .is_err());
+
+        // Non-square grids path through the right cells.
+        grid.set(Point::new(0, 1), 9)?;
+        let (path, cost) = grid.dijkstra(
+            Point::new(0, 0),
+            Point::new(1, 2),
+            NeighbourPattern::Compass4,
+        )?;
+        assert_eq!(cost, Some(4 + 5 + 6));
+        assert_eq!(path.len(), 4);
+        Ok(())
+    }
+
     #[test]
     fn grid_border() -> AocResult<()> {
         #[rustfmt::skip]
@@ -381,6 +752,128 @@ mod grid_tests {
         Ok(())
     }
 
+    #[test]
+    fn edges() -> AocResult<()> {
+        #[rustfmt::skip]
+        let grid = Grid::from_slice(&[
+            1, 2, 3,
+            4, 5, 6,
+            7, 8, 9], 3, 3)?;
+        let values = |side| grid.edge(side).map(|(_, v)| v).collect::<Vec<_>>();
+        assert_eq!(values(Direction::North), [1, 2, 3]);
+        assert_eq!(values(Direction::East), [3, 6, 9]);
+        assert_eq!(values(Direction::South), [7, 8, 9]);
+        assert_eq!(values(Direction::West), [1, 4, 7]);
+        let border: Vec<u8> = grid.border_points().map(|p| grid.at(p).unwrap()).collect();
+        assert_eq!(border, [1, 2, 3, 4, 6, 7, 8, 9]);
+        let corners: Vec<Point> = grid.corners().collect();
+        assert_eq!(
+            corners,
+            [(0, 0), (0, 2), (2, 0), (2, 2)].map(Point::from_pair)
+        );
+
+        let column = Grid::from_slice(&[1, 2, 3], 3, 1)?;
+        assert_eq!(column.border_points().count(), 3);
+        assert_eq!(column.corners().count(), 2);
+        let dot = Grid::from_slice(&[1], 1, 1)?;
+        assert_eq!(dot.border_points().count(), 1);
+        assert_eq!(dot.corners().count(), 1);
+        assert_eq!(
+            dot.edge(Direction::East).collect::<Vec<_>>(),
+            [(Point::new(0, 0), 1)]
+        );
+        for (rows, cols) in [(0, 0), (3, 0), (0, 3)] {
+            let empty = Grid::from_slice(&[], rows, cols)?;
+            assert_eq!(empty.border_points().count(), 0);
+            for side in [
+                Direction::North,
+                Direction::East,
+                Direction::South,
+                Direction::West,
+            ] {
+                assert_eq!(empty.edge(side).count(), 0);
+            }
+            assert_eq!(empty.corners().count(), 0);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn shifts() -> AocResult<()> {
+        #[rustfmt::skip]
+        let mut grid = Grid::from_slice(&[
+            1, 2, 3,
+            4, 5, 6], 2, 3)?;
+        grid.shift_row(0, 1)?;
+        grid.shift_col(2, -1)?;
+        #[rustfmt::skip]
+        let expected = Grid::from_slice(&[
+            3, 1, 6,
+            4, 5, 2], 2, 3)?;
+        assert_eq!(grid, expected);
+        grid.shift_col(2, 3)?;
+        grid.shift_row(0, -4)?;
+        #[rustfmt::skip]
+        let expected = Grid::from_slice(&[
+            1, 2, 3,
+            4, 5, 6], 2, 3)?;
+        assert_eq!(grid, expected);
+        assert!(grid.shift_row(2, 1).is_err());
+        assert!(grid.shift_col(3, 1).is_err());
+
+        grid.shift(1, -1);
+        #[rustfmt::skip]
+        let expected = Grid::from_slice(&[
+            5, 6, 4,
+            2, 3, 1], 2, 3)?;
+        assert_eq!(grid, expected);
+        // Shifts are modulo the grid's size, so this undoes the last one.
+        grid.shift(-3, 7);
+        assert_eq!(grid, Grid::from_slice(&[1, 2, 3, 4, 5, 6], 2, 3)?);
+
+        let mut empty = Grid::from_slice(&[], 0, 0)?;
+        empty.shift(1, 1);
+        assert!(empty.vec().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn convolution() -> AocResult<()> {
+        let grid = Grid::from_slice(&[1, 2, 3, 4], 2, 2)?;
+        let sums = grid.convolve_3x3(0, |w| w.iter().sum());
+        #[rustfmt::skip]
+        let expected = Grid::from_slice(&[
+            1, 3, 3, 2,
+            4, 10, 10, 6,
+            4, 10, 10, 6,
+            3, 7, 7, 4,
+        ], 4, 4)?;
+        assert_eq!(sums, expected);
+        let centres = grid.convolve_3x3(7, |w| w[4]);
+        assert_eq!(centres.get(Point::new(1, 2)), Some(2));
+        assert_eq!(centres.get(Point::new(3, 0)), Some(7));
+        let empty = Grid::from_slice(&[], 0, 0)?.convolve_3x3(1, |w| w.iter().sum());
+        assert_eq!(empty, Grid::from_slice(&[9; 4], 2, 2)?);
+        Ok(())
+    }
+
+    #[test]
+    fn tiles() -> AocResult<()> {
+        let grid = Grid::from_slice(&[1, 2, 8, 9], 2, 2)?;
+        let tiled = grid.tiled(2, 3, |v, ti, tj| v + (10 * ti + tj) as u8)?;
+        #[rustfmt::skip]
+        let expected = Grid::from_slice(&[
+            1, 2, 2, 3, 3, 4,
+            8, 9, 9, 10, 10, 11,
+            11, 12, 12, 13, 13, 14,
+            18, 19, 19, 20, 20, 21,
+        ], 4, 6)?;
+        assert_eq!(tiled, expected);
+        assert_eq!(grid.tiled(1, 1, |v, _, _| v)?, grid);
+        assert_eq!(grid.tiled(0, 3, |v, _, _| v)?.num_rows(), 0);
+        Ok(())
+    }
+
     #[test]
     fn at() -> AocResult<()> {
         #[rustfmt::skip]
@@ -482,4 +975,71 @@ mod grid_tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn flood_fill() -> AocResult<()> {
+        #[rustfmt::skip]
+        let mut grid = Grid::from_slice(&[
+            1, 1, 0, 1,
+            0, 1, 0, 0,
+            1, 0, 1, 1], 3, 4)?;
+        let ones = |v| v == 1;
+        assert_eq!(
+            grid.flood_fill(Point::new(0, 0), NeighbourPattern::Compass4, ones)?,
+            vec![Point::new(0, 0), Point::new(0, 1), Point::new(1, 1)]
+        );
+        assert_eq!(
+            grid.flood_fill(Point::new(0, 0), NeighbourPattern::Compass8, ones)?
+                .len(),
+            6
+        );
+        assert_eq!(
+            grid.flood_fill(Point::new(1, 0), NeighbourPattern::Compass4, |v| v == 0)?,
+            vec![Point::new(1, 0)]
+        );
+        assert!(grid
+            .flood_fill(Point::new(3, 0), NeighbourPattern::Compass4, ones)
+            .is_err());
+
+        grid.make_toroidal(true);
+        assert_eq!(
+            grid.flood_fill(Point::new(0, 0), NeighbourPattern::Compass4, ones)?
+                .len(),
+            7
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn dial() -> AocResult<()> {
+        #[rustfmt::skip]
+        let grid = Grid::from_slice(&[
+            1, 9, 9, 9,
+            1, 0, 0, 9,
+            9, 9, 0, 1,
+            255, 9, 9, 1], 4, 4)?;
+        let (start, finish) = (Point::new(0, 0), Point::new(3, 3));
+        for pattern in [NeighbourPattern::Compass4, NeighbourPattern::Compass8] {
+            let (path, cost) = grid.dial_shortest_path(start, finish, pattern)?;
+            let (_, expected) = grid.dijkstra(start, finish, pattern)?;
+            assert_eq!(cost, expected);
+            assert_eq!(path.first(), Some(&start));
+            assert_eq!(path.last(), Some(&finish));
+        }
+        let (path, cost) =
+            grid.dial_shortest_path(start, finish, NeighbourPattern::Compass4)?;
+        // Down, then along the zeros, then down the 1s.
+        assert_eq!(cost, Some(3));
+        assert_eq!(path.len(), 7);
+        assert_eq!(
+            grid.dial_shortest_path(start, Point::new(3, 0), NeighbourPattern::Compass4)?
+                .1,
+            Some(1 + 9 + 255)
+        );
+        assert_eq!(
+            grid.dial_shortest_path(start, start, NeighbourPattern::Compass4)?,
+            (vec![start], Some(0))
+        );
+        Ok(())
+    }
 }
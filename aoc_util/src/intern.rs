@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A compact handle for an interned name. Only meaningful for the `Interner` (or graph)
+/// that produced it.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct NodeId(u32);
+
+impl NodeId {
+    pub(crate) fn from_index(idx: usize) -> Self {
+        NodeId(u32::try_from(idx).expect("Too many interned names for a u32"))
+    }
+
+    /// Ids are handed out densely from 0, so they can index into `Vec`s.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Maps names to `NodeId`s and back. Each name is stored once, shared between the two
+/// directions of the mapping.
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+    names: Vec<Arc<str>>,
+    ids: HashMap<Arc<str>, NodeId>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Returns the id for `name`, assigning the next free one if `name` is new.
+    pub fn intern(&mut self, name: &str) -> NodeId {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = NodeId::from_index(self.names.len());
+        let name: Arc<str> = Arc::from(name);
+        self.names.push(name.clone());
+        self.ids.insert(name, id);
+        id
+    }
+
+    pub fn get(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    /// Panics if `id` didn't come from this interner.
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id.index()]
+    }
+}
+
+#[cfg(test)]
+mod interner_tests {
+    use super::*;
+
+    #[test]
+    fn interner() {
+        let mut interner = Interner::new();
+        assert!(interner.is_empty());
+        let a = interner.intern("a");
+        let b = interner.intern("bb");
+        assert_eq!(interner.intern("a"), a);
+        assert_ne!(a, b);
+        assert_eq!((a.index(), b.index()), (0, 1));
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.get("bb"), Some(b));
+        assert_eq!(interner.get("c"), None);
+        assert_eq!(interner.name(b), "bb");
+    }
+}
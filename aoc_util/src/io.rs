@@ -1,34 +1,310 @@
-use crate::errors::{failure, AocResult};
+use crate::checksum::{check_checksum, manifest_path, ChecksumStatus};
+use crate::ensure;
+use crate::errors::{AocResult, LineContext};
 
 use std::env;
-use std::path::Path;
+use std::ffi::OsString;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
 
-pub fn get_cli_arg() -> AocResult<String> {
-    let mut args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        return failure(format!("Bad CLI args: {:?}", args));
+/// Set by `set_data_dir`.
+static DATA_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Reads a whole puzzle input from the file at `path` (see `read_input_file`), or from
+/// stdin if `path` is "-".
+pub fn read_input(path: &str) -> AocResult<String> {
+    read_input_from(path, io::stdin().lock())
+}
+
+fn read_input_from<R: Read>(path: &str, mut stdin: R) -> AocResult<String> {
+    if path == "-" {
+        let mut input = String::new();
+        stdin.read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        Ok(read_input_file(Path::new(path)).map_err(|e| format!("{path}: {e}"))?)
+    }
+}
+
+/// Reads the input at `path`, which may be gzipped: either `path` ends in ".gz", or
+/// only `path` + ".gz" exists. If the input has an entry in its directory's checksum
+/// manifest and doesn't match it, a warning is printed to stderr.
+pub fn read_input_file(path: &Path) -> AocResult<String> {
+    let file = find_input(path).unwrap_or_else(|| path.to_path_buf());
+    let contents = if file.extension().is_some_and(|ext| ext == "gz") {
+        gunzip(&file)?
+    } else {
+        fs::read_to_string(&file)?
+    };
+    match check_checksum(path, &contents) {
+        Ok(ChecksumStatus::Mismatch { expected, actual }) => eprintln!(
+            "Warning: {} has SHA-256 {actual}, but {} expects {expected}. Was it \
+             truncated or edited?",
+            file.display(),
+            manifest_path(path).display()
+        ),
+        Ok(_) => (),
+        Err(e) => eprintln!("Warning: couldn't check {}: {e}", file.display()),
+    }
+    Ok(contents)
+}
+
+/// Like `read_input`, split into lines.
+pub fn read_lines(path: &str) -> AocResult<Vec<String>> {
+    Ok(read_input(path)?.lines().map(String::from).collect())
+}
+
+/// Like `read_input`, without leading or trailing whitespace, for one-line inputs.
+pub fn read_to_trimmed_string(path: &str) -> AocResult<String> {
+    Ok(read_input(path)?.trim().to_string())
+}
+
+/// Calls `f` on each line of the input at `path` (or stdin, if `path` is "-"), reading
+/// as it goes rather than holding the whole input in memory. Gzipped inputs are streamed
+/// through gzip, as with `read_input_file`, but there's no checksum check, since that
+/// needs the whole input. Stops at the first error from `f`, reporting its line number.
+pub fn for_each_line<F>(path: &str, f: F) -> AocResult<()>
+where
+    F: FnMut(&str) -> AocResult<()>,
+{
+    if path == "-" {
+        return for_each_line_in(io::stdin().lock(), f);
     }
-    Ok(args.pop().unwrap())
+    let file = find_input(Path::new(path)).unwrap_or_else(|| PathBuf::from(path));
+    if file.extension().is_none_or(|ext| ext != "gz") {
+        let reader = fs::File::open(&file).map_err(|e| format!("{path}: {e}"))?;
+        return for_each_line_in(BufReader::new(reader), f);
+    }
+    let mut child = Command::new("gzip")
+        .arg("-dc")
+        .arg(&file)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Couldn't run gzip: {e}"))?;
+    let stdout = child.stdout.take().ok_or("No stdout from gzip?")?;
+    let result = for_each_line_in(BufReader::new(stdout), f);
+    // Reap gzip even if `f` gave up early, which may have made it fail writing.
+    let output = child.wait_with_output()?;
+    result?;
+    ensure!(
+        output.status.success(),
+        "gzip -dc failed: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    Ok(())
+}
+
+fn for_each_line_in<R: BufRead, F>(reader: R, mut f: F) -> AocResult<()>
+where
+    F: FnMut(&str) -> AocResult<()>,
+{
+    for (i, line) in reader.lines().enumerate() {
+        f(&line?).at_line(i + 1)?;
+    }
+    Ok(())
+}
+
+/// The file holding the input at `path`: `path` itself, or else its gzipped version.
+pub fn find_input(path: &Path) -> Option<PathBuf> {
+    if path.exists() {
+        return Some(path.to_path_buf());
+    }
+    let mut gz = path.as_os_str().to_owned();
+    gz.push(".gz");
+    let gz = PathBuf::from(gz);
+    gz.exists().then_some(gz)
+}
+
+fn gunzip(path: &Path) -> AocResult<String> {
+    let output = Command::new("gzip")
+        .arg("-dc")
+        .arg(path)
+        .output()
+        .map_err(|e| format!("Couldn't run gzip: {e}"))?;
+    ensure!(
+        output.status.success(),
+        "gzip -dc failed: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// The directory holding puzzle inputs and examples: `$AOC_DATA_DIR` if set, else the
+/// directory given to `set_data_dir`, else the `data/` directory of the crate being run
+/// or tested (cargo sets `CARGO_MANIFEST_DIR` for `cargo run` and `cargo test`), else
+/// `data/` under the current directory.
+pub fn data_dir() -> PathBuf {
+    let chosen = DATA_DIR.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    choose_data_dir(
+        env::var_os("AOC_DATA_DIR"),
+        chosen,
+        env::var_os("CARGO_MANIFEST_DIR"),
+    )
+}
+
+/// Overrides the crate's own `data/` directory, for programs like the runner that
+/// solve puzzles from other crates, each with its data in its own directory.
+pub fn set_data_dir(dir: impl Into<PathBuf>) {
+    *DATA_DIR.lock().unwrap_or_else(|e| e.into_inner()) = Some(dir.into());
+}
+
+fn choose_data_dir(
+    data_dir: Option<OsString>,
+    chosen: Option<PathBuf>,
+    manifest_dir: Option<OsString>,
+) -> PathBuf {
+    match (data_dir, chosen, manifest_dir) {
+        (Some(dir), _, _) if !dir.is_empty() => dir.into(),
+        (_, Some(dir), _) => dir,
+        (_, _, Some(dir)) if !dir.is_empty() => Path::new(&dir).join("data"),
+        _ => PathBuf::from("data"),
+    }
+}
+
+fn data_path(name: &str) -> String {
+    data_dir().join(name).to_string_lossy().into_owned()
 }
 
 pub fn get_input_file(codefile: &str) -> AocResult<String> {
-    get_data_file(codefile, "input")
+    Ok(data_path(&data_file_name(codefile, "input")?))
 }
 
 pub fn get_test_file(codefile: &str) -> AocResult<String> {
-    get_data_file(codefile, "test")
+    Ok(data_path(&data_file_name(codefile, "test")?))
 }
 
-fn get_data_file(codefile: &str, kind: &str) -> AocResult<String> {
+/// Like `get_test_file`, for days with several examples: example 1 is the usual test
+/// file, and example `n` > 1 is e.g. "12_test_2.txt".
+pub fn get_test_file_n(codefile: &str, n: usize) -> AocResult<String> {
+    if n == 1 {
+        return get_test_file(codefile);
+    }
+    Ok(data_path(&data_file_name(codefile, &format!("test_{n}"))?))
+}
+
+/// The path of `day`'s data file of the given kind, e.g. "data/05_test.txt".
+pub fn get_day_file(day: u32, kind: &str) -> String {
+    data_path(&format!("{day:02}_{kind}.txt"))
+}
+
+fn data_file_name(codefile: &str, kind: &str) -> AocResult<String> {
     let stem = Path::new(codefile)
         .file_stem()
         .ok_or(format!("No stem for {codefile}?"))?;
-    let datafile = "data/".to_string()
-        + stem
-            .to_str()
-            .ok_or(format!("OsStr {stem:?} -> str failed?"))?
-        + "_"
-        + kind
-        + ".txt";
-    Ok(datafile)
+    let stem = stem
+        .to_str()
+        .ok_or(format!("OsStr {stem:?} -> str failed?"))?;
+    // Library modules are named e.g. "day16", but share their data with bin "16".
+    let day = stem.strip_prefix("day").unwrap_or(stem);
+    Ok(day.to_string() + "_" + kind + ".txt")
+}
+
+#[cfg(test)]
+mod io_tests {
+    use super::*;
+
+    #[test]
+    fn data_files() -> AocResult<()> {
+        assert_eq!(data_file_name("src/bin/16.rs", "input")?, "16_input.txt");
+        assert_eq!(data_file_name("src/day16.rs", "test")?, "16_test.txt");
+        let dir = data_dir();
+        assert_eq!(
+            get_test_file("src/day16.rs")?,
+            dir.join("16_test.txt").to_string_lossy()
+        );
+        assert_eq!(get_test_file_n("src/day12.rs", 1)?, get_test_file("day12")?);
+        assert_eq!(
+            get_test_file_n("src/day12.rs", 3)?,
+            dir.join("12_test_3.txt").to_string_lossy()
+        );
+        assert_eq!(
+            get_day_file(5, "test"),
+            dir.join("05_test.txt").to_string_lossy()
+        );
+
+        let s = |s: &str| Some(OsString::from(s));
+        let p = |p: &str| Some(PathBuf::from(p));
+        assert_eq!(
+            choose_data_dir(s("/in"), p("/set"), s("/crate")),
+            Path::new("/in")
+        );
+        assert_eq!(
+            choose_data_dir(s(""), p("/set"), s("/crate")),
+            Path::new("/set")
+        );
+        assert_eq!(
+            choose_data_dir(s(""), None, s("/crate")),
+            Path::new("/crate/data")
+        );
+        assert_eq!(choose_data_dir(None, None, None), Path::new("data"));
+        Ok(())
+    }
+
+    #[test]
+    fn input_sources() -> AocResult<()> {
+        assert_eq!(read_input_from("-", "1\n2\n".as_bytes())?, "1\n2\n");
+        assert!(read_input_from("data/nonexistent.txt", "".as_bytes())
+            .unwrap_err()
+            .to_string()
+            .starts_with("data/nonexistent.txt: "));
+        Ok(())
+    }
+
+    #[test]
+    fn line_helpers() -> AocResult<()> {
+        let mut lines = Vec::new();
+        for_each_line_in("a\nbb\n\nccc".as_bytes(), |line| {
+            lines.push(line.to_string());
+            Ok(())
+        })?;
+        assert_eq!(lines, ["a", "bb", "", "ccc"]);
+
+        let err = for_each_line_in("1\n2\nx\n4".as_bytes(), |line| {
+            line.parse::<i32>()?;
+            Ok(())
+        })
+        .unwrap_err();
+        assert!(err.to_string().starts_with("Line 3: "), "{err}");
+
+        assert!(for_each_line("data/nonexistent.txt", |_| Ok(())).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn gzipped_inputs() -> AocResult<()> {
+        let dir = env::temp_dir().join(format!("aoc_io_tests_{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let result = (|| -> AocResult<()> {
+            let input = dir.join("01_input.txt");
+            fs::write(&input, "199\n200\n")?;
+            let path = input.to_string_lossy();
+            assert_eq!(read_lines(&path)?, ["199", "200"]);
+            assert_eq!(read_to_trimmed_string(&path)?, "199\n200");
+            let status = Command::new("gzip").arg(&input).status()?;
+            assert!(status.success());
+            assert!(!input.exists());
+
+            let gz = dir.join("01_input.txt.gz");
+            assert_eq!(find_input(&input), Some(gz.clone()));
+            assert_eq!(read_input_file(&input)?, "199\n200\n");
+            assert_eq!(read_input_file(&gz)?, "199\n200\n");
+            let mut streamed = Vec::new();
+            for_each_line(&path, |line| {
+                streamed.push(line.to_string());
+                Ok(())
+            })?;
+            assert_eq!(streamed, ["199", "200"]);
+            assert_eq!(find_input(&dir.join("02_input.txt")), None);
+
+            fs::write(dir.join("02_input.txt.gz"), "not gzip")?;
+            assert!(read_input_file(&dir.join("02_input.txt")).is_err());
+            Ok(())
+        })();
+        fs::remove_dir_all(&dir)?;
+        result
+    }
 }
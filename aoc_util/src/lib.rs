@@ -1,7 +1,43 @@
+pub mod answers;
+pub mod arena;
+pub mod arenatree;
+pub mod bench;
 pub mod binarytree;
+pub mod bingo;
+pub mod bits;
+pub mod bitset;
+pub mod brackets;
+pub mod bucket;
+pub mod checksum;
+pub mod cli;
+pub mod counter;
 pub mod cuboid;
+pub mod dsu;
 pub mod errors;
+pub mod fasthash;
+pub mod flow;
+pub mod game;
 pub mod graph;
 pub mod grid;
+pub mod intern;
 pub mod io;
+pub mod log;
+pub mod math;
+pub mod matrix;
+pub mod memo;
+pub mod net;
+pub mod ocr;
+pub mod opt;
+#[cfg(feature = "parallel")]
+pub mod par;
+pub mod parse;
 pub mod point;
+pub mod recurrence;
+pub mod search;
+pub mod segment;
+pub mod sevenseg;
+pub mod sha256;
+pub mod solver;
+pub mod timing;
+pub mod transform;
+pub mod window;
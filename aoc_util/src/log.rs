@@ -0,0 +1,119 @@
+//! Diagnostics for long-running solvers, printed to stderr only when asked for: `info!`
+//! with `--verbose` or `AOC_VERBOSE` set, and `debug!` too with `AOC_VERBOSE=debug`.
+
+use std::env;
+use std::ffi::OsStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Level {
+    Info = 1,
+    Debug = 2,
+}
+
+/// Whether messages at `level` should be printed.
+pub fn enabled(level: Level) -> bool {
+    let env_level = env::var_os("AOC_VERBOSE").map_or(0, |v| level_from_env(&v) as u8);
+    LEVEL.load(Ordering::Relaxed).max(env_level) >= level as u8
+}
+
+/// Turns on messages at `level` and below, on top of whatever `AOC_VERBOSE` asks for.
+pub fn set_level(level: Level) {
+    LEVEL.fetch_max(level as u8, Ordering::Relaxed);
+}
+
+fn level_from_env(value: &OsStr) -> Level {
+    if value.eq_ignore_ascii_case("debug") || value == "2" {
+        Level::Debug
+    } else {
+        Level::Info
+    }
+}
+
+/// Like `eprintln!`, if `Level::Info` is enabled.
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Info) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Like `eprintln!`, if `Level::Debug` is enabled.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Debug) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Called with the units of work done so far and, if known, the total.
+pub type ProgressFn<'a> = dyn FnMut(u64, Option<u64>) + 'a;
+
+/// Handed to a slow computation, which calls `report` now and then so that whoever
+/// started it can tell it's getting somewhere.
+#[derive(Default)]
+pub struct Progress<'a> {
+    callback: Option<Box<ProgressFn<'a>>>,
+}
+
+impl<'a> Progress<'a> {
+    pub fn new(callback: impl FnMut(u64, Option<u64>) + 'a) -> Self {
+        Progress {
+            callback: Some(Box::new(callback)),
+        }
+    }
+
+    /// Ignores all reports.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Prints reports as "LABEL: DONE/TOTAL" if `Level::Info` is enabled.
+    pub fn log(label: &'a str) -> Self {
+        if !enabled(Level::Info) {
+            return Self::none();
+        }
+        Self::new(move |done, total| match total {
+            Some(total) => eprintln!("{label}: {done}/{total}"),
+            None => eprintln!("{label}: {done}"),
+        })
+    }
+
+    pub fn report(&mut self, done: u64, total: Option<u64>) {
+        if let Some(callback) = &mut self.callback {
+            callback(done, total);
+        }
+    }
+}
+
+#[cfg(test)]
+mod log_tests {
+    use super::*;
+
+    #[test]
+    fn levels() {
+        assert_eq!(level_from_env(OsStr::new("1")), Level::Info);
+        assert_eq!(level_from_env(OsStr::new("")), Level::Info);
+        assert_eq!(level_from_env(OsStr::new("DEBUG")), Level::Debug);
+        assert_eq!(level_from_env(OsStr::new("2")), Level::Debug);
+        assert!(Level::Info < Level::Debug);
+    }
+
+    #[test]
+    fn progress() {
+        let mut reports = Vec::new();
+        let mut progress = Progress::new(|done, total| reports.push((done, total)));
+        progress.report(1, Some(3));
+        progress.report(5, None);
+        drop(progress);
+        assert_eq!(reports, [(1, Some(3)), (5, None)]);
+
+        Progress::none().report(1, None);
+    }
+}
@@ -0,0 +1,145 @@
+//! Number theory for puzzles about cycles lining up.
+
+use crate::errors::AocResult;
+use crate::{ensure, failure};
+use std::fmt;
+use std::ops::{Add, Mul};
+
+/// The greatest common divisor of `a` and `b`. `gcd(0, 0)` is 0.
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple of `a` and `b`, or an error if it doesn't fit in a `u64`.
+/// `lcm(0, x)` is 0.
+pub fn lcm(a: u64, b: u64) -> AocResult<u64> {
+    if a == 0 || b == 0 {
+        return Ok(0);
+    }
+    match (a / gcd(a, b)).checked_mul(b) {
+        Some(l) => Ok(l),
+        None => failure!("lcm({a}, {b}) overflows"),
+    }
+}
+
+/// The extended Euclidean algorithm: returns `(g, x, y)` where `g` is the non-negative
+/// gcd of `a` and `b`, and `a * x + b * y == g`.
+pub fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// The inverse of `a` modulo `m`, in `0..m`: the `x` for which `a * x` is 1 mod `m`.
+/// Fails unless `m` is positive and `a` and `m` are coprime.
+pub fn mod_inv(a: i64, m: i64) -> AocResult<i64> {
+    ensure!(m > 0, "Modulus {m} isn't positive");
+    let (g, x, _) = egcd(a.rem_euclid(m), m);
+    ensure!(
+        g == 1,
+        "{a} has no inverse mod {m}: they share the factor {g}"
+    );
+    Ok(x.rem_euclid(m))
+}
+
+/// An integer modulo `M`, for counts too big to compute exactly. `M` must be positive.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ModInt<const M: u64>(u64);
+
+impl<const M: u64> ModInt<M> {
+    pub const fn new(x: u64) -> Self {
+        ModInt(x % M)
+    }
+
+    /// The representative in `0..M`.
+    pub const fn value(self) -> u64 {
+        self.0
+    }
+}
+
+impl<const M: u64> From<u64> for ModInt<M> {
+    fn from(x: u64) -> Self {
+        Self::new(x)
+    }
+}
+
+impl<const M: u64> fmt::Display for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        ModInt(((u128::from(self.0) + u128::from(other.0)) % u128::from(M)) as u64)
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        ModInt(((u128::from(self.0) * u128::from(other.0)) % u128::from(M)) as u64)
+    }
+}
+
+#[cfg(test)]
+mod math_tests {
+    use super::*;
+
+    #[test]
+    fn divisors() -> AocResult<()> {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(18, 12), 6);
+        assert_eq!(gcd(7, 0), 7);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, 6)?, 12);
+        assert_eq!(lcm(0, 6)?, 0);
+        assert_eq!([7, 13, 59, 31, 19].into_iter().try_fold(1, lcm)?, 3162341);
+        assert_eq!(lcm(u64::MAX, 1)?, u64::MAX);
+        assert!(lcm(u64::MAX, 2).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn inverses() -> AocResult<()> {
+        for (a, b) in [(240, 46), (-240, 46), (240, -46), (0, 5), (5, 0), (17, 17)] {
+            let (g, x, y) = egcd(a, b);
+            assert_eq!(g, gcd(a.unsigned_abs(), b.unsigned_abs()) as i64);
+            assert_eq!(a * x + b * y, g, "egcd({a}, {b})");
+        }
+        assert_eq!(mod_inv(3, 11)?, 4);
+        assert_eq!(mod_inv(-3, 11)?, 7);
+        assert_eq!(mod_inv(1, 1)?, 0);
+        assert!(mod_inv(6, 9).is_err());
+        assert!(mod_inv(3, 0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn modular() {
+        type M7 = ModInt<7>;
+        assert_eq!(M7::new(9).value(), 2);
+        assert_eq!((M7::new(5) + M7::new(4)).value(), 2);
+        assert_eq!((M7::new(5) * M7::new(4)).to_string(), "6");
+        let big = ModInt::<{ u64::MAX }>::new(u64::MAX - 1);
+        assert_eq!((big + big).value(), u64::MAX - 2);
+        assert_eq!((big * big).value(), 1);
+    }
+}
@@ -0,0 +1,244 @@
+use crate::errors::AocResult;
+use crate::math::ModInt;
+use crate::{ensure, failure};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// Numbers a `Matrix` can hold. Arithmetic is checked, so that a matrix power that
+/// overflows is an error rather than a wrong answer.
+pub trait Scalar: Copy + fmt::Debug + fmt::Display + PartialEq {
+    const ZERO: Self;
+    const ONE: Self;
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_scalar {
+    ($($t:ty),+) => {
+        $(
+            impl Scalar for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+            }
+        )+
+    };
+}
+
+impl_scalar!(i32, i64, i128, u32, u64, u128, usize);
+
+/// Modular arithmetic can't overflow.
+impl<const M: u64> Scalar for ModInt<M> {
+    const ZERO: Self = ModInt::new(0);
+    const ONE: Self = ModInt::new(1);
+    fn checked_add(self, other: Self) -> Option<Self> {
+        Some(self + other)
+    }
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        Some(self * other)
+    }
+}
+
+/// A dense, row-major matrix.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Matrix<T> {
+    cells: Vec<T>,
+    num_rows: usize,
+    num_cols: usize,
+}
+
+impl<T: fmt::Display> fmt::Display for Matrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.cells.chunks(self.num_cols.max(1)).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for (j, x) in row.iter().enumerate() {
+                if j > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{x}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: Scalar> Matrix<T> {
+    pub fn zero(num_rows: usize, num_cols: usize) -> Self {
+        Matrix {
+            cells: vec![T::ZERO; num_rows * num_cols],
+            num_rows,
+            num_cols,
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zero(n, n);
+        for i in 0..n {
+            m[(i, i)] = T::ONE;
+        }
+        m
+    }
+
+    pub fn from_rows<R: AsRef<[T]>>(rows: &[R]) -> AocResult<Self> {
+        let num_cols = rows.first().map_or(0, |r| r.as_ref().len());
+        let mut cells = Vec::with_capacity(rows.len() * num_cols);
+        for (i, row) in rows.iter().enumerate() {
+            let row = row.as_ref();
+            ensure!(
+                row.len() == num_cols,
+                "Row {i} has {} columns, expected {num_cols}",
+                row.len()
+            );
+            cells.extend_from_slice(row);
+        }
+        Ok(Matrix {
+            cells,
+            num_rows: rows.len(),
+            num_cols,
+        })
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.cells[i * self.num_cols..(i + 1) * self.num_cols]
+    }
+
+    /// The matrix product `self * other`.
+    pub fn mul(&self, other: &Self) -> AocResult<Self> {
+        ensure!(
+            self.num_cols == other.num_rows,
+            "Can't multiply a {}x{} matrix by a {}x{} one",
+            self.num_rows,
+            self.num_cols,
+            other.num_rows,
+            other.num_cols
+        );
+        let mut out = Self::zero(self.num_rows, other.num_cols);
+        for i in 0..self.num_rows {
+            for k in 0..self.num_cols {
+                let a = self[(i, k)];
+                if a == T::ZERO {
+                    continue;
+                }
+                for j in 0..other.num_cols {
+                    out[(i, j)] = checked_fma(out[(i, j)], a, other[(k, j)])?;
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    /// The product of `self` and the column vector `v`.
+    pub fn mul_vec(&self, v: &[T]) -> AocResult<Vec<T>> {
+        ensure!(
+            self.num_cols == v.len(),
+            "Can't multiply a {}x{} matrix by a vector of length {}",
+            self.num_rows,
+            self.num_cols,
+            v.len()
+        );
+        (0..self.num_rows)
+            .map(|i| {
+                self.row(i)
+                    .iter()
+                    .zip(v)
+                    .try_fold(T::ZERO, |acc, (&a, &b)| checked_fma(acc, a, b))
+            })
+            .collect()
+    }
+
+    /// `self` multiplied by itself `n` times, by repeated squaring, so that it takes
+    /// O(log n) multiplications. `pow(0)` is the identity.
+    pub fn pow(&self, mut n: u64) -> AocResult<Self> {
+        ensure!(
+            self.num_rows == self.num_cols,
+            "Can't raise a {}x{} matrix to a power",
+            self.num_rows,
+            self.num_cols
+        );
+        let mut out = Self::identity(self.num_rows);
+        let mut square = self.clone();
+        while n > 0 {
+            if n & 1 == 1 {
+                out = out.mul(&square)?;
+            }
+            n >>= 1;
+            if n > 0 {
+                square = square.mul(&square)?;
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// `acc + a * b`, or an error on overflow.
+fn checked_fma<T: Scalar>(acc: T, a: T, b: T) -> AocResult<T> {
+    match a.checked_mul(b).and_then(|p| acc.checked_add(p)) {
+        Some(x) => Ok(x),
+        None => failure!("Overflow computing {acc:?} + {a:?} * {b:?}"),
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    /// Panics if `(row, col)` is outside the matrix.
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert!(row < self.num_rows && col < self.num_cols);
+        &self.cells[row * self.num_cols + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    /// Panics if `(row, col)` is outside the matrix.
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        assert!(row < self.num_rows && col < self.num_cols);
+        &mut self.cells[row * self.num_cols + col]
+    }
+}
+
+#[cfg(test)]
+mod matrix_tests {
+    use super::*;
+
+    #[test]
+    fn products() -> AocResult<()> {
+        let a = Matrix::from_rows(&[[1i64, 2, 3], [4, 5, 6]])?;
+        let b = Matrix::from_rows(&[[7, 8], [9, 10], [11, 12]])?;
+        assert_eq!(a.mul(&b)?, Matrix::from_rows(&[[58, 64], [139, 154]])?);
+        assert_eq!(a.mul_vec(&[1, 0, -1])?, [-2, -2]);
+        assert_eq!(Matrix::identity(2).mul(&a)?, a);
+        assert!(a.mul(&a).is_err());
+        assert!(a.mul_vec(&[1, 2]).is_err());
+        assert!(Matrix::from_rows(&[vec![1], vec![2, 3]]).is_err());
+        assert_eq!(a.to_string(), "1 2 3\n4 5 6");
+        Ok(())
+    }
+
+    #[test]
+    fn powers() -> AocResult<()> {
+        let fib = Matrix::from_rows(&[[1u64, 1], [1, 0]])?;
+        assert_eq!(fib.pow(0)?, Matrix::identity(2));
+        assert_eq!(fib.pow(1)?, fib);
+        assert_eq!(fib.pow(10)?[(0, 1)], 55);
+        assert_eq!(fib.pow(92)?[(0, 1)], 7540113804746346429);
+        // F(94) doesn't fit in a u64.
+        assert!(fib.pow(93).is_err());
+        assert!(Matrix::<u64>::zero(2, 3).pow(2).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use crate::errors::AocResult;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A cache of computed values for recursive solvers. The function that computes a
+/// missing value is handed the `Memo` itself, so it can recurse through it:
+///
+/// ```
+/// use aoc_util::memo::Memo;
+///
+/// fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+///     memo.entry_or_compute(n, |memo| {
+///         if n < 2 { n } else { fib(memo, n - 1) + fib(memo, n - 2) }
+///     })
+/// }
+/// assert_eq!(fib(&mut Memo::new(), 90), 2880067194370816120);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    capacity: Option<usize>,
+}
+
+impl<K: Hash + Eq, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self {
+            cache: HashMap::new(),
+            capacity: None,
+        }
+    }
+
+    /// A `Memo` that holds at most `capacity` values. Once it's full, new values are
+    /// still computed, but not stored.
+    pub fn with_capacity_bound(capacity: usize) -> Self {
+        Self {
+            cache: HashMap::new(),
+            capacity: Some(capacity),
+        }
+    }
+
+    /// The cached value for `key`, computing and caching it with `f` if there isn't one.
+    pub fn entry_or_compute<F: FnOnce(&mut Self) -> V>(&mut self, key: K, f: F) -> V {
+        if let Some(v) = self.cache.get(&key) {
+            return v.clone();
+        }
+        let v = f(self);
+        self.insert(key, v.clone());
+        v
+    }
+
+    /// Like `entry_or_compute`, for computations that can fail. Failures aren't cached.
+    pub fn try_entry_or_compute<F>(&mut self, key: K, f: F) -> AocResult<V>
+    where
+        F: FnOnce(&mut Self) -> AocResult<V>,
+    {
+        if let Some(v) = self.cache.get(&key) {
+            return Ok(v.clone());
+        }
+        let v = f(self)?;
+        self.insert(key, v.clone());
+        Ok(v)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// The number of cached values.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    fn insert(&mut self, key: K, v: V) {
+        if self.capacity.is_none_or(|c| self.cache.len() < c) {
+            self.cache.insert(key, v);
+        }
+    }
+}
+
+#[cfg(test)]
+mod memo_tests {
+    use super::*;
+    use crate::failure;
+
+    /// The number of paths from (0, 0) to (x, y) taking unit steps right or up.
+    fn paths(memo: &mut Memo<(u32, u32), u64>, calls: &mut u32, x: u32, y: u32) -> u64 {
+        *calls += 1;
+        memo.entry_or_compute((x, y), |memo| match (x, y) {
+            (0, _) | (_, 0) => 1,
+            _ => paths(memo, calls, x - 1, y) + paths(memo, calls, x, y - 1),
+        })
+    }
+
+    #[test]
+    fn recursion() {
+        let mut memo = Memo::new();
+        let mut calls = 0;
+        assert_eq!(paths(&mut memo, &mut calls, 16, 16), 601080390);
+        assert!(calls < 1000);
+        assert_eq!(memo.get(&(2, 2)), Some(&6));
+        assert_eq!(memo.len(), 17 * 17 - 1);
+
+        memo.clear();
+        assert!(memo.is_empty());
+    }
+
+    #[test]
+    fn bounded() {
+        let mut memo = Memo::with_capacity_bound(10);
+        let mut calls = 0;
+        assert_eq!(paths(&mut memo, &mut calls, 8, 8), 12870);
+        assert_eq!(memo.len(), 10);
+    }
+
+    #[test]
+    fn fallible() -> AocResult<()> {
+        let mut memo: Memo<i32, i32> = Memo::new();
+        assert_eq!(memo.try_entry_or_compute(3, |_| Ok(9))?, 9);
+        assert_eq!(memo.try_entry_or_compute(3, |_| failure!("recomputed"))?, 9);
+        assert!(memo
+            .try_entry_or_compute(-1, |_| failure!("negative"))
+            .is_err());
+        assert_eq!(memo.get(&-1), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,365 @@
+use crate::checksum::record_checksum;
+use crate::ensure;
+use crate::errors::AocResult;
+use crate::io::{data_dir, find_input, read_input_file};
+
+use std::env;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_USER_AGENT: &str = "github.com/tdanniels/aoc-rs by tdanniels";
+
+/// Time of the most recent request made by any `Client`. Shared so that the rate limit
+/// holds no matter how many clients are constructed.
+static LAST_REQUEST: Mutex<Option<Instant>> = Mutex::new(None);
+
+#[derive(Clone, Debug)]
+pub struct ClientConfig {
+    /// Sent with every request. Defaults to `AOC_USER_AGENT` if set, otherwise to
+    /// `DEFAULT_USER_AGENT`.
+    pub user_agent: String,
+    /// Minimum time between the start of any two requests.
+    pub min_interval: Duration,
+    /// Number of times a transiently failing GET is retried before giving up. POSTs
+    /// are never retried, since a submission that did arrive would count twice.
+    pub max_retries: u32,
+    /// Delay before the first retry. Doubles with every subsequent retry.
+    pub initial_backoff: Duration,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            user_agent: env::var("AOC_USER_AGENT")
+                .unwrap_or_else(|_| DEFAULT_USER_AGENT.to_string()),
+            min_interval: Duration::from_secs(3),
+            max_retries: 3,
+            initial_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    fn is_transient_failure(&self) -> bool {
+        self.status == 429 || (500..600).contains(&self.status)
+    }
+}
+
+/// curl exiting unsuccessfully, with its exit code if it had one.
+#[derive(Debug)]
+struct CurlError {
+    code: Option<i32>,
+    msg: String,
+}
+
+impl CurlError {
+    /// Whether curl gave up on resolving, connecting, a timeout or a dropped
+    /// connection, any of which may go away if tried again.
+    fn is_transient(&self) -> bool {
+        matches!(self.code, Some(6 | 7 | 28 | 35 | 52 | 55 | 56))
+    }
+}
+
+impl fmt::Display for CurlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg)
+    }
+}
+
+impl error::Error for CurlError {}
+
+/// An HTTP client for adventofcode.com. Requests are made by running `curl`, since
+/// this repo doesn't use external crates.
+#[derive(Clone, Debug, Default)]
+pub struct Client {
+    config: ClientConfig,
+}
+
+impl Client {
+    pub fn new(config: ClientConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    pub fn get(&self, url: &str, session: Option<&str>) -> AocResult<Response> {
+        self.request(url, session, None)
+    }
+
+    /// POSTs `form`, which must already be URL encoded (e.g. "level=1&answer=42"). Not
+    /// retried on failure.
+    pub fn post(&self, url: &str, session: Option<&str>, form: &str) -> AocResult<Response> {
+        self.request(url, session, Some(form))
+    }
+
+    fn request(
+        &self,
+        url: &str,
+        session: Option<&str>,
+        form: Option<&str>,
+    ) -> AocResult<Response> {
+        let mut attempt = 0;
+        loop {
+            self.wait_for_rate_limit();
+            let result = self.run_curl(url, session, form);
+            let is_post = form.is_some();
+            if is_post || !is_transient(&result) || attempt >= self.config.max_retries {
+                return result;
+            }
+            thread::sleep(self.backoff_delay(attempt));
+            attempt += 1;
+        }
+    }
+
+    /// Returns the delay before retry number `attempt` (counting from 0).
+    pub fn backoff_delay(&self, attempt: u32) -> Duration {
+        self.config
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+    }
+
+    fn wait_for_rate_limit(&self) {
+        let mut last = LAST_REQUEST.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(last) = *last {
+            let elapsed = last.elapsed();
+            if elapsed < self.config.min_interval {
+                thread::sleep(self.config.min_interval - elapsed);
+            }
+        }
+        *last = Some(Instant::now());
+    }
+
+    fn run_curl(
+        &self,
+        url: &str,
+        session: Option<&str>,
+        form: Option<&str>,
+    ) -> AocResult<Response> {
+        let mut cmd = Command::new("curl");
+        cmd.args(["--silent", "--show-error", "--location"])
+            .args(["--user-agent", &self.config.user_agent])
+            .args(["--write-out", "\n%{http_code}"])
+            // Headers are read from stdin so that the session cookie doesn't show up in
+            // the process list.
+            .args(["--header", "@-"]);
+        if let Some(form) = form {
+            cmd.args(["--data", form]);
+        }
+        cmd.arg(url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let mut child = cmd.spawn()?;
+        {
+            let mut stdin = child.stdin.take().ok_or("No stdin for curl?")?;
+            if let Some(session) = session {
+                writeln!(stdin, "Cookie: session={session}")?;
+            }
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(Box::new(CurlError {
+                code: output.status.code(),
+                msg: format!(
+                    "curl {url} failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+            }));
+        }
+        parse_curl_output(&String::from_utf8(output.stdout)?)
+    }
+}
+
+/// Whether a request that ended in `result` might succeed if made again.
+fn is_transient(result: &AocResult<Response>) -> bool {
+    match result {
+        Ok(response) => response.is_transient_failure(),
+        Err(err) => err
+            .downcast_ref::<CurlError>()
+            .is_some_and(CurlError::is_transient),
+    }
+}
+
+/// Returns the adventofcode.com session cookie: `AOC_SESSION` if set, otherwise the
+/// contents of `$XDG_CONFIG_HOME/aoc/session` (or `~/.config/aoc/session`).
+pub fn session_token() -> AocResult<String> {
+    let from_file = session_config_path().and_then(|p| fs::read_to_string(p).ok());
+    choose_session(env::var("AOC_SESSION").ok(), from_file).ok_or_else(|| {
+        format!(
+            "No session cookie: set AOC_SESSION or write it to {:?}",
+            session_config_path()
+        )
+        .into()
+    })
+}
+
+fn session_config_path() -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|h| Path::new(&h).join(".config")))?;
+    Some(config_dir.join("aoc").join("session"))
+}
+
+/// Prefers the environment over the config file, ignoring blank values from either.
+fn choose_session(from_env: Option<String>, from_file: Option<String>) -> Option<String> {
+    [from_env, from_file]
+        .into_iter()
+        .flatten()
+        .map(|s| s.trim().to_string())
+        .find(|s| !s.is_empty())
+}
+
+pub fn input_url(year: u32, day: u32) -> String {
+    format!("https://adventofcode.com/{year}/day/{day}/input")
+}
+
+/// Where `fetch_input` caches a day's input, under `io::data_dir()`. This is the same
+/// file `io::get_input_file` names for that day.
+pub fn input_path(day: u32) -> PathBuf {
+    data_dir().join(format!("{day:02}_input.txt"))
+}
+
+/// Returns the puzzle input for `day` of `year`, downloading it to `input_path(day)`
+/// first if it isn't there yet.
+pub fn fetch_input(year: u32, day: u32) -> AocResult<String> {
+    fetch_input_into(year, day, &input_path(day))
+}
+
+/// Like `fetch_input`, but caches the input at `path` (or reads it from `path` + ".gz"
+/// if that's there instead), recording its checksum in the directory's manifest.
+pub fn fetch_input_into(year: u32, day: u32, path: &Path) -> AocResult<String> {
+    if find_input(path).is_some() {
+        return read_input_file(path);
+    }
+    let url = input_url(year, day);
+    let response = Client::default().get(&url, Some(&session_token()?))?;
+    ensure!(
+        response.is_success(),
+        "GET {url} failed with HTTP {}",
+        response.status
+    );
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, &response.body)?;
+    record_checksum(path, &response.body)?;
+    Ok(response.body)
+}
+
+/// Splits curl's stdout into the body and the status code appended by `--write-out`.
+fn parse_curl_output(output: &str) -> AocResult<Response> {
+    let (body, status) = output
+        .rsplit_once('\n')
+        .ok_or("No status code in curl output?")?;
+    Ok(Response {
+        status: status.trim().parse()?,
+        body: body.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod net_tests {
+    use super::*;
+
+    #[test]
+    fn backoff() {
+        let client = Client::new(ClientConfig {
+            initial_backoff: Duration::from_millis(100),
+            ..ClientConfig::default()
+        });
+        assert_eq!(client.backoff_delay(0), Duration::from_millis(100));
+        assert_eq!(client.backoff_delay(1), Duration::from_millis(200));
+        assert_eq!(client.backoff_delay(3), Duration::from_millis(800));
+    }
+
+    #[test]
+    fn session_and_paths() {
+        let s = |v: &str| Some(v.to_string());
+        assert_eq!(choose_session(s("abc\n"), s("def")), s("abc"));
+        assert_eq!(choose_session(s("  "), s("def\n")), s("def"));
+        assert_eq!(choose_session(None, s("def")), s("def"));
+        assert_eq!(choose_session(None, s("")), None);
+        assert_eq!(
+            input_url(2021, 6),
+            "https://adventofcode.com/2021/day/6/input"
+        );
+        assert_eq!(input_path(6), data_dir().join("06_input.txt"));
+    }
+
+    #[test]
+    fn cached_input() -> AocResult<()> {
+        let dir = env::temp_dir().join(format!("aoc_net_tests_{}", std::process::id()));
+        let path = dir.join("06_input.txt");
+        fs::create_dir_all(&dir)?;
+        fs::write(&path, "3,4,3,1,2\n")?;
+        // Already cached, so this mustn't touch the network.
+        let input = fetch_input_into(2021, 6, &path);
+        fs::remove_dir_all(&dir)?;
+        assert_eq!(input?, "3,4,3,1,2\n");
+        Ok(())
+    }
+
+    #[test]
+    fn curl_output() -> AocResult<()> {
+        assert_eq!(
+            parse_curl_output("1,2,3\n4\n200")?,
+            Response {
+                status: 200,
+                body: "1,2,3\n4".to_string()
+            }
+        );
+        let r = parse_curl_output("\n503")?;
+        assert!(r.is_transient_failure() && !r.is_success());
+        assert!(parse_curl_output("no status").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn transient_failures() {
+        let status = |status| {
+            Ok(Response {
+                status,
+                body: String::new(),
+            })
+        };
+        assert!(is_transient(&status(429)));
+        assert!(is_transient(&status(502)));
+        assert!(!is_transient(&status(200)));
+        assert!(!is_transient(&status(404)));
+
+        let curl = |code| -> AocResult<Response> {
+            Err(Box::new(CurlError {
+                code,
+                msg: "curl failed".to_string(),
+            }))
+        };
+        // Couldn't connect, and timed out.
+        assert!(is_transient(&curl(Some(7))));
+        assert!(is_transient(&curl(Some(28))));
+        // A bad URL won't get any better, and nor will curl being killed.
+        assert!(!is_transient(&curl(Some(3))));
+        assert!(!is_transient(&curl(None)));
+        // Nor will anything that isn't curl's doing.
+        assert!(!is_transient(&Err("No stdin for curl?".into())));
+    }
+}
@@ -0,0 +1,217 @@
+//! Reading the block-letter banners that some puzzles draw as their answer.
+
+use crate::errors::{malformed_at, AocResult};
+use crate::grid::Grid;
+use crate::point::Point;
+use crate::{ensure, failure};
+
+/// Something that can be read as a banner of lit and unlit pixels.
+pub trait Banner {
+    /// The banner's pixels, row by row. Rows may differ in length.
+    fn pixels(&self) -> AocResult<Vec<Vec<bool>>>;
+}
+
+/// Rows of '#' (lit) and '.' or ' ' (unlit), one row per line.
+impl Banner for str {
+    fn pixels(&self) -> AocResult<Vec<Vec<bool>>> {
+        self.lines()
+            .enumerate()
+            .map(|(i, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(j, c)| match c {
+                        '#' => Ok(true),
+                        '.' | ' ' => Ok(false),
+                        _ => malformed_at(i + 1, j + 1, format!("bad pixel {c:?}")),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Any non-zero cell is lit.
+impl Banner for Grid {
+    fn pixels(&self) -> AocResult<Vec<Vec<bool>>> {
+        (0..self.num_rows())
+            .map(|i| {
+                (0..self.num_cols())
+                    .map(|j| Ok(self.at(Point { i, j })? != 0))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Reads the letters drawn in `banner`, in either the 4x6 font (2016 day 8, 2019 days 8
+/// and 11, 2021 day 13, 2022 day 10) or the 6x10 font (2018 day 10). Letters are told
+/// apart by the blank columns between them, and blank rows and columns around the
+/// banner are ignored.
+pub fn recognize<B: Banner + ?Sized>(banner: &B) -> AocResult<String> {
+    let mut rows = banner.pixels()?;
+    while rows.last().is_some_and(|r| !r.contains(&true)) {
+        rows.pop();
+    }
+    let top = rows
+        .iter()
+        .position(|r| r.contains(&true))
+        .unwrap_or(rows.len());
+    rows.drain(..top);
+    ensure!(!rows.is_empty(), "Nothing drawn");
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    for row in &mut rows {
+        row.resize(width, false);
+    }
+
+    let font: &[(char, &[&str])] = match rows.len() {
+        SMALL_HEIGHT => &SMALL,
+        LARGE_HEIGHT => &LARGE,
+        h => failure!("No font is {h} pixels tall"),
+    };
+    let blank = |col: usize| rows.iter().all(|r| !r[col]);
+    let mut out = String::new();
+    let mut col = 0;
+    while col < width {
+        if blank(col) {
+            col += 1;
+            continue;
+        }
+        let start = col;
+        while col < width && !blank(col) {
+            col += 1;
+        }
+        let matches = |glyph: &[&str]| {
+            glyph.iter().zip(&rows).all(|(g, r)| {
+                g.len() == col - start
+                    && g.bytes()
+                        .zip(&r[start..col])
+                        .all(|(b, &lit)| (b == b'#') == lit)
+            })
+        };
+        match font.iter().find(|(_, glyph)| matches(glyph)) {
+            Some(&(letter, _)) => out.push(letter),
+            None => failure!("Unrecognized letter at column {}", start + 1),
+        }
+    }
+    Ok(out)
+}
+
+const SMALL_HEIGHT: usize = 6;
+const LARGE_HEIGHT: usize = 10;
+
+#[rustfmt::skip]
+const SMALL: [(char, &[&str]); 16] = [
+    ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[rustfmt::skip]
+const LARGE: [(char, &[&str]); 15] = [
+    ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#",
+            "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', &["#####.", "#....#", "#....#", "#....#", "#####.",
+            "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', &[".####.", "#....#", "#.....", "#.....", "#.....",
+            "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', &["######", "#.....", "#.....", "#.....", "#####.",
+            "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', &["######", "#.....", "#.....", "#.....", "#####.",
+            "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', &[".####.", "#....#", "#.....", "#.....", "#.....",
+            "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', &["#....#", "#....#", "#....#", "#....#", "######",
+            "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', &["...###", "....#.", "....#.", "....#.", "....#.",
+            "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....",
+            "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', &["#.....", "#.....", "#.....", "#.....", "#.....",
+            "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#",
+            "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', &["#####.", "#....#", "#....#", "#....#", "#####.",
+            "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', &["#####.", "#....#", "#....#", "#....#", "#####.",
+            "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..",
+            "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', &["######", ".....#", ".....#", "....#.", "...#..",
+            "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+#[cfg(test)]
+mod ocr_tests {
+    use super::*;
+
+    /// Draws every letter of `font`, `gap` columns apart.
+    fn draw(font: &[(char, &[&str])], gap: usize) -> String {
+        let height = font[0].1.len();
+        (0..height)
+            .map(|row| {
+                let glyphs: Vec<&str> = font.iter().map(|(_, g)| g[row]).collect();
+                glyphs.join(&".".repeat(gap))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn fonts() -> AocResult<()> {
+        assert_eq!(recognize(draw(&SMALL, 1).as_str())?, "ABCEFGHJKLOPRSUZ");
+        assert_eq!(recognize(draw(&LARGE, 2).as_str())?, "ABCEFGHJKLNPRXZ");
+        Ok(())
+    }
+
+    #[test]
+    fn banners() -> AocResult<()> {
+        let banner = "\
+#..#.####.#....####.#..#...##.###..#..#
+#..#....#.#....#....#..#....#.#..#.#.#.
+####...#..#....###..####....#.#..#.##..
+#..#..#...#....#....#..#....#.###..#.#.
+#..#.#....#....#....#..#.#..#.#.#..#.#.
+#..#.####.####.####.#..#..##..#..#.#..#
+";
+        assert_eq!(recognize(banner)?, "HZLEHJRK");
+
+        // Surrounding blank space and ragged rows don't matter.
+        let padded = "\n  .##.\n  #..#\n  #..#\n  ####\n  #..#  \n  #..#\n\n";
+        assert_eq!(recognize(padded)?, "A");
+
+        let cells: Vec<u8> = ["###.", "#..#", "###.", "#..#", "#..#", "###."]
+            .concat()
+            .bytes()
+            .map(|b| u8::from(b == b'#'))
+            .collect();
+        let grid = Grid::from_slice(&cells, 6, 4)?;
+        assert_eq!(recognize(&grid)?, "B");
+        Ok(())
+    }
+
+    #[test]
+    fn errors() {
+        assert!(recognize("").is_err());
+        assert!(recognize("#\n#\n#").is_err());
+        let err = recognize("####\n#..x").unwrap_err();
+        assert_eq!(err.to_string(), "Line 2, column 4: bad pixel 'x'");
+        let square = "#####\n#...#\n#...#\n#...#\n#...#\n#####";
+        assert_eq!(
+            recognize(square).unwrap_err().to_string(),
+            "Unrecognized letter at column 1"
+        );
+    }
+}
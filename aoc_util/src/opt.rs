@@ -0,0 +1,110 @@
+//! Choosing the point on a line that's cheapest to gather everything at, as with the
+//! crab submarines of 2021 day 7.
+
+use std::ops::RangeInclusive;
+
+/// The total cost of moving every one of `values` to `target`, where `cost` gives the
+/// cost of moving a distance.
+pub fn total_cost<F: Fn(i64) -> i64>(values: &[i64], target: i64, cost: F) -> i64 {
+    values.iter().map(|&x| cost((x - target).abs())).sum()
+}
+
+/// The integer in `range` where the convex function `f` is smallest, and its value
+/// there, or None if `range` is empty. Ties go to the smallest argument. This is a binary
+/// search on the slope of `f` (the integer form of ternary search), so it makes
+/// O(log n) calls to `f`.
+pub fn minimize_convex<F: FnMut(i64) -> i64>(
+    range: RangeInclusive<i64>,
+    mut f: F,
+) -> Option<(i64, i64)> {
+    let (mut lo, mut hi) = range.into_inner();
+    if lo > hi {
+        return None;
+    }
+    // The answer is the first point from which f stops decreasing.
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if f(mid) <= f(mid + 1) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    Some((lo, f(lo)))
+}
+
+/// The target that minimizes `total_cost(values, target, cost)`, and that total, or
+/// None if `values` is empty. `cost` must be convex and non-decreasing; then the total
+/// is convex in the target, and the best target lies between the smallest and largest
+/// values, so this takes O(n log(max - min)) time.
+pub fn minimize_1d<F: Fn(i64) -> i64>(values: &[i64], cost: F) -> Option<(i64, i64)> {
+    let lo = *values.iter().min()?;
+    let hi = *values.iter().max()?;
+    minimize_convex(lo..=hi, |t| total_cost(values, t, &cost))
+}
+
+/// A median of `values`, which minimizes the total distance to them, or None if
+/// `values` is empty. Runs in O(n) time.
+pub fn median(values: &[i64]) -> Option<i64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut values = values.to_vec();
+    let mid = (values.len() - 1) / 2;
+    Some(*values.select_nth_unstable(mid).1)
+}
+
+/// Like `minimize_1d`, for costs like the triangular numbers `d * (d + 1) / 2`, where the
+/// best real-valued target is within 1/2 of the mean of `values`. Only the integers
+/// around the mean are tried, so this takes O(n) time.
+pub fn minimize_near_mean<F: Fn(i64) -> i64>(values: &[i64], cost: F) -> Option<(i64, i64)> {
+    let n = i64::try_from(values.len()).ok().filter(|&n| n > 0)?;
+    let floor_mean = values.iter().sum::<i64>().div_euclid(n);
+    minimize_convex(floor_mean - 1..=floor_mean + 2, |t| {
+        total_cost(values, t, &cost)
+    })
+}
+
+#[cfg(test)]
+mod opt_tests {
+    use super::*;
+
+    const CRABS: [i64; 10] = [16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+
+    fn triangular(d: i64) -> i64 {
+        d * (d + 1) / 2
+    }
+
+    #[test]
+    fn convex() {
+        assert_eq!(
+            minimize_convex(-10..=10, |x| (x - 3) * (x - 3)),
+            Some((3, 0))
+        );
+        assert_eq!(minimize_convex(5..=10, |x| (x - 3) * (x - 3)), Some((5, 4)));
+        assert_eq!(
+            minimize_convex(-10..=2, |x| (x - 3) * (x - 3)),
+            Some((2, 1))
+        );
+        assert_eq!(
+            minimize_convex(0..=10, |x| (x - 4).abs().max(2)),
+            Some((2, 2))
+        );
+        assert_eq!(minimize_convex(7..=7, |x| x), Some((7, 7)));
+        let (lo, hi) = (1, 0);
+        assert_eq!(minimize_convex(lo..=hi, |x| x), None);
+    }
+
+    #[test]
+    fn gathering() {
+        assert_eq!(median(&CRABS), Some(2));
+        assert_eq!(total_cost(&CRABS, 2, |d| d), 37);
+        assert_eq!(minimize_1d(&CRABS, |d| d).map(|(_, c)| c), Some(37));
+        assert_eq!(minimize_1d(&CRABS, triangular), Some((5, 168)));
+        assert_eq!(minimize_near_mean(&CRABS, triangular), Some((5, 168)));
+        assert_eq!(minimize_near_mean(&[-3, -4], triangular), Some((-4, 1)));
+        assert_eq!(median(&[]), None);
+        assert_eq!(minimize_1d(&[], |d| d), None);
+        assert_eq!(minimize_near_mean(&[], |d| d), None);
+    }
+}
@@ -0,0 +1,59 @@
+//! Data parallelism on scoped std threads, for the `parallel` feature.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Number of worker threads to use: the available parallelism, or 1 if unknown.
+pub fn num_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Maps `f` over `items` on up to `num_threads()` threads, returning the results in
+/// the order of `items`. Items are handed out one at a time, so uneven workloads
+/// still balance across threads.
+pub fn par_map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> =
+        Mutex::new((0..items.len()).map(|_| None).collect());
+    thread::scope(|s| {
+        for _ in 0..num_threads().min(items.len()) {
+            s.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(i) else {
+                    break;
+                };
+                let r = f(item);
+                results.lock().unwrap()[i] = Some(r);
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        // Every index below items.len() was handed out exactly once.
+        .map(|r| r.unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+mod par_tests {
+    use super::*;
+
+    #[test]
+    fn map() {
+        let items: Vec<u64> = (0..1000).collect();
+        assert_eq!(
+            par_map(&items, |x| x * x),
+            items.iter().map(|x| x * x).collect::<Vec<_>>()
+        );
+        assert!(par_map(&[] as &[u8], |x| *x).is_empty());
+        assert!(num_threads() >= 1);
+    }
+}
@@ -0,0 +1,312 @@
+//! Helpers for the line formats that keep coming up in puzzle inputs.
+
+use crate::errors::{malformed_at, AocError, AocResult};
+use crate::{ensure, failure};
+use std::error::Error;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// Every integer in `s`, in order, whatever separates them: "x=-5..10, y=3" gives
+/// `[-5, 10, 3]`. A '-' or '+' directly before a digit is taken as its sign. Numbers
+/// that don't fit in `T`, including negative ones when `T` is unsigned, are skipped.
+pub fn ints<T: FromStr>(s: &str) -> Vec<T> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        let mut start = i;
+        if start > 0 && matches!(bytes[start - 1], b'-' | b'+') {
+            start -= 1;
+        }
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if let Ok(n) = s[start..i].parse() {
+            out.push(n);
+        }
+    }
+    out
+}
+
+/// Parses each `sep`-separated piece of `s`. Errors give the column of the bad piece, so
+/// that `LineContext::at_line` can place them in the whole input.
+pub fn split_parse<T>(s: &str, sep: &str) -> AocResult<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn Error>>,
+{
+    let mut offset = 0;
+    s.split(sep)
+        .map(|piece| {
+            let start = offset;
+            offset += piece.len() + sep.len();
+            parse_piece(s, start, piece)
+        })
+        .collect()
+}
+
+/// Parses `s` as two values separated by the first `sep`, like "3,4" or "a-b".
+pub fn parse_pair<A, B>(s: &str, sep: &str) -> AocResult<(A, B)>
+where
+    A: FromStr,
+    A::Err: Into<Box<dyn Error>>,
+    B: FromStr,
+    B::Err: Into<Box<dyn Error>>,
+{
+    let Some((a, b)) = s.split_once(sep) else {
+        return malformed_at(1, 1, format!("expected two values separated by {sep:?}"));
+    };
+    Ok((parse_piece(s, 0, a)?, parse_piece(s, s.len() - b.len(), b)?))
+}
+
+/// Matches `s` against `template`, where each "{}" in the template stands for a field
+/// to parse, and returns the fields as a tuple:
+/// `let (axis, at): (char, usize) = capture("fold along {}={}", line)?;`
+/// A field runs up to the first occurrence of the text after it in the template, so
+/// two fields can't be adjacent.
+pub fn capture<T: FromCaptures>(template: &str, s: &str) -> AocResult<T> {
+    let mut literals = template.split("{}");
+    // `split` always yields at least one piece.
+    let first = literals.next().unwrap_or_default();
+    if !s.starts_with(first) {
+        return malformed_at(1, 1, format!("expected {first:?}"));
+    }
+    let mut pos = first.len();
+    let mut fields = Vec::new();
+    let mut literals = literals.peekable();
+    while let Some(literal) = literals.next() {
+        let len = if literal.is_empty() && literals.peek().is_none() {
+            s.len() - pos
+        } else {
+            ensure!(
+                !literal.is_empty(),
+                "Adjacent fields in template {template:?}"
+            );
+            match s[pos..].find(literal) {
+                Some(len) => len,
+                None => {
+                    let column = s[..pos].chars().count() + 1;
+                    return malformed_at(1, column, format!("expected {literal:?}"));
+                }
+            }
+        };
+        fields.push((pos, &s[pos..pos + len]));
+        pos += len + literal.len();
+    }
+    if pos != s.len() {
+        let column = s[..pos].chars().count() + 1;
+        return malformed_at(1, column, "trailing characters");
+    }
+    T::from_captures(s, &fields)
+}
+
+/// Tuples of fields that `capture` can parse.
+pub trait FromCaptures: Sized {
+    /// Parses `fields`, each of which is a byte offset into `s` and the text there.
+    fn from_captures(s: &str, fields: &[(usize, &str)]) -> AocResult<Self>;
+}
+
+macro_rules! impl_from_captures {
+    ($n:literal; $($t:ident $i:tt),+) => {
+        impl<$($t),+> FromCaptures for ($($t,)+)
+        where
+            $($t: FromStr, $t::Err: Into<Box<dyn Error>>,)+
+        {
+            fn from_captures(s: &str, fields: &[(usize, &str)]) -> AocResult<Self> {
+                if fields.len() != $n {
+                    failure!("Template has {} fields, expected {}", fields.len(), $n);
+                }
+                Ok(($(parse_piece(s, fields[$i].0, fields[$i].1)?,)+))
+            }
+        }
+    };
+}
+
+impl_from_captures!(1; A 0);
+impl_from_captures!(2; A 0, B 1);
+impl_from_captures!(3; A 0, B 1, C 2);
+impl_from_captures!(4; A 0, B 1, C 2, D 3);
+impl_from_captures!(5; A 0, B 1, C 2, D 3, E 4);
+impl_from_captures!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+
+/// Parses `piece`, which starts at byte `start` of `s`. Errors from parsers that report
+/// their own columns (e.g. a `FromStr` built on these helpers) are shifted to match.
+fn parse_piece<T>(s: &str, start: usize, piece: &str) -> AocResult<T>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn Error>>,
+{
+    piece.parse::<T>().map_err(|e| {
+        let column = s[..start].chars().count() + 1;
+        let err = match AocError::from_boxed(e.into()) {
+            AocError::Malformed {
+                line: 1,
+                column: inner,
+                msg,
+            } => AocError::malformed_at(1, column + inner.unwrap_or(1) - 1, msg),
+            err => AocError::malformed_at(1, column, format!("bad value {piece:?}: {err}")),
+        };
+        err.into()
+    })
+}
+
+/// Splits `lines` into the blocks between blank lines. Runs of blank lines, and blank
+/// lines at the start or end, don't make empty blocks.
+pub fn blocks<S, I>(lines: I) -> Vec<Vec<String>>
+where
+    S: AsRef<str>,
+    I: IntoIterator<Item = S>,
+{
+    numbered_blocks(lines)
+        .into_iter()
+        .map(|(_, block)| block)
+        .collect()
+}
+
+/// Like `blocks`, but pairs each block with the (1-based) line it starts on, for use
+/// with `LineContext::at_line`.
+pub fn numbered_blocks<S, I>(lines: I) -> Vec<(usize, Vec<String>)>
+where
+    S: AsRef<str>,
+    I: IntoIterator<Item = S>,
+{
+    let mut out = Vec::new();
+    let mut block = Vec::new();
+    let mut start = 1;
+    for (i, line) in lines.into_iter().enumerate() {
+        let line = line.as_ref();
+        if line.trim().is_empty() {
+            if !block.is_empty() {
+                out.push((start, std::mem::take(&mut block)));
+            }
+            continue;
+        }
+        if block.is_empty() {
+            start = i + 1;
+        }
+        block.push(line.to_string());
+    }
+    if !block.is_empty() {
+        out.push((start, block));
+    }
+    out
+}
+
+/// `blocks` of the lines read from `reader`.
+pub fn read_blocks<R: BufRead>(reader: R) -> AocResult<Vec<Vec<String>>> {
+    let lines = reader.lines().collect::<Result<Vec<_>, _>>()?;
+    Ok(blocks(lines))
+}
+
+#[cfg(test)]
+mod parse_tests {
+    use super::*;
+
+    #[test]
+    fn extract_ints() {
+        assert_eq!(
+            ints::<i64>("target area: x=20..30, y=-10..-5"),
+            [20, 30, -10, -5]
+        );
+        assert_eq!(ints::<u32>("Player 2 starting position: 10"), [2, 10]);
+        assert_eq!(ints::<i32>("forward +5 -x 7-3"), [5, 7, -3]);
+        assert_eq!(ints::<u8>("-1 255 256 0"), [255, 0]);
+        assert!(ints::<i32>("no numbers - here").is_empty());
+    }
+
+    #[test]
+    fn split() -> AocResult<()> {
+        assert_eq!(split_parse::<u8>("3,4,3,1,2", ",")?, [3, 4, 3, 1, 2]);
+        assert_eq!(split_parse::<String>("a -> b", " -> ")?, ["a", "b"]);
+        let err = split_parse::<u8>("3,4,x,1", ",").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 1, column 5: bad value \"x\": invalid digit found in string"
+        );
+
+        assert_eq!(parse_pair::<usize, i32>("6,-10", ",")?, (6, -10));
+        assert_eq!(
+            parse_pair::<String, char>("fold along y=7", "=")?,
+            ("fold along y".to_string(), '7')
+        );
+        let err = parse_pair::<u8, u8>("6,1,0", ",").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 1, column 3: bad value \"1,0\": invalid digit found in string"
+        );
+        // Columns from a nested parser are relative to the whole string.
+        #[derive(Debug)]
+        struct P(u8, u8);
+        impl FromStr for P {
+            type Err = Box<dyn Error>;
+            fn from_str(s: &str) -> AocResult<Self> {
+                let (a, b) = parse_pair(s, ",")?;
+                Ok(P(a, b))
+            }
+        }
+        let ps = split_parse::<P>("1,2 -> 3,4", " -> ")?;
+        assert_eq!((ps[1].0, ps[1].1), (3, 4));
+        let err = split_parse::<P>("1,2 -> 3,x", " -> ").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 1, column 10: bad value \"x\": invalid digit found in string"
+        );
+
+        let err = parse_pair::<u8, u8>("6", ",").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 1, column 1: expected two values separated by \",\""
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn split_blocks() -> AocResult<()> {
+        let input = "\na\nb\n\n\nc\n  \nd\ne\n\n";
+        assert_eq!(
+            numbered_blocks(input.lines()),
+            [
+                (2, vec!["a".to_string(), "b".to_string()]),
+                (6, vec!["c".to_string()]),
+                (8, vec!["d".to_string(), "e".to_string()]),
+            ]
+        );
+        assert_eq!(blocks(input.lines()), read_blocks(input.as_bytes())?);
+        assert!(blocks(["", " "]).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn captures() -> AocResult<()> {
+        let line = "target area: x=20..30, y=-10..-5";
+        let t: (i64, i64, i64, i64) = capture("target area: x={}..{}, y={}..{}", line)?;
+        assert_eq!(t, (20, 30, -10, -5));
+        assert_eq!(
+            capture::<(char, usize)>("fold along {}={}", "fold along y=7")?,
+            ('y', 7)
+        );
+        assert_eq!(
+            capture::<(String,)>("{} -> x", "ab -> x")?,
+            ("ab".to_string(),)
+        );
+
+        let err = capture::<(u8, u8)>("{},{}", "1,x").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 1, column 3: bad value \"x\": invalid digit found in string"
+        );
+        let err = capture::<(u8, u8)>("{}..{}", "1-2").unwrap_err();
+        assert_eq!(err.to_string(), "Line 1, column 1: expected \"..\"");
+        let err = capture::<(u8,)>("a={};", "a=1;b").unwrap_err();
+        assert_eq!(err.to_string(), "Line 1, column 5: trailing characters");
+        let err = capture::<(u8,)>("b={}", "a=1").unwrap_err();
+        assert_eq!(err.to_string(), "Line 1, column 1: expected \"b=\"");
+        assert!(capture::<(u8, u8)>("{}{}", "12").is_err());
+        assert!(capture::<(u8, u8)>("{}", "12").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+//! Populations that move between buckets at each step, like the lanternfish of 2021 day 6.
+
+use crate::errors::AocResult;
+use crate::matrix::{Matrix, Scalar};
+use crate::{ensure, failure};
+
+/// Advances the counts in `buckets` by `n` steps. At each step, whatever is in bucket `i`
+/// moves to every bucket listed in `transition[i]`: listing two buckets doubles it, and
+/// listing none drops it. Small `n` are stepped through one at a time; large ones are
+/// done in O(log n) matrix products instead.
+pub fn step_counts<T: Scalar>(
+    buckets: &[T],
+    transition: &[&[usize]],
+    n: u64,
+) -> AocResult<Vec<T>> {
+    let len = buckets.len();
+    ensure!(
+        transition.len() == len,
+        "{} transitions for {len} buckets",
+        transition.len()
+    );
+    if let Some(&bad) = transition
+        .iter()
+        .flat_map(|t| t.iter())
+        .find(|&&b| b >= len)
+    {
+        failure!("Transition to bucket {bad}, but there are only {len} buckets");
+    }
+
+    // Stepping costs about n * (number of moves); each matrix product costs len^3, and
+    // there are about 2 * log2(n) of them.
+    let moves: u64 = transition.iter().map(|t| t.len() as u64).sum();
+    let log_n = u64::from(u64::BITS - n.leading_zeros());
+    if n.saturating_mul(moves) <= (len as u64).pow(3).saturating_mul(2 * log_n) {
+        iterate(buckets, transition, n)
+    } else {
+        by_matrix(buckets, transition, n)
+    }
+}
+
+fn iterate<T: Scalar>(buckets: &[T], transition: &[&[usize]], n: u64) -> AocResult<Vec<T>> {
+    let mut counts = buckets.to_vec();
+    for step in 0..n {
+        let mut next = vec![T::ZERO; counts.len()];
+        for (&count, targets) in counts.iter().zip(transition) {
+            for &t in targets.iter() {
+                let Some(sum) = next[t].checked_add(count) else {
+                    failure!("Counts overflow after {} steps", step + 1);
+                };
+                next[t] = sum;
+            }
+        }
+        counts = next;
+    }
+    Ok(counts)
+}
+
+fn by_matrix<T: Scalar>(
+    buckets: &[T],
+    transition: &[&[usize]],
+    n: u64,
+) -> AocResult<Vec<T>> {
+    let mut step: Matrix<T> = Matrix::zero(buckets.len(), buckets.len());
+    for (from, targets) in transition.iter().enumerate() {
+        for &to in targets.iter() {
+            let Some(moves) = step[(to, from)].checked_add(T::ONE) else {
+                failure!("Too many moves from bucket {from} to bucket {to}");
+            };
+            step[(to, from)] = moves;
+        }
+    }
+    step.pow(n)?.mul_vec(buckets)
+}
+
+#[cfg(test)]
+mod recurrence_tests {
+    use super::*;
+    use crate::math::ModInt;
+
+    // Rabbits: the young (bucket 0) grow up, and each adult (bucket 1) stays and has a
+    // young one.
+    const RABBITS: [&[usize]; 2] = [&[1], &[0, 1]];
+
+    #[test]
+    fn steps() -> AocResult<()> {
+        assert_eq!(step_counts(&[1u64, 0], &RABBITS, 0)?, [1, 0]);
+        assert_eq!(step_counts(&[1u64, 0], &RABBITS, 10)?, [34, 55]);
+        for n in [0, 1, 5, 50, 92] {
+            let start = [1u64, 0];
+            assert_eq!(
+                iterate(&start, &RABBITS, n)?,
+                by_matrix(&start, &RABBITS, n)?,
+                "{n} steps"
+            );
+        }
+        assert!(step_counts(&[1u64, 0], &RABBITS, 100).is_err());
+        assert!(iterate(&[1u64, 0], &RABBITS, 100).is_err());
+
+        type M = ModInt<1_000_000_007>;
+        let start = [M::new(1), M::new(0)];
+        let counts = step_counts(&start, &RABBITS, 1_000_000_000_000)?;
+        // F(10^12) mod 10^9 + 7.
+        assert_eq!(counts[1].value(), 730695249);
+
+        assert!(step_counts(&[1u64], &RABBITS, 1).is_err());
+        assert!(step_counts(&[1u64, 0], &[&[1], &[2]], 1).is_err());
+        Ok(())
+    }
+}
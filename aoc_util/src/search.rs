@@ -0,0 +1,621 @@
+use crate::arena::{Arena, Id};
+use crate::fasthash::{FastBuildHasher, FastMap, FastSet};
+use std::cmp::{max, Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+/// Counters describing how much work a search did. Useful for comparing heuristics and
+/// pruning strategies without relying on wall-clock time alone.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SearchStats {
+    /// Number of states popped from the frontier (or visited, for recursive searches).
+    pub nodes_expanded: u64,
+    /// Number of states skipped thanks to a cache or visited set.
+    pub cache_hits: u64,
+    /// Largest size the frontier reached.
+    pub max_frontier: usize,
+}
+
+impl SearchStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_expansion(&mut self) {
+        self.nodes_expanded += 1;
+    }
+
+    pub fn record_cache_hit(&mut self) {
+        self.cache_hits += 1;
+    }
+
+    pub fn record_frontier(&mut self, frontier_len: usize) {
+        self.max_frontier = max(self.max_frontier, frontier_len);
+    }
+}
+
+impl fmt::Display for SearchStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "nodes expanded: {}, cache hits: {}, max frontier: {}",
+            self.nodes_expanded, self.cache_hits, self.max_frontier
+        )
+    }
+}
+
+/// Finds the cheapest path from `start` to any state satisfying `is_goal`, where
+/// `successors` returns `(next state, step cost)` pairs. Returns the goal state reached
+/// and the total cost, or None if no goal is reachable.
+pub fn dijkstra<S, F, G>(start: S, successors: F, is_goal: G) -> Option<(S, u64)>
+where
+    S: Clone + Eq + Hash,
+    F: Fn(&S) -> Vec<(S, u64)>,
+    G: Fn(&S) -> bool,
+{
+    dijkstra_with_stats(start, successors, is_goal, &mut SearchStats::new())
+}
+
+pub fn dijkstra_with_stats<S, F, G>(
+    start: S,
+    successors: F,
+    is_goal: G,
+    stats: &mut SearchStats,
+) -> Option<(S, u64)>
+where
+    S: Clone + Eq + Hash,
+    F: Fn(&S) -> Vec<(S, u64)>,
+    G: Fn(&S) -> bool,
+{
+    astar_with_stats(start, successors, |_| 0, is_goal, stats)
+}
+
+/// Like `dijkstra`, but guided by `heuristic`, an estimate of the remaining cost to a
+/// goal. The result is only guaranteed to be optimal if the heuristic never
+/// overestimates.
+pub fn astar<S, F, H, G>(
+    start: S,
+    successors: F,
+    heuristic: H,
+    is_goal: G,
+) -> Option<(S, u64)>
+where
+    S: Clone + Eq + Hash,
+    F: Fn(&S) -> Vec<(S, u64)>,
+    H: Fn(&S) -> u64,
+    G: Fn(&S) -> bool,
+{
+    astar_with_stats(
+        start,
+        successors,
+        heuristic,
+        is_goal,
+        &mut SearchStats::new(),
+    )
+}
+
+pub fn astar_with_stats<S, F, H, G>(
+    start: S,
+    successors: F,
+    heuristic: H,
+    is_goal: G,
+    stats: &mut SearchStats,
+) -> Option<(S, u64)>
+where
+    S: Clone + Eq + Hash,
+    F: Fn(&S) -> Vec<(S, u64)>,
+    H: Fn(&S) -> u64,
+    G: Fn(&S) -> bool,
+{
+    // Every state found is kept, once, in an arena, so the queue only has to order plain
+    // handles and `S` doesn't need to be `Ord`. The best costs are found through a map
+    // from a state's hash to the last state seen with that hash, which links to any
+    // earlier ones. Looking a state up compares it against the states with its hash, so
+    // no state is cloned to serve as a key.
+    let hasher = FastBuildHasher::default();
+    let mut seen = Arena::new();
+    let mut latest = FastMap::default();
+    let h = heuristic(&start);
+    let start_hash = hasher.hash_one(&start);
+    let start = seen.alloc(Seen {
+        state: start,
+        cost: 0,
+        same_hash: None,
+    });
+    latest.insert(start_hash, start);
+    let mut q = BinaryHeap::from([Reverse((h, 0, start))]);
+
+    while let Some(Reverse((_, cost, id))) = q.pop() {
+        if cost > seen[id].cost {
+            // Stale queue entry.
+            stats.record_cache_hit();
+            continue;
+        }
+        stats.record_expansion();
+        if is_goal(&seen[id].state) {
+            return Some((seen.remove(id)?.state, cost));
+        }
+        for (next, step) in successors(&seen[id].state) {
+            let alt = cost + step;
+            let hash = hasher.hash_one(&next);
+            let same_hash = latest.get(&hash).copied();
+            let mut found = same_hash;
+            while let Some(f) = found.filter(|&f| seen[f].state != next) {
+                found = seen[f].same_hash;
+            }
+            if found.is_some_and(|f| seen[f].cost <= alt) {
+                continue;
+            }
+            let estimate = alt + heuristic(&next);
+            let next_id = match found {
+                Some(f) => {
+                    seen[f].cost = alt;
+                    f
+                }
+                None => {
+                    let f = seen.alloc(Seen {
+                        state: next,
+                        cost: alt,
+                        same_hash,
+                    });
+                    latest.insert(hash, f);
+                    f
+                }
+            };
+            q.push(Reverse((estimate, alt, next_id)));
+        }
+        stats.record_frontier(q.len());
+    }
+    None
+}
+
+/// A state found by `astar_with_stats`.
+struct Seen<S> {
+    state: S,
+    /// The cheapest cost the state has been reached at so far.
+    cost: u64,
+    /// The state found before this one with the same hash, if any.
+    same_hash: Option<Id<Seen<S>>>,
+}
+
+/// Like `astar_with_stats`, but the best cost found so far for each state is stored
+/// under `key(state)` rather than the state itself. A small key that's cheap to
+/// compute, such as an incrementally maintained hash of a big state, saves comparing
+/// states and keeping every one found. States with the same key are treated as the same
+/// state, so a lossy key, like a hash, must be wide enough that collisions won't happen
+/// in practice.
+pub fn astar_by_key_with_stats<S, K, F, H, G, Q>(
+    start: S,
+    successors: F,
+    heuristic: H,
+    is_goal: G,
+    key: Q,
+    stats: &mut SearchStats,
+) -> Option<(S, u64)>
+where
+    K: Eq + Hash,
+    F: Fn(&S) -> Vec<(S, u64)>,
+    H: Fn(&S) -> u64,
+    G: Fn(&S) -> bool,
+    Q: Fn(&S) -> K,
+{
+    // States live in an arena so that the queue only has to order plain handles, and
+    // `S` doesn't need to be `Ord`. A state is dropped once it's popped, and its slot is
+    // reused, so the arena only holds the frontier.
+    let mut best = FastMap::from_iter([(key(&start), 0)]);
+    let mut states = Arena::new();
+    let h = heuristic(&start);
+    let mut q = BinaryHeap::from([Reverse((h, 0, states.alloc(start)))]);
+
+    while let Some(Reverse((_, cost, id))) = q.pop() {
+        let state = states.remove(id).expect("Queued states are in the arena");
+        if best.get(&key(&state)).is_some_and(|&b| cost > b) {
+            // Stale queue entry.
+            stats.record_cache_hit();
+            continue;
+        }
+        stats.record_expansion();
+        if is_goal(&state) {
+            return Some((state, cost));
+        }
+        for (next, step) in successors(&state) {
+            let alt = cost + step;
+            let next_key = key(&next);
+            if best.get(&next_key).is_none_or(|&b| alt < b) {
+                best.insert(next_key, alt);
+                q.push(Reverse((alt + heuristic(&next), alt, states.alloc(next))));
+            }
+        }
+        stats.record_frontier(q.len());
+    }
+    None
+}
+
+/// Like `astar`, but by iterative deepening (IDA*): a series of depth-first searches,
+/// each abandoning paths whose cost plus heuristic exceeds a bound, and each raising the
+/// bound to the smallest value that exceeded it last time. It only keeps the current
+/// path in memory, at the price of re-expanding states, so it suits searches whose
+/// visited sets would be too big for `astar`. The heuristic must not overestimate, and a
+/// search with no reachable goal only ends if the state space is finite.
+pub fn ida_star<S, F, H, G>(
+    start: S,
+    successors: F,
+    heuristic: H,
+    is_goal: G,
+) -> Option<(S, u64)>
+where
+    S: Clone + Eq,
+    F: Fn(&S) -> Vec<(S, u64)>,
+    H: Fn(&S) -> u64,
+    G: Fn(&S) -> bool,
+{
+    ida_star_with_stats(
+        start,
+        successors,
+        heuristic,
+        is_goal,
+        &mut SearchStats::new(),
+    )
+}
+
+/// Like `ida_star`, but records the work done in `stats`. The frontier is the current
+/// path, so `max_frontier` is the length of the longest path tried.
+pub fn ida_star_with_stats<S, F, H, G>(
+    start: S,
+    successors: F,
+    heuristic: H,
+    is_goal: G,
+    stats: &mut SearchStats,
+) -> Option<(S, u64)>
+where
+    S: Clone + Eq,
+    F: Fn(&S) -> Vec<(S, u64)>,
+    H: Fn(&S) -> u64,
+    G: Fn(&S) -> bool,
+{
+    let search = IdaSearch {
+        successors,
+        heuristic,
+        is_goal,
+    };
+    let mut bound = (search.heuristic)(&start);
+    let mut path = vec![start];
+    loop {
+        match search.probe(&mut path, 0, bound, stats) {
+            Probe::Found(cost) => return path.pop().map(|goal| (goal, cost)),
+            Probe::Exceeded(Some(next_bound)) => bound = next_bound,
+            Probe::Exceeded(None) => return None,
+        }
+    }
+}
+
+struct IdaSearch<F, H, G> {
+    successors: F,
+    heuristic: H,
+    is_goal: G,
+}
+
+enum Probe {
+    /// A goal was reached at this cost; it's the last state on the path.
+    Found(u64),
+    /// The smallest cost plus heuristic beyond the bound, or None if nothing went
+    /// beyond it.
+    Exceeded(Option<u64>),
+}
+
+impl<F, H, G> IdaSearch<F, H, G> {
+    /// Searches onward from the last state on `path`, reached at `cost`.
+    fn probe<S>(
+        &self,
+        path: &mut Vec<S>,
+        cost: u64,
+        bound: u64,
+        stats: &mut SearchStats,
+    ) -> Probe
+    where
+        S: Clone + Eq,
+        F: Fn(&S) -> Vec<(S, u64)>,
+        H: Fn(&S) -> u64,
+        G: Fn(&S) -> bool,
+    {
+        let Some(state) = path.last() else {
+            return Probe::Exceeded(None);
+        };
+        let estimate = cost + (self.heuristic)(state);
+        if estimate > bound {
+            return Probe::Exceeded(Some(estimate));
+        }
+        stats.record_expansion();
+        stats.record_frontier(path.len());
+        if (self.is_goal)(state) {
+            return Probe::Found(cost);
+        }
+        let mut next_bound = None;
+        for (next, step) in (self.successors)(state) {
+            if path.contains(&next) {
+                stats.record_cache_hit();
+                continue;
+            }
+            path.push(next);
+            match self.probe(path, cost + step, bound, stats) {
+                found @ Probe::Found(_) => return found,
+                Probe::Exceeded(b) => {
+                    next_bound = match (next_bound, b) {
+                        (Some(x), Some(y)) => Some(u64::min(x, y)),
+                        (x, y) => x.or(y),
+                    }
+                }
+            }
+            path.pop();
+        }
+        Probe::Exceeded(next_bound)
+    }
+}
+
+/// Searches for the state with the lowest `score`, for when there are too many states to
+/// search exactly. The search goes a level at a time: every state in the beam is
+/// expanded, and only the `width` best-scoring successors make up the next level.
+/// Duplicates within a level are dropped. It ends when a level has no successors, so
+/// `successors` must eventually return none, for instance by bounding the depth.
+/// Returns the best state seen at any level, and its score; it may well not be the
+/// best state overall. Ties go to the state generated first, so the result is
+/// reproducible if `successors` is. Panics if `width` is 0.
+pub fn beam_search<S, K, F, C>(start: S, successors: F, score: C, width: usize) -> (S, K)
+where
+    S: Clone + Eq + Hash,
+    K: Ord,
+    F: Fn(&S) -> Vec<S>,
+    C: Fn(&S) -> K,
+{
+    beam_search_with_stats(
+        start,
+        successors,
+        score,
+        |_, _| Ordering::Equal,
+        width,
+        &mut SearchStats::new(),
+    )
+}
+
+/// Like `beam_search`, but ties between states with equal scores are broken by
+/// `tie_break`, which should order the preferred state first. Equal states under
+/// `tie_break` still go to the one generated first.
+pub fn beam_search_with_stats<S, K, F, C, T>(
+    start: S,
+    successors: F,
+    score: C,
+    tie_break: T,
+    width: usize,
+    stats: &mut SearchStats,
+) -> (S, K)
+where
+    S: Clone + Eq + Hash,
+    K: Ord,
+    F: Fn(&S) -> Vec<S>,
+    C: Fn(&S) -> K,
+    T: Fn(&S, &S) -> Ordering,
+{
+    assert!(width > 0, "Beam width must be positive");
+    let start_score = score(&start);
+    let mut beam = vec![(start_score, start)];
+    let mut best: Option<(K, S)> = None;
+
+    while !beam.is_empty() {
+        let mut seen = FastSet::default();
+        let mut next = Vec::new();
+        for (state_score, state) in beam {
+            stats.record_expansion();
+            for succ in successors(&state) {
+                if seen.insert(succ.clone()) {
+                    next.push((score(&succ), succ));
+                } else {
+                    stats.record_cache_hit();
+                }
+            }
+            if best.as_ref().is_none_or(|(b, _)| state_score < *b) {
+                best = Some((state_score, state));
+            }
+        }
+        stats.record_frontier(next.len());
+        // Stable, so that full ties keep generation order.
+        next.sort_by(|(ka, a), (kb, b)| ka.cmp(kb).then_with(|| tie_break(a, b)));
+        next.truncate(width);
+        beam = next;
+    }
+    let (score, state) = best.expect("The start state is always scored");
+    (state, score)
+}
+
+/// The first value in `lo..hi` for which `pred` is false, or `hi` if there isn't one.
+/// `pred` must be true for some prefix of the range and false for the rest, as with
+/// `slice::partition_point`. So the largest value for which `pred` holds is one less
+/// than the result, if the result is greater than `lo`.
+pub fn partition_point_i64<P: FnMut(i64) -> bool>(lo: i64, hi: i64, mut pred: P) -> i64 {
+    let (mut lo, mut hi) = (lo, hi.max(lo));
+    while lo < hi {
+        // Can't overflow, unlike (lo + hi) / 2.
+        let mid = lo + ((hi as i128 - lo as i128) / 2) as i64;
+        if pred(mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+#[cfg(test)]
+mod search_tests {
+    use super::*;
+
+    #[test]
+    fn search_stats() {
+        let mut stats = SearchStats::new();
+        stats.record_expansion();
+        stats.record_expansion();
+        stats.record_cache_hit();
+        stats.record_frontier(3);
+        stats.record_frontier(1);
+        assert_eq!(
+            stats,
+            SearchStats {
+                nodes_expanded: 2,
+                cache_hits: 1,
+                max_frontier: 3
+            }
+        );
+        assert_eq!(
+            stats.to_string(),
+            "nodes expanded: 2, cache hits: 1, max frontier: 3"
+        );
+    }
+
+    /// Steps of +1 (cost 1) or *2 (cost 3) from 1 to a target.
+    fn successors(&n: &u64) -> Vec<(u64, u64)> {
+        vec![(n + 1, 1), (n * 2, 3)]
+    }
+
+    #[test]
+    fn dijkstra_search() {
+        // 1 -> 2 -> 3 -> 4 -> 5 -> 10 -> 20 is cheaper than 19 increments.
+        assert_eq!(dijkstra(1, successors, |&n| n == 20), Some((20, 10)));
+        assert_eq!(dijkstra(1, |_| vec![], |&n| n == 2), None);
+        let (goal, cost) = dijkstra(5, successors, |&n| n % 7 == 0).unwrap();
+        assert_eq!((goal, cost), (7, 2));
+    }
+
+    #[test]
+    fn astar_search() {
+        let mut dijkstra_stats = SearchStats::new();
+        let mut astar_stats = SearchStats::new();
+        let goal = 1000;
+        let expected =
+            dijkstra_with_stats(1, successors, |&n| n == goal, &mut dijkstra_stats);
+        let got = astar_with_stats(
+            1,
+            // Never overshoot, so that the heuristic stays admissible.
+            |&n| {
+                successors(&n)
+                    .into_iter()
+                    .filter(|&(m, _)| m <= goal)
+                    .collect()
+            },
+            |&n| if n < goal { 1 } else { 0 },
+            |&n| n == goal,
+            &mut astar_stats,
+        );
+        assert_eq!(got, expected);
+        assert!(astar_stats.nodes_expanded <= dijkstra_stats.nodes_expanded);
+        assert!(astar_stats.nodes_expanded > 0);
+    }
+
+    #[test]
+    fn hash_collisions() {
+        // Every state hashes alike, so telling them apart is down to equality.
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        struct Colliding(u64);
+        impl Hash for Colliding {
+            fn hash<H: std::hash::Hasher>(&self, _: &mut H) {}
+        }
+        let found = dijkstra(
+            Colliding(1),
+            |c| {
+                successors(&c.0)
+                    .into_iter()
+                    .map(|(n, step)| (Colliding(n), step))
+                    .collect()
+            },
+            |c| c.0 == 20,
+        );
+        assert_eq!(found, Some((Colliding(20), 10)));
+    }
+
+    #[test]
+    fn ida_star_search() {
+        // Never overshoot, so that the search space is finite.
+        let bounded = |&n: &u64| -> Vec<(u64, u64)> {
+            successors(&n)
+                .into_iter()
+                .filter(|&(m, _)| m <= 20)
+                .collect()
+        };
+        assert_eq!(ida_star(1, bounded, |_| 0, |&n| n == 20), Some((20, 10)));
+        assert_eq!(
+            ida_star(1, bounded, |&n| u64::from(n < 20), |&n| n == 20),
+            Some((20, 10))
+        );
+        assert_eq!(ida_star(1, bounded, |_| 0, |&n| n == 21), None);
+        // A cycle: the path check keeps it from going round forever.
+        assert_eq!(
+            ida_star(0, |&n| vec![((n + 1) % 4, 1)], |_| 0, |&n| n == 9),
+            None
+        );
+
+        let mut stats = SearchStats::new();
+        let found = ida_star_with_stats(5, bounded, |_| 0, |&n| n % 7 == 0, &mut stats);
+        assert_eq!(found, Some((7, 2)));
+        assert_eq!(stats.max_frontier, 3);
+    }
+
+    #[test]
+    fn beam() {
+        // A greedy search takes the cheap first step to 'a', but the best state is 'd',
+        // behind the dearer 'b'.
+        let edges = |&c: &char| -> Vec<char> {
+            match c {
+                's' => vec!['a', 'b'],
+                'a' => vec!['c'],
+                'b' => vec!['d', 'c'],
+                _ => vec![],
+            }
+        };
+        let score = |&c: &char| match c {
+            's' => 10,
+            'a' => 1,
+            'b' => 5,
+            'c' => 100,
+            _ => 0,
+        };
+        assert_eq!(beam_search('s', edges, score, 1), ('a', 1));
+        assert_eq!(beam_search('s', edges, score, 2), ('d', 0));
+
+        // Ties go to the first generated, unless broken otherwise.
+        let tied = |&c: &char| {
+            if c == 's' {
+                vec!['x', 'y', 'x']
+            } else {
+                vec![]
+            }
+        };
+        assert_eq!(beam_search('s', tied, |&c| c == 's', 1), ('x', false));
+        let mut stats = SearchStats::new();
+        let found =
+            beam_search_with_stats('s', tied, |&c| c == 's', |a, b| b.cmp(a), 1, &mut stats);
+        assert_eq!(found, ('y', false));
+        assert_eq!(stats.nodes_expanded, 2);
+        assert_eq!(stats.cache_hits, 1);
+    }
+
+    #[test]
+    fn partition_point() {
+        assert_eq!(partition_point_i64(0, 100, |x| x * x <= 50), 8);
+        assert_eq!(partition_point_i64(-10, 10, |x| x < -3), -3);
+        assert_eq!(partition_point_i64(0, 10, |_| true), 10);
+        assert_eq!(partition_point_i64(0, 10, |_| false), 0);
+        assert_eq!(partition_point_i64(5, 5, |_| true), 5);
+        assert_eq!(partition_point_i64(5, 2, |_| true), 5);
+        assert_eq!(
+            partition_point_i64(i64::MIN, i64::MAX, |x| x < i64::MAX - 1),
+            i64::MAX - 1
+        );
+        assert_eq!(partition_point_i64(i64::MIN, i64::MAX, |_| false), i64::MIN);
+
+        // Agrees with the standard library's version.
+        let v: Vec<i64> = (0..37).map(|x| x * 3).collect();
+        for t in -1..120 {
+            let got = partition_point_i64(0, v.len() as i64, |i| v[i as usize] < t);
+            assert_eq!(got as usize, v.partition_point(|&x| x < t));
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Line segments between integer points, like the hydrothermal vents of 2021 day 5.
+
+use crate::math::gcd;
+
+/// The segment from `from` to `to`, both included. Coordinates are `(x, y)`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Segment {
+    pub from: (i64, i64),
+    pub to: (i64, i64),
+}
+
+impl Segment {
+    pub fn new(from: (i64, i64), to: (i64, i64)) -> Self {
+        Segment { from, to }
+    }
+
+    /// Whether the segment is horizontal or vertical. A single point counts.
+    pub fn is_axis_aligned(&self) -> bool {
+        self.from.0 == self.to.0 || self.from.1 == self.to.1
+    }
+
+    /// Whether the segment is at 45 degrees to the axes.
+    pub fn is_diagonal(&self) -> bool {
+        let (dx, dy) = self.delta();
+        dx != 0 && dx.abs() == dy.abs()
+    }
+
+    /// Every integer point on the segment, from `from` to `to`. For a segment at any
+    /// angle, that's every `(dx, dy) / gcd(dx, dy)` step along it.
+    pub fn points(&self) -> impl Iterator<Item = (i64, i64)> {
+        let (dx, dy) = self.delta();
+        let n = gcd(dx.unsigned_abs(), dy.unsigned_abs()) as i64;
+        let (step_x, step_y) = if n == 0 { (0, 0) } else { (dx / n, dy / n) };
+        let (x, y) = self.from;
+        (0..=n).map(move |k| (x + k * step_x, y + k * step_y))
+    }
+
+    fn delta(&self) -> (i64, i64) {
+        (self.to.0 - self.from.0, self.to.1 - self.from.1)
+    }
+}
+
+#[cfg(test)]
+mod segment_tests {
+    use super::*;
+
+    #[test]
+    fn points() {
+        let pts = |s: Segment| s.points().collect::<Vec<_>>();
+        let s = Segment::new((1, 1), (1, 3));
+        assert!(s.is_axis_aligned() && !s.is_diagonal());
+        assert_eq!(pts(s), [(1, 1), (1, 2), (1, 3)]);
+        let s = Segment::new((9, 7), (7, 9));
+        assert!(!s.is_axis_aligned() && s.is_diagonal());
+        assert_eq!(pts(s), [(9, 7), (8, 8), (7, 9)]);
+        let s = Segment::new((0, 0), (6, -4));
+        assert!(!s.is_axis_aligned() && !s.is_diagonal());
+        assert_eq!(pts(s), [(0, 0), (3, -2), (6, -4)]);
+        let s = Segment::new((2, 2), (2, 2));
+        assert!(s.is_axis_aligned() && !s.is_diagonal());
+        assert_eq!(pts(s), [(2, 2)]);
+    }
+}
@@ -0,0 +1,159 @@
+//! Seven-segment displays whose segment wiring has been scrambled, as in 2021 day 8.
+
+use crate::counter::Counter;
+use crate::errors::AocResult;
+use crate::{ensure, failure};
+use std::collections::{HashMap, HashSet};
+
+/// Sorts the segments of `pattern`, so that the same set of segments always gives the
+/// same string.
+pub fn canonicalize(pattern: &str) -> String {
+    let mut segments: Vec<char> = pattern.chars().collect();
+    segments.sort_unstable();
+    segments.into_iter().collect()
+}
+
+/// Works out which digit each of the ten scrambled `patterns` shows. The keys of the
+/// returned map are the `canonicalize`d patterns.
+pub fn decode(patterns: &[&str]) -> AocResult<HashMap<String, u8>> {
+    // Deduction, where sig(x) is the signal wired to segment x of a display and
+    // sigs(n) is the signal pattern for digit n:
+    // Initially known: sigs(1), sigs(4), sigs(7), sigs(8)
+    // == 1478
+    // 1. There are only two initially unknown signal patterns that don't include a
+    //    signal - in this case, sig(c). One of them, sigs(5), is of length 5, and the
+    //    other, sigs(6), is of length 6. From this we can deduce sig(c), sigs(5), and
+    //    sigs(6).
+    // == 145678
+    // 2. Only one signal pattern, sigs(2), does not include sig(f). From this we can
+    //    deduce sigs(2) and sig(f).
+    // == 1245678
+    // 3. sigs(3) is the signal pattern of length 5 (that is not the known pattern for 5)
+    //    with sig(f) set.
+    // == 12345678
+    // 4. sigs(6) - sigs(5) = sig(e), and sigs(9) is the signal pattern of length 6 with
+    //    sig(e) not set.
+    // == 123456789
+    // 5. sigs(0) is the last remaining signal of length 6.
+    // == 0123456789
+    let patterns: Vec<String> = patterns.iter().map(|p| canonicalize(p)).collect();
+    ensure!(
+        patterns.iter().collect::<HashSet<_>>().len() == 10,
+        "Expected ten distinct signal patterns, got {patterns:?}"
+    );
+    let sighisto: Counter<char> = patterns.iter().flat_map(|p| p.chars()).collect();
+    let mut sigpat2digit: HashMap<&str, u8> = HashMap::new();
+
+    // 1, 4, 7, 8. Known based on unique weights.
+    for (digit, len) in [(1, 2), (4, 4), (7, 3), (8, 7)] {
+        let pattern = patterns.iter().find(|p| p.len() == len);
+        assign(&mut sigpat2digit, digit, pattern)?;
+    }
+
+    // 5, 6. Which signal is set by all but two (currently unknown) patterns?
+    // Those two patterns will correspond to 5 (weight 5) and 6 (weight 6).
+    let sig_c = sighisto
+        .iter()
+        .find(|&(k, v)| v == 8 && sigpat2digit.keys().all(|p| p.contains(*k)))
+        .ok_or("No signal for 5/6?")?
+        .0;
+    for (digit, len) in [(5, 5), (6, 6)] {
+        let pattern = patterns
+            .iter()
+            .find(|p| !p.contains(*sig_c) && p.len() == len);
+        assign(&mut sigpat2digit, digit, pattern)?;
+    }
+
+    // 2. Which signal is set by all but one pattern? That pattern will correspond to 2.
+    let sig_f = sighisto
+        .iter()
+        .find(|&(_, v)| v == 9)
+        .ok_or("No signal for 2?")?
+        .0;
+    let pattern = patterns
+        .iter()
+        .find(|p| !p.contains(*sig_f) && p.len() == 5);
+    assign(&mut sigpat2digit, 2, pattern)?;
+
+    // 3. Which pattern of length 5, which is not the known pattern for 5, has sig_f set?
+    let pattern = patterns.iter().find(|p| {
+        p.contains(*sig_f) && p.len() == 5 && !sigpat2digit.contains_key(p.as_str())
+    });
+    assign(&mut sigpat2digit, 3, pattern)?;
+
+    // 9. The pattern of length 6 without sig_e.
+    let sigs_of = |digit| -> AocResult<HashSet<char>> {
+        let (pattern, _) = sigpat2digit
+            .iter()
+            .find(|(_, &v)| v == digit)
+            .ok_or(format!("No {digit}?"))?;
+        Ok(pattern.chars().collect())
+    };
+    let sig_e = *sigs_of(6)?
+        .difference(&sigs_of(5)?)
+        .next()
+        .ok_or("No difference?")?;
+    let pattern = patterns.iter().find(|p| !p.contains(sig_e) && p.len() == 6);
+    assign(&mut sigpat2digit, 9, pattern)?;
+
+    // 0. The last remaining pattern of length 6.
+    let pattern = patterns
+        .iter()
+        .find(|p| p.len() == 6 && !sigpat2digit.contains_key(p.as_str()));
+    assign(&mut sigpat2digit, 0, pattern)?;
+
+    Ok(sigpat2digit
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect())
+}
+
+fn assign<'a>(
+    sigpat2digit: &mut HashMap<&'a str, u8>,
+    digit: u8,
+    pattern: Option<&'a String>,
+) -> AocResult<()> {
+    let Some(pattern) = pattern else {
+        failure!("No pattern found for {}?", digit);
+    };
+    if sigpat2digit.insert(pattern, digit).is_some() {
+        failure!("Overwrote the pattern for {}", digit);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod sevenseg_tests {
+    use super::*;
+
+    #[test]
+    fn canonical() {
+        assert_eq!(canonicalize("gcdfa"), "acdfg");
+        assert_eq!(canonicalize(""), "");
+    }
+
+    #[test]
+    fn unscrambled() -> AocResult<()> {
+        let digits = [
+            "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg",
+            "abcdfg",
+        ];
+        let decoded = decode(&digits)?;
+        for (digit, pattern) in digits.iter().enumerate() {
+            assert_eq!(decoded[*pattern], digit as u8);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn scrambled() -> AocResult<()> {
+        let patterns = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab";
+        let decoded = decode(&patterns.split(' ').collect::<Vec<_>>())?;
+        let expected = [8, 5, 2, 3, 7, 9, 6, 4, 0, 1];
+        for (pattern, digit) in patterns.split(' ').zip(expected) {
+            assert_eq!(decoded[&canonicalize(pattern)], digit);
+        }
+        assert!(decode(&["ab", "abc"]).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,436 @@
+use crate::cli::Args;
+use crate::ensure;
+use crate::errors::AocResult;
+use crate::io::read_input;
+use crate::sha256::sha256_hex;
+use crate::timing::timed;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// A day's solution. Both parts are given the whole puzzle input, and return their
+/// answer formatted for printing.
+pub trait Solver: Sync {
+    fn part1(&self, input: &str) -> AocResult<String>;
+    fn part2(&self, input: &str) -> AocResult<String>;
+}
+
+/// A year's solvers, by day.
+#[derive(Default)]
+pub struct Registry {
+    solvers: BTreeMap<u32, &'static dyn Solver>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `Err` if `day` isn't in 1..=25 or already has a solver.
+    pub fn register(&mut self, day: u32, solver: &'static dyn Solver) -> AocResult<()> {
+        ensure!((1..=25).contains(&day), "Invalid day {day}");
+        ensure!(
+            self.solvers.insert(day, solver).is_none(),
+            "Day {day} registered twice"
+        );
+        Ok(())
+    }
+
+    pub fn get(&self, day: u32) -> Option<&'static dyn Solver> {
+        self.solvers.get(&day).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.solvers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.solvers.is_empty()
+    }
+
+    /// Iterates over `(day, solver)` in order of day.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &'static dyn Solver)> + '_ {
+        self.solvers.iter().map(|(day, solver)| (*day, *solver))
+    }
+}
+
+/// Builds a `Registry` from `day => solver` pairs, where each solver is a constant
+/// expression (typically a unit struct). Panics on an invalid or duplicate day.
+#[macro_export]
+macro_rules! registry {
+    ($($day:literal => $solver:expr),* $(,)?) => {{
+        let mut registry = $crate::solver::Registry::new();
+        $(
+            registry
+                .register($day, &$solver)
+                .expect("Bad solver registration");
+        )*
+        registry
+    }};
+}
+
+/// The `main` of `day`'s binary: solves the input chosen on the command line (see
+/// `cli::Args`). Multi-line answers start on their own line.
+pub fn run_cli(day: u32, solver: &dyn Solver) -> AocResult<()> {
+    let args = Args::from_env(&[], &[])?;
+    run_parts(solver, &read_input(&args.input_path(day)?)?, args.part)
+}
+
+/// Solves and prints both parts for `input`.
+pub fn run(solver: &dyn Solver, input: &str) -> AocResult<()> {
+    run_parts(solver, input, None)
+}
+
+/// Solves and prints `part` for `input`, or both parts if `part` is `None`.
+pub fn run_parts(solver: &dyn Solver, input: &str, part: Option<u32>) -> AocResult<()> {
+    if part.is_none_or(|p| p == 1) {
+        let answer = {
+            let _t = timed("part 1");
+            solver.part1(input)?
+        };
+        print_answer(1, &answer);
+    }
+    if part.is_none_or(|p| p == 2) {
+        let answer = {
+            let _t = timed("part 2");
+            solver.part2(input)?
+        };
+        print_answer(2, &answer);
+    }
+    Ok(())
+}
+
+fn print_answer(part: u32, answer: &str) {
+    if answer.contains('\n') {
+        println!("Part {part}:\n{}", answer.trim_end());
+    } else {
+        println!("Part {part}: {answer}");
+    }
+}
+
+/// One day's answers and how long each part took to compute.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayReport {
+    pub day: u32,
+    pub answers: [String; 2],
+    pub times: [Duration; 2],
+    /// Hex SHA-256 of the input, to tell apart results for different inputs.
+    pub input_sha256: String,
+}
+
+/// Solves both parts for `input`, timing each.
+pub fn solve_timed(day: u32, solver: &dyn Solver, input: &str) -> AocResult<DayReport> {
+    let start = Instant::now();
+    let part1 = {
+        let _t = timed(format!("day {day} part 1"));
+        solver.part1(input)?
+    };
+    let mid = Instant::now();
+    let part2 = {
+        let _t = timed(format!("day {day} part 2"));
+        solver.part2(input)?
+    };
+    let end = Instant::now();
+    Ok(DayReport {
+        day,
+        answers: [part1, part2],
+        times: [mid - start, end - mid],
+        input_sha256: sha256_hex(input.as_bytes()),
+    })
+}
+
+/// Writes `reports` as a table with a row per day and a final row with the total
+/// time. Multi-line answers are summarized by their line count. If the days were run
+/// concurrently, `wall` is the elapsed time for all of them, and is reported along
+/// with the speedup over running them one at a time.
+pub fn write_table(
+    reports: &[DayReport],
+    wall: Option<Duration>,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let cell = |answer: &str| match answer.trim_end().lines().count() {
+        0 | 1 => answer.trim_end().to_string(),
+        n => format!("({n} lines)"),
+    };
+    let width = reports
+        .iter()
+        .flat_map(|r| r.answers.iter().map(|a| cell(a).len()))
+        .max()
+        .unwrap_or(0)
+        .max("Part 1".len());
+    let time = |d: &Duration| format!("{d:.1?}");
+    let label_width = 3 + 2 * (width + 2) + 10 + 2;
+
+    writeln!(
+        out,
+        "Day  {:<width$}  {:>10}  {:<width$}  {:>10}",
+        "Part 1", "Time", "Part 2", "Time"
+    )?;
+    for r in reports {
+        writeln!(
+            out,
+            "{:>3}  {:<width$}  {:>10}  {:<width$}  {:>10}",
+            r.day,
+            cell(&r.answers[0]),
+            time(&r.times[0]),
+            cell(&r.answers[1]),
+            time(&r.times[1]),
+        )?;
+    }
+    let total: Duration = reports.iter().flat_map(|r| r.times).sum();
+    writeln!(out, "{:<label_width$}  {:>10}", "Total", time(&total))?;
+    if let Some(wall) = wall {
+        writeln!(out, "{:<label_width$}  {:>10}", "Wall", time(&wall))?;
+        let speedup = total.as_secs_f64() / wall.as_secs_f64();
+        writeln!(
+            out,
+            "{:<label_width$}  {:>10}",
+            "Speedup",
+            format!("{speedup:.2}x")
+        )?;
+    }
+    Ok(())
+}
+
+/// How the runner prints its results.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(OutputFormat::Table),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(format!(
+                "Unknown output format {s:?}: expected table, json or csv"
+            )),
+        }
+    }
+}
+
+/// Writes `reports` in `format`. JSON and CSV have one record per part, with fields
+/// `day`, `part`, `answer`, `duration_ns` and `input_sha256`.
+pub fn write_reports(
+    reports: &[DayReport],
+    format: OutputFormat,
+    wall: Option<Duration>,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Table => write_table(reports, wall, out),
+        OutputFormat::Json => write_json(reports, out),
+        OutputFormat::Csv => write_csv(reports, out),
+    }
+}
+
+fn records(reports: &[DayReport]) -> impl Iterator<Item = (&DayReport, usize)> {
+    reports.iter().flat_map(|r| [(r, 0), (r, 1)])
+}
+
+fn write_json(reports: &[DayReport], out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "[")?;
+    for (i, (r, part)) in records(reports).enumerate() {
+        if i > 0 {
+            writeln!(out, ",")?;
+        }
+        write!(
+            out,
+            "  {{\"day\": {}, \"part\": {}, \"answer\": \"{}\", \"duration_ns\": {}, \
+             \"input_sha256\": \"{}\"}}",
+            r.day,
+            part + 1,
+            json_escape(&r.answers[part]),
+            r.times[part].as_nanos(),
+            r.input_sha256
+        )?;
+    }
+    if !reports.is_empty() {
+        writeln!(out)?;
+    }
+    writeln!(out, "]")
+}
+
+fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn write_csv(reports: &[DayReport], out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "day,part,answer,duration_ns,input_sha256")?;
+    for (r, part) in records(reports) {
+        writeln!(
+            out,
+            "{},{},{},{},{}",
+            r.day,
+            part + 1,
+            csv_field(&r.answers[part]),
+            r.times[part].as_nanos(),
+            r.input_sha256
+        )?;
+    }
+    Ok(())
+}
+
+/// Quotes `s` if it contains anything special to CSV, doubling any quotes.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod solver_tests {
+    use super::*;
+    use crate::errors::failure;
+
+    struct LineCount;
+
+    impl Solver for LineCount {
+        fn part1(&self, input: &str) -> AocResult<String> {
+            Ok(input.lines().count().to_string())
+        }
+
+        fn part2(&self, input: &str) -> AocResult<String> {
+            Ok(input.len().to_string())
+        }
+    }
+
+    struct Failing;
+
+    impl Solver for Failing {
+        fn part1(&self, _input: &str) -> AocResult<String> {
+            failure("Nope")
+        }
+
+        fn part2(&self, input: &str) -> AocResult<String> {
+            self.part1(input)
+        }
+    }
+
+    #[test]
+    fn registry() -> AocResult<()> {
+        let registry = crate::registry! {
+            3 => Failing,
+            1 => LineCount,
+        };
+        assert_eq!(registry.len(), 2);
+        assert_eq!(
+            registry.iter().map(|(day, _)| day).collect::<Vec<_>>(),
+            [1, 3]
+        );
+        assert_eq!(registry.get(1).ok_or("No day 1")?.part1("a\nb\n")?, "2");
+        assert_eq!(registry.get(1).ok_or("No day 1")?.part2("a\nb\n")?, "4");
+        assert!(registry.get(3).ok_or("No day 3")?.part2("").is_err());
+        assert!(registry.get(2).is_none());
+
+        let mut registry = Registry::new();
+        assert!(registry.is_empty());
+        registry.register(25, &LineCount)?;
+        assert!(registry.register(25, &Failing).is_err());
+        assert!(registry.register(0, &Failing).is_err());
+        assert!(registry.register(26, &Failing).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn timing_table() -> AocResult<()> {
+        let report = solve_timed(7, &LineCount, "a\nb\n")?;
+        assert_eq!(report.day, 7);
+        assert_eq!(report.answers, ["2", "4"]);
+        assert_eq!(report.input_sha256, sha256_hex(b"a\nb\n"));
+        assert!(solve_timed(1, &Failing, "").is_err());
+
+        let reports = [
+            DayReport {
+                day: 1,
+                answers: ["1754".to_string(), "1789".to_string()],
+                times: [Duration::from_micros(15), Duration::from_micros(20)],
+                input_sha256: "ab".to_string(),
+            },
+            DayReport {
+                day: 13,
+                answers: ["753".to_string(), "#..#\n####\n".to_string()],
+                times: [Duration::from_millis(2), Duration::from_secs(1)],
+                input_sha256: "cd".to_string(),
+            },
+        ];
+        let mut out = Vec::new();
+        write_table(&reports, None, &mut out)?;
+        assert_eq!(
+            String::from_utf8(out)?,
+            "\
+Day  Part 1           Time  Part 2           Time
+  1  1754           15.0µs  1789           20.0µs
+ 13  753             2.0ms  (2 lines)        1.0s
+Total                                        1.0s
+"
+        );
+
+        let mut out = Vec::new();
+        write_table(&reports[1..], Some(Duration::from_millis(401)), &mut out)?;
+        assert!(String::from_utf8(out)?.ends_with(
+            "\
+Total                                        1.0s
+Wall                                      401.0ms
+Speedup                                     2.50x
+"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn machine_readable_output() -> AocResult<()> {
+        let reports = [DayReport {
+            day: 13,
+            answers: ["1,2".to_string(), "#\"\n.\\".to_string()],
+            times: [Duration::from_nanos(1500), Duration::from_micros(2)],
+            input_sha256: "ab".to_string(),
+        }];
+
+        let mut out = Vec::new();
+        write_reports(&reports, "json".parse()?, None, &mut out)?;
+        assert_eq!(
+            String::from_utf8(out)?,
+            r##"[
+  {"day": 13, "part": 1, "answer": "1,2", "duration_ns": 1500, "input_sha256": "ab"},
+  {"day": 13, "part": 2, "answer": "#\"\n.\\", "duration_ns": 2000, "input_sha256": "ab"}
+]
+"##
+        );
+
+        let mut out = Vec::new();
+        write_reports(&reports, "csv".parse()?, None, &mut out)?;
+        assert_eq!(
+            String::from_utf8(out)?,
+            "day,part,answer,duration_ns,input_sha256
+13,1,\"1,2\",1500,ab
+13,2,\"#\"\"\n.\\\",2000,ab
+"
+        );
+
+        let mut out = Vec::new();
+        write_reports(&[], OutputFormat::Json, None, &mut out)?;
+        assert_eq!(String::from_utf8(out)?, "[\n]\n");
+        assert!("xml".parse::<OutputFormat>().is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+//! Where the time goes within a day: `let _t = timed("parse");` times the rest of the
+//! enclosing block. Spans opened while another is open nest under it, and when an
+//! outermost span ends, its breakdown is printed to stderr. Nothing is recorded unless
+//! timings are turned on with `--timings` or by setting `AOC_TIMINGS`.
+//!
+//! Spans are per thread, so parts solved concurrently each get their own breakdown.
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::env;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Whether spans are being recorded.
+pub fn enabled() -> bool {
+    static FROM_ENV: OnceLock<bool> = OnceLock::new();
+    ENABLED.load(Ordering::Relaxed)
+        || *FROM_ENV.get_or_init(|| env::var_os("AOC_TIMINGS").is_some())
+}
+
+/// Turns on timings, on top of whatever `AOC_TIMINGS` asks for.
+pub fn set_enabled() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+thread_local! {
+    /// The spans open on this thread, outermost first.
+    static OPEN: RefCell<Vec<SpanTree>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Starts a span named `label`, which ends when the returned guard is dropped.
+pub fn timed(label: impl Into<Cow<'static, str>>) -> Span {
+    if !enabled() {
+        return Span { start: None };
+    }
+    OPEN.with_borrow_mut(|open| {
+        open.push(SpanTree {
+            label: label.into(),
+            elapsed: Duration::ZERO,
+            count: 1,
+            children: Vec::new(),
+        })
+    });
+    Span {
+        start: Some(Instant::now()),
+    }
+}
+
+/// An open span. Dropping it ends the span, printing the breakdown if it's outermost.
+#[must_use = "the span ends as soon as it's dropped"]
+pub struct Span {
+    /// None if timings were off when the span was opened.
+    start: Option<Instant>,
+}
+
+impl Span {
+    /// Ends the span, returning its breakdown instead of printing it if it's outermost.
+    pub fn finish(mut self) -> Option<SpanTree> {
+        self.close()
+    }
+
+    fn close(&mut self) -> Option<SpanTree> {
+        let elapsed = self.start.take()?.elapsed();
+        OPEN.with_borrow_mut(|open| {
+            let mut tree = open.pop()?;
+            tree.elapsed = elapsed;
+            match open.last_mut() {
+                Some(parent) => {
+                    parent.add_child(tree);
+                    None
+                }
+                None => Some(tree),
+            }
+        })
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some(tree) = self.close() {
+            eprint!("{tree}");
+        }
+    }
+}
+
+/// A finished span and the spans within it. Spans with the same label and parent are
+/// merged, so a phase timed inside a loop shows up once, with its total time and count.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpanTree {
+    pub label: Cow<'static, str>,
+    pub elapsed: Duration,
+    /// How many spans were merged into this one.
+    pub count: u32,
+    pub children: Vec<SpanTree>,
+}
+
+impl SpanTree {
+    fn add_child(&mut self, child: SpanTree) {
+        match self.children.iter_mut().find(|c| c.label == child.label) {
+            Some(existing) => {
+                existing.elapsed += child.elapsed;
+                existing.count += child.count;
+                for grandchild in child.children {
+                    existing.add_child(grandchild);
+                }
+            }
+            None => self.children.push(child),
+        }
+    }
+
+    fn write(&self, f: &mut fmt::Formatter, depth: usize, total: Duration) -> fmt::Result {
+        let label = format!("{:indent$}{}", "", self.label, indent = 2 * depth);
+        let share = 100.0 * self.elapsed.as_secs_f64() / total.as_secs_f64().max(1e-9);
+        write!(
+            f,
+            "{label:<32} {:>10.3}ms {share:>5.1}%",
+            self.elapsed.as_secs_f64() * 1000.0
+        )?;
+        if self.count > 1 {
+            write!(f, " ({} calls)", self.count)?;
+        }
+        writeln!(f)?;
+        for child in &self.children {
+            child.write(f, depth + 1, total)?;
+        }
+        Ok(())
+    }
+}
+
+/// One line per span, indented by depth, with its time and share of the outermost span.
+impl fmt::Display for SpanTree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f, 0, self.elapsed)
+    }
+}
+
+#[cfg(test)]
+mod timing_tests {
+    use super::*;
+
+    #[test]
+    fn nesting() {
+        set_enabled();
+        let outer = timed("part 1");
+        {
+            let _parse = timed("parse");
+        }
+        for _ in 0..3 {
+            let _step = timed("step");
+            let _inner = timed(format!("inner {}", 1));
+        }
+        let tree = outer.finish().unwrap();
+
+        assert_eq!(tree.label, "part 1");
+        let labels: Vec<_> = tree.children.iter().map(|c| (&*c.label, c.count)).collect();
+        assert_eq!(labels, [("parse", 1), ("step", 3)]);
+        assert_eq!(tree.children[1].children[0].count, 3);
+        let child_time: Duration = tree.children.iter().map(|c| c.elapsed).sum();
+        assert!(child_time <= tree.elapsed);
+
+        let text = tree.to_string();
+        assert_eq!(text.lines().count(), 4);
+        assert!(
+            text.lines().nth(3).unwrap().starts_with("    inner 1"),
+            "{text}"
+        );
+        assert!(text.contains("(3 calls)"), "{text}");
+
+        // Nothing is left open.
+        assert!(timed("alone").finish().is_some());
+    }
+}
@@ -0,0 +1,143 @@
+//! Folding point sets and grids over a line, as with the transparent paper of 2021 day 13.
+
+use crate::errors::AocResult;
+use crate::grid::Grid;
+use crate::point::Point;
+use crate::{ensure, failure};
+use std::collections::HashSet;
+
+/// Folds the points right of column `col` over onto the left. Points on the fold line
+/// stay where they are.
+pub fn fold_x(points: &HashSet<Point>, col: usize) -> AocResult<HashSet<Point>> {
+    points
+        .iter()
+        .map(|&p| Ok(Point::new(p.i, reflect(p.j, col)?)))
+        .collect()
+}
+
+/// Folds the points below row `row` up onto the top. Points on the fold line stay where
+/// they are.
+pub fn fold_y(points: &HashSet<Point>, row: usize) -> AocResult<HashSet<Point>> {
+    points
+        .iter()
+        .map(|&p| Ok(Point::new(reflect(p.i, row)?, p.j)))
+        .collect()
+}
+
+/// Folds the part of `grid` right of column `col` over onto the left, dropping the fold
+/// line. Where two cells land on each other the larger value is kept, so folding a grid
+/// of 0s and 1s ORs the halves together.
+pub fn fold_grid_x(grid: &Grid, col: usize) -> AocResult<Grid> {
+    check_fold(grid.num_cols(), col)?;
+    let (rows, cols) = (grid.num_rows(), grid.num_cols());
+    let cells = grid.vec();
+    let mut out = Vec::with_capacity(rows * col);
+    for i in 0..rows {
+        for j in 0..col {
+            let mirror = 2 * col - j;
+            let right = if mirror < cols {
+                cells[i * cols + mirror]
+            } else {
+                0
+            };
+            out.push(cells[i * cols + j].max(right));
+        }
+    }
+    Grid::from_slice(&out, rows, col)
+}
+
+/// Folds the part of `grid` below row `row` up onto the top, dropping the fold line.
+/// Cells are combined as in `fold_grid_x`.
+pub fn fold_grid_y(grid: &Grid, row: usize) -> AocResult<Grid> {
+    check_fold(grid.num_rows(), row)?;
+    let (rows, cols) = (grid.num_rows(), grid.num_cols());
+    let cells = grid.vec();
+    let mut out = cells[..row * cols].to_vec();
+    for i in row + 1..rows {
+        let mirror = 2 * row - i;
+        for j in 0..cols {
+            let cell = &mut out[mirror * cols + j];
+            *cell = (*cell).max(cells[i * cols + j]);
+        }
+    }
+    Grid::from_slice(&out, row, cols)
+}
+
+/// Draws `points` as a grid of 1s on a background of 0s. The grid's top left corner is
+/// (0, 0), and it's just big enough to hold every point.
+pub fn render_points(points: &HashSet<Point>) -> AocResult<Grid> {
+    let rows = points.iter().map(|p| p.i + 1).max().unwrap_or(0);
+    let cols = points.iter().map(|p| p.j + 1).max().unwrap_or(0);
+    let mut cells = vec![0; rows * cols];
+    for p in points {
+        cells[p.i * cols + p.j] = 1;
+    }
+    Grid::from_slice(&cells, rows, cols)
+}
+
+fn reflect(x: usize, line: usize) -> AocResult<usize> {
+    if x <= line {
+        return Ok(x);
+    }
+    match (2 * line).checked_sub(x) {
+        Some(reflected) => Ok(reflected),
+        None => failure!("{x} is too far past the fold at {line} to fold over it"),
+    }
+}
+
+fn check_fold(len: usize, line: usize) -> AocResult<()> {
+    ensure!(line < len, "Fold at {line} is outside a grid of size {len}");
+    ensure!(
+        len - line - 1 <= line,
+        "Fold at {line} leaves more than half of a grid of size {len} to fold over"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod transform_tests {
+    use super::*;
+
+    fn points(pairs: &[(usize, usize)]) -> HashSet<Point> {
+        pairs.iter().copied().map(Point::from_pair).collect()
+    }
+
+    #[test]
+    fn fold_points() -> AocResult<()> {
+        let paper = points(&[(0, 0), (0, 4), (1, 3), (2, 2), (4, 1)]);
+        assert_eq!(
+            fold_x(&paper, 2)?,
+            points(&[(0, 0), (1, 1), (2, 2), (4, 1)])
+        );
+        assert_eq!(
+            fold_y(&paper, 2)?,
+            points(&[(0, 0), (0, 4), (1, 3), (2, 2), (0, 1)])
+        );
+        assert!(fold_x(&paper, 1).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn fold_grids() -> AocResult<()> {
+        let grid = Grid::from_slice(&[1, 0, 0, 0, 0, 0, 1, 0, 1, 1], 2, 5)?;
+        assert_eq!(
+            fold_grid_x(&grid, 2)?,
+            Grid::from_slice(&[1, 0, 1, 1], 2, 2)?
+        );
+        assert_eq!(
+            fold_grid_y(&grid, 1)?,
+            Grid::from_slice(&[1, 0, 0, 0, 0], 1, 5)?
+        );
+        assert!(fold_grid_x(&grid, 1).is_err());
+        assert!(fold_grid_y(&grid, 2).is_err());
+
+        // Folding the rendered points matches rendering the folded points.
+        let paper = points(&[(0, 0), (0, 4), (1, 3), (4, 1), (3, 4)]);
+        let grid = render_points(&paper)?;
+        assert_eq!((grid.num_rows(), grid.num_cols()), (5, 5));
+        assert_eq!(fold_grid_x(&grid, 2)?, render_points(&fold_x(&paper, 2)?)?);
+        assert_eq!(fold_grid_y(&grid, 2)?, render_points(&fold_y(&paper, 2)?)?);
+        assert_eq!(render_points(&HashSet::new())?.num_rows(), 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+//! Rolling aggregates over sequences, for puzzles like 2021 day 1 that compare each
+//! stretch of a sequence with the one before.
+
+use std::collections::VecDeque;
+use std::ops::{Add, Sub};
+
+/// The sums of each run of `k` consecutive items of `iter`, in order. The sum is kept up
+/// to date as the window slides, so each item costs one addition and one subtraction.
+/// There are no sums if `iter` has fewer than `k` items. Panics if `k` is 0.
+pub fn windows_sum<T, I>(iter: I, k: usize) -> impl Iterator<Item = T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+    I: IntoIterator<Item = T>,
+{
+    assert!(k > 0, "Windows must hold at least one item");
+    let mut window = VecDeque::with_capacity(k);
+    let mut sum = T::default();
+    iter.into_iter().filter_map(move |x| {
+        sum = sum + x;
+        window.push_back(x);
+        if window.len() > k {
+            sum = sum - window.pop_front()?;
+        }
+        (window.len() == k).then_some(sum)
+    })
+}
+
+/// The number of items of `iter` that are bigger than the item before them.
+pub fn count_increases<T, I>(iter: I) -> usize
+where
+    T: PartialOrd,
+    I: IntoIterator<Item = T>,
+{
+    let mut prev: Option<T> = None;
+    let mut count = 0;
+    for x in iter {
+        if prev.as_ref().is_some_and(|p| x > *p) {
+            count += 1;
+        }
+        prev = Some(x);
+    }
+    count
+}
+
+#[cfg(test)]
+mod window_tests {
+    use super::*;
+
+    const DEPTHS: [i64; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn sums() {
+        assert_eq!(
+            windows_sum(DEPTHS, 3).collect::<Vec<_>>(),
+            [607, 618, 618, 617, 647, 716, 769, 792]
+        );
+        assert!(windows_sum(DEPTHS, 1).eq(DEPTHS));
+        assert_eq!(windows_sum(DEPTHS, 10).collect::<Vec<_>>(), [2256]);
+        assert_eq!(windows_sum(DEPTHS, 11).count(), 0);
+        assert_eq!(
+            windows_sum([0.5, 1.5, 2.0], 2).collect::<Vec<_>>(),
+            [2.0, 3.5]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_window() {
+        windows_sum(DEPTHS, 0).count();
+    }
+
+    #[test]
+    fn increases() {
+        assert_eq!(count_increases(DEPTHS), 7);
+        assert_eq!(count_increases(windows_sum(DEPTHS, 3)), 5);
+        assert_eq!(count_increases([3, 3, 2, 1]), 0);
+        assert_eq!(count_increases(Vec::<i64>::new()), 0);
+        assert_eq!(count_increases(["a", "c", "b"]), 1);
+    }
+}
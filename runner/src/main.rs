@@ -0,0 +1,201 @@
+//! Runs any day's solver: `aoc [--year YEAR] DAY [INPUT]`, where YEAR defaults to the
+//! latest year in `YEARS`. Without INPUT, reads the day's input from the year's `data/`,
+//! downloading it first if it isn't there. INPUT may be "-" for stdin, or the
+//! input itself can be given with `--input-string TEXT`, e.g. to try a puzzle's
+//! example. The usual `--input`, `--test`, `--part` and `--verbose` options work too
+//! (see `aoc_util::cli`).
+//!
+//! `aoc --all` runs every day of the year on its input and prints a table of answers and timings.
+//! With the `parallel` feature, the days run concurrently.
+//!
+//! `aoc status` prints a calendar of the year: which parts have solvers, which of those
+//! have known answers in the year's `answers.toml`, and how many stars that makes.
+//!
+//! `--output table|json|csv` prints results with timings and input hashes in the
+//! given format, for a single day or for `--all`.
+//!
+//! Built with the `embed-tests` or `embed-inputs` features, `--test` or the default
+//! input come from the binary itself rather than `data/` or the network.
+
+use aoc_util::{
+    answers::{write_status, Answers},
+    cli::Args,
+    errors::{failure, AocResult},
+    io::{read_input, set_data_dir},
+    solver::{
+        run_parts, solve_timed, write_reports, DayReport, OutputFormat, Registry, Solver,
+    },
+};
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+const USAGE: &str = "Usage: aoc [--year YEAR] [--output table|json|csv] \
+                     (DAY [INPUT|-] | DAY --input-string TEXT | DAY --test | --all | status)";
+
+/// A year's crate, as the runner sees it.
+struct Year {
+    year: u32,
+    /// The crate's directory in the workspace.
+    dir: &'static str,
+    solvers: fn() -> Registry,
+    #[cfg(feature = "embed-tests")]
+    test_input: fn(u32) -> Option<&'static str>,
+    #[cfg(feature = "embed-inputs")]
+    input: fn(u32) -> Option<&'static str>,
+}
+
+/// Every year the runner knows. A new year's crate goes here and in `Cargo.toml`.
+const YEARS: &[Year] = &[Year {
+    year: aoc2021::YEAR,
+    dir: "2021",
+    solvers: aoc2021::solvers,
+    #[cfg(feature = "embed-tests")]
+    test_input: aoc2021::embedded::test_input,
+    #[cfg(feature = "embed-inputs")]
+    input: aoc2021::embedded::input,
+}];
+
+impl Year {
+    fn find(year: Option<&str>) -> AocResult<&'static Year> {
+        let found = match year {
+            Some(year) => {
+                let year: u32 = year.parse()?;
+                YEARS.iter().find(|y| y.year == year)
+            }
+            None => YEARS.iter().max_by_key(|y| y.year),
+        };
+        Ok(found.ok_or(format!("No solvers for year {}", year.unwrap_or("?")))?)
+    }
+
+    /// The year's crate directory, found via the workspace when run by cargo, and
+    /// otherwise relative to the current directory.
+    fn crate_dir(&self) -> PathBuf {
+        let workspace = env::var_os("CARGO_MANIFEST_DIR")
+            .and_then(|dir| Path::new(&dir).parent().map(Path::to_path_buf))
+            .unwrap_or_default();
+        workspace.join(self.dir)
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.crate_dir().join("data")
+    }
+
+    /// The year's known answers, or none if it has no `answers.toml`.
+    fn answers(&self) -> AocResult<Answers> {
+        let path = self.crate_dir().join("answers.toml");
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+        Answers::from_file(&path.to_string_lossy())
+    }
+
+    #[cfg(feature = "embed-tests")]
+    fn test_input(&self, day: u32) -> AocResult<String> {
+        let input = (self.test_input)(day);
+        Ok(input
+            .ok_or(format!("Day {day} has no example"))?
+            .to_string())
+    }
+
+    #[cfg(not(feature = "embed-tests"))]
+    fn test_input(&self, day: u32) -> AocResult<String> {
+        read_input(&aoc_util::io::get_day_file(day, "test"))
+    }
+
+    #[cfg(feature = "embed-inputs")]
+    fn puzzle_input(&self, day: u32) -> AocResult<String> {
+        let input = (self.input)(day);
+        Ok(input.ok_or(format!("No input for day {day}"))?.to_string())
+    }
+
+    /// Reads the day's input from `data/`, downloading it first if it isn't there.
+    #[cfg(not(feature = "embed-inputs"))]
+    fn puzzle_input(&self, day: u32) -> AocResult<String> {
+        aoc_util::net::fetch_input(self.year, day)
+    }
+}
+
+fn main() -> AocResult<()> {
+    let args = Args::from_env(&["--all"], &["--output", "--input-string", "--year"])?;
+    let year = Year::find(args.option("--year"))?;
+    set_data_dir(year.data_dir());
+    let format = args
+        .option("--output")
+        .map(|f| f.parse::<OutputFormat>())
+        .transpose()?;
+    let input_string = args.option("--input-string");
+
+    if args.flag("--all") {
+        if !args.positional.is_empty()
+            || args.input.is_some()
+            || args.test
+            || input_string.is_some()
+        {
+            return failure(USAGE);
+        }
+        return run_all(year, format.unwrap_or(OutputFormat::Table));
+    }
+    if args.positional == ["status"] {
+        if args.input.is_some() || args.test || input_string.is_some() || format.is_some() {
+            return failure(USAGE);
+        }
+        write_status(
+            year.year,
+            &(year.solvers)(),
+            &year.answers()?,
+            &mut io::stdout().lock(),
+        )?;
+        return Ok(());
+    }
+    let [day, path @ ..] = args.positional.as_slice() else {
+        return failure(USAGE);
+    };
+    let day: u32 = day.parse()?;
+    let solver = (year.solvers)()
+        .get(day)
+        .ok_or(format!("No solver for {} day {day}", year.year))?;
+    let path = match (path, args.input.as_deref()) {
+        ([], input) => input,
+        ([path], None) => Some(path.as_str()),
+        _ => return failure(USAGE),
+    };
+    let input = match (path, input_string, args.test) {
+        (None, Some(input), false) => input.to_string(),
+        (Some(path), None, false) => read_input(path)?,
+        (None, None, true) => year.test_input(day)?,
+        (None, None, false) => year.puzzle_input(day)?,
+        _ => return failure(USAGE),
+    };
+    match format {
+        Some(format) => {
+            let report = solve_timed(day, solver, &input)?;
+            write_reports(&[report], format, None, &mut io::stdout().lock())?;
+            Ok(())
+        }
+        None => run_parts(solver, &input, args.part),
+    }
+}
+
+fn run_all(year: &Year, format: OutputFormat) -> AocResult<()> {
+    let mut days = Vec::new();
+    for (day, solver) in (year.solvers)().iter() {
+        days.push((day, solver, year.puzzle_input(day)?));
+    }
+    let solve = |(day, solver, input): &(u32, &dyn Solver, String)| {
+        solve_timed(*day, *solver, input).map_err(|e| format!("Day {day}: {e}"))
+    };
+
+    let start = Instant::now();
+    #[cfg(feature = "parallel")]
+    let reports = aoc_util::par::par_map(&days, solve);
+    #[cfg(not(feature = "parallel"))]
+    let reports: Vec<_> = days.iter().map(solve).collect();
+    let wall = start.elapsed();
+
+    let reports = reports.into_iter().collect::<Result<Vec<DayReport>, _>>()?;
+    let wall = cfg!(feature = "parallel").then_some(wall);
+    write_reports(&reports, format, wall, &mut io::stdout().lock())?;
+    Ok(())
+}